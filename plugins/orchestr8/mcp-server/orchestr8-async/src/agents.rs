@@ -0,0 +1,320 @@
+use crate::db::{Database, TaskStatus};
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Lifecycle state of a registered agent.
+///
+/// The transitions form a small state machine: an agent is `Registered` when it
+/// first announces itself, becomes `Idle` once it is ready for work, `Busy`
+/// while it holds a task, and `Offline` when its heartbeat lapses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentState {
+    Registered,
+    Idle,
+    Busy,
+    Offline,
+}
+
+impl AgentState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentState::Registered => "registered",
+            AgentState::Idle => "idle",
+            AgentState::Busy => "busy",
+            AgentState::Offline => "offline",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "idle" => AgentState::Idle,
+            "busy" => AgentState::Busy,
+            "offline" => AgentState::Offline,
+            _ => AgentState::Registered,
+        }
+    }
+
+    /// Whether an agent in this state may be dispatched a new task.
+    pub fn accepts_tasks(&self) -> bool {
+        matches!(self, AgentState::Registered | AgentState::Idle)
+    }
+}
+
+/// A named agent and its current lifecycle state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    pub name: String,
+    pub state: AgentState,
+    /// Task the agent is currently executing, if `Busy`.
+    pub current_task: Option<Uuid>,
+    pub last_heartbeat: DateTime<Utc>,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Registry configuration.
+#[derive(Debug, Clone)]
+pub struct AgentConfig {
+    /// How long after its last heartbeat an agent is considered offline.
+    pub heartbeat_timeout_seconds: i64,
+    /// How long after its last heartbeat an offline agent's in-flight task is
+    /// requeued.
+    pub offline_grace_seconds: i64,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_timeout_seconds: 90,
+            offline_grace_seconds: 120,
+        }
+    }
+}
+
+/// Tracks the agent fleet: registration, heartbeats, busy/idle transitions, and
+/// reclamation of work from agents that have gone silent. The scheduler consults
+/// this registry so tasks are only dispatched to healthy, idle agents.
+pub struct AgentRegistry {
+    db: Arc<Database>,
+    config: AgentConfig,
+}
+
+impl AgentRegistry {
+    pub fn new(db: Arc<Database>, config: AgentConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn with_defaults(db: Arc<Database>) -> Self {
+        Self::new(db, AgentConfig::default())
+    }
+
+    /// Register an agent, or reset an existing one to `Registered` and record a
+    /// fresh heartbeat. Returns the agent's current record.
+    pub fn register(&self, name: &str) -> Result<Agent> {
+        let now = Utc::now();
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "INSERT INTO agents (name, state, current_task, last_heartbeat, registered_at)
+             VALUES (?, 'registered', NULL, ?, ?)
+             ON CONFLICT (name) DO UPDATE SET
+                state = 'registered', current_task = NULL, last_heartbeat = excluded.last_heartbeat",
+            duckdb::params![name, now, now],
+        )?;
+
+        info!("Agent '{}' registered", name);
+        self.get(name)?
+            .ok_or_else(|| anyhow::anyhow!("Agent '{}' missing after registration", name))
+    }
+
+    /// Record a heartbeat, bringing a previously-offline agent back to `Idle`.
+    pub fn heartbeat(&self, name: &str) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "UPDATE agents
+             SET last_heartbeat = ?,
+                 state = CASE WHEN state = 'offline' THEN 'idle' ELSE state END
+             WHERE name = ?",
+            duckdb::params![Utc::now(), name],
+        )?;
+        Ok(())
+    }
+
+    /// Mark an agent ready to receive work.
+    pub fn mark_idle(&self, name: &str) -> Result<()> {
+        self.set_state(name, AgentState::Idle, None)
+    }
+
+    /// Mark an agent busy with a specific task.
+    pub fn mark_busy(&self, name: &str, task_id: Uuid) -> Result<()> {
+        self.set_state(name, AgentState::Busy, Some(task_id))
+    }
+
+    fn set_state(&self, name: &str, state: AgentState, task: Option<Uuid>) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "UPDATE agents SET state = ?, current_task = ?, last_heartbeat = ? WHERE name = ?",
+            duckdb::params![
+                state.as_str(),
+                task.map(|t| t.to_string()),
+                Utc::now(),
+                name
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Fetch a single agent by name.
+    pub fn get(&self, name: &str) -> Result<Option<Agent>> {
+        let conn = self.db.get_conn()?;
+        let agent = conn
+            .query_row(
+                "SELECT name, state, current_task, last_heartbeat, registered_at
+                 FROM agents WHERE name = ?",
+                duckdb::params![name],
+                Self::row_to_agent,
+            )
+            .ok();
+        Ok(agent)
+    }
+
+    /// List all agents with their current lifecycle state.
+    pub fn list_agents(&self) -> Result<Vec<Agent>> {
+        let conn = self.db.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, state, current_task, last_heartbeat, registered_at
+             FROM agents ORDER BY registered_at ASC",
+        )?;
+        let rows = stmt.query_map([], Self::row_to_agent)?;
+
+        let mut agents = Vec::new();
+        for row in rows {
+            agents.push(row?);
+        }
+        Ok(agents)
+    }
+
+    fn row_to_agent(row: &duckdb::Row) -> duckdb::Result<Agent> {
+        let current_task: Option<String> = row.get(2)?;
+        Ok(Agent {
+            name: row.get(0)?,
+            state: AgentState::from_str(&row.get::<_, String>(1)?),
+            current_task: current_task.and_then(|t| Uuid::parse_str(&t).ok()),
+            last_heartbeat: row.get(3)?,
+            registered_at: row.get(4)?,
+        })
+    }
+
+    /// Mark agents whose heartbeat has lapsed as `Offline`. Returns the number
+    /// of agents transitioned.
+    pub fn mark_silent_offline(&self) -> Result<usize> {
+        let cutoff = Utc::now() - ChronoDuration::seconds(self.config.heartbeat_timeout_seconds);
+        let conn = self.db.get_conn()?;
+        let changed = conn.execute(
+            "UPDATE agents SET state = 'offline'
+             WHERE state != 'offline' AND last_heartbeat < ?",
+            duckdb::params![cutoff],
+        )?;
+        if changed > 0 {
+            warn!("Marked {} silent agent(s) offline", changed);
+        }
+        Ok(changed)
+    }
+
+    /// Requeue tasks held by offline agents whose last heartbeat is older than
+    /// the grace period, so another healthy agent can pick them up. Returns the
+    /// number of tasks requeued.
+    pub fn requeue_offline_tasks(&self) -> Result<usize> {
+        let cutoff = Utc::now() - ChronoDuration::seconds(self.config.offline_grace_seconds);
+
+        let stranded = {
+            let conn = self.db.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT name, current_task FROM agents
+                 WHERE state = 'offline' AND current_task IS NOT NULL AND last_heartbeat < ?",
+            )?;
+            let rows = stmt.query_map(duckdb::params![cutoff], |row| {
+                let name: String = row.get(0)?;
+                let task: String = row.get(1)?;
+                Ok((name, task))
+            })?;
+            let mut out = Vec::new();
+            for row in rows {
+                out.push(row?);
+            }
+            out
+        };
+
+        let count = stranded.len();
+        for (name, task_str) in stranded {
+            let task_id = Uuid::parse_str(&task_str)?;
+            warn!(
+                "Agent '{}' offline past grace period; requeuing task {}",
+                name, task_id
+            );
+            self.db.update_task_status(task_id, TaskStatus::Pending)?;
+            self.db.add_task_log(
+                task_id,
+                "WARN",
+                &format!("Requeued after agent '{}' went offline", name),
+                None,
+            )?;
+            let conn = self.db.get_conn()?;
+            conn.execute(
+                "UPDATE agents SET current_task = NULL WHERE name = ?",
+                duckdb::params![name],
+            )?;
+        }
+
+        Ok(count)
+    }
+
+    /// Start the background reaper that marks silent agents offline and requeues
+    /// the tasks of agents that have been offline past the grace period.
+    pub async fn start_reaper(&self) -> Result<()> {
+        info!("Starting agent health reaper");
+        let registry = Arc::new(Self::new(Arc::clone(&self.db), self.config.clone()));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(15)).await;
+                if let Err(e) = registry.mark_silent_offline() {
+                    warn!("Error marking silent agents offline: {}", e);
+                }
+                if let Err(e) = registry.requeue_offline_tasks() {
+                    warn!("Error requeuing offline agent tasks: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> AgentRegistry {
+        let db = Arc::new(Database::in_memory().unwrap());
+        AgentRegistry::with_defaults(db)
+    }
+
+    #[test]
+    fn test_register_and_list() {
+        let reg = registry();
+        let agent = reg.register("researcher").unwrap();
+        assert_eq!(agent.state, AgentState::Registered);
+
+        let agents = reg.list_agents().unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].name, "researcher");
+    }
+
+    #[test]
+    fn test_state_transitions_gate_dispatch() {
+        let reg = registry();
+        reg.register("coder").unwrap();
+        assert!(reg.db.agent_accepts_tasks("coder").unwrap());
+
+        let task_id = Uuid::new_v4();
+        reg.mark_busy("coder", task_id).unwrap();
+        let agent = reg.get("coder").unwrap().unwrap();
+        assert_eq!(agent.state, AgentState::Busy);
+        assert_eq!(agent.current_task, Some(task_id));
+        assert!(!reg.db.agent_accepts_tasks("coder").unwrap());
+
+        reg.mark_idle("coder").unwrap();
+        assert!(reg.db.agent_accepts_tasks("coder").unwrap());
+    }
+
+    #[test]
+    fn test_unknown_agent_accepts_tasks() {
+        let reg = registry();
+        assert!(reg.db.agent_accepts_tasks("never-registered").unwrap());
+    }
+}
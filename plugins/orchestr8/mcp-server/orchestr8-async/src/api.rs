@@ -1,15 +1,29 @@
-use crate::db::{AsyncTask, Database, TaskPriority, TaskStatus};
+use crate::agents::AgentRegistry;
+use crate::db::{AsyncTask, Database, TaskEvent, TaskPriority, TaskStatus};
+use crate::artifacts::ArtifactStore;
 use crate::queue::{TaskQueue, WorkflowExecutor, WorkflowStatus};
+use crate::runner::RunnerCoordinator;
+use crate::usage::{UsageMeter, UsageQuery};
 use crate::webhook::WebhookManager;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Json, Response},
+    body::StreamBody,
+    extract::{BodyStream, Path, Query, State},
+    http::{header, Request, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
     routing::{delete, get, post},
     Router,
 };
+use futures::stream::{self, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::io::ReaderStream;
 use tower_http::cors::CorsLayer;
 use tracing::info;
 use uuid::Uuid;
@@ -21,6 +35,86 @@ pub struct ApiState {
     pub queue: Arc<TaskQueue>,
     pub executor: Arc<WorkflowExecutor>,
     pub webhook_manager: Arc<WebhookManager>,
+    pub runner: Arc<RunnerCoordinator>,
+    pub artifacts: Arc<ArtifactStore>,
+    pub agents: Arc<AgentRegistry>,
+    pub usage: Arc<UsageMeter>,
+    pub security: Arc<ApiSecurity>,
+}
+
+/// HTTP edge security policy: an optional shared-secret bearer token guarding
+/// every `/api/*` route and a CORS origin allow-list. Both default to "off" so
+/// a localhost deployment keeps working with no configuration.
+#[derive(Debug, Clone, Default)]
+pub struct ApiSecurity {
+    /// Shared secrets accepted in `Authorization: Bearer <token>`. Empty
+    /// leaves the API unauthenticated (backward-compatible localhost
+    /// behavior); any other request must match one of these.
+    pub tokens: Vec<String>,
+    /// Origins permitted by CORS. Empty means permissive (any origin).
+    pub allowed_origins: Vec<String>,
+}
+
+impl ApiSecurity {
+    /// An open policy: no token required and any origin allowed.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Read the policy from the environment: `ORCHESTR8_API_TOKEN` sets one
+    /// or more comma-separated shared secrets and `ORCHESTR8_ALLOWED_ORIGINS`
+    /// a comma-separated origin allow-list.
+    pub fn from_env() -> Self {
+        let tokens = std::env::var("ORCHESTR8_API_TOKEN")
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let allowed_origins = std::env::var("ORCHESTR8_ALLOWED_ORIGINS")
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            tokens,
+            allowed_origins,
+        }
+    }
+
+    /// Whether bearer-token authentication is enforced.
+    pub fn requires_auth(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Check a presented token against every configured secret in constant
+    /// time, so a timing side-channel can't be used to guess a valid token
+    /// byte by byte.
+    pub fn verify(&self, presented: &str) -> bool {
+        self.tokens
+            .iter()
+            .any(|expected| constant_time_eq(expected, presented))
+    }
+}
+
+/// Compare two strings in constant time with respect to their shared length.
+/// Unequal lengths short-circuit (length isn't the secret), but once lengths
+/// match, every byte is compared regardless of earlier mismatches.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
 }
 
 /// API error response
@@ -28,11 +122,29 @@ pub struct ApiState {
 pub struct ApiError {
     pub error: String,
     pub message: String,
+    /// HTTP status to respond with. Defaults to 500; not part of the wire body.
+    #[serde(skip, default = "default_error_status")]
+    pub status: StatusCode,
+}
+
+fn default_error_status() -> StatusCode {
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+impl ApiError {
+    /// A 401 response for a missing or mismatched bearer token.
+    pub fn unauthorized() -> Self {
+        ApiError {
+            error: "unauthorized".to_string(),
+            message: "missing or invalid bearer token".to_string(),
+            status: StatusCode::UNAUTHORIZED,
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(self)).into_response()
+        (self.status, Json(self)).into_response()
     }
 }
 
@@ -41,6 +153,7 @@ impl From<anyhow::Error> for ApiError {
         ApiError {
             error: "internal_error".to_string(),
             message: err.to_string(),
+            status: StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -54,7 +167,14 @@ pub fn create_router(state: ApiState) -> Router {
         .route("/api/tasks/:id", delete(cancel_task))
         .route("/api/tasks/:id/retry", post(retry_task))
         .route("/api/tasks/:id/logs", get(get_task_logs))
+        .route("/api/tasks/:id/stream", get(stream_task_events))
         .route("/api/tasks", get(list_tasks))
+        // JSON-RPC 2.0 batch endpoint (compact multi-op protocol)
+        .route("/api/rpc", post(crate::rpc::rpc_endpoint))
+        // Artifact endpoints (out-of-band task outputs)
+        .route("/api/tasks/:id/artifacts", get(list_task_artifacts))
+        .route("/api/tasks/:id/artifacts/:name", post(upload_artifact))
+        .route("/api/tasks/:id/artifacts/:name", get(download_artifact))
         // Workflow endpoints
         .route("/api/workflows", post(create_workflow))
         .route("/api/workflows/:id", get(get_workflow))
@@ -65,12 +185,78 @@ pub fn create_router(state: ApiState) -> Router {
         .route("/api/workflows/:id/tasks", get(get_workflow_tasks))
         // Webhook endpoints
         .route("/api/webhooks/:task_id/history", get(get_webhook_history))
+        // Runner (distributed worker) endpoints
+        .route("/api/runners", post(register_runner))
+        .route("/api/runners/register", post(register_runner))
+        .route("/api/runners", get(list_runners))
+        .route("/api/runners/:id/heartbeat", post(runner_heartbeat))
+        .route("/api/runners/:id/claim", post(claim_task))
+        .route("/api/runners/:id/poll", post(claim_task))
+        .route("/api/runners/:id/tasks/:task_id/logs", post(stream_task_log))
+        .route("/api/runners/:id/tasks/:task_id/result", post(submit_task_result))
+        .route("/api/tasks/:id/result", post(submit_task_result_direct))
+        // Agent lifecycle / fleet health endpoints
+        .route("/api/agents", post(register_agent))
+        .route("/api/agents", get(list_agents))
+        .route("/api/agents/:name/heartbeat", post(agent_heartbeat))
+        // Usage metering / cost estimation
+        .route("/api/usage", get(get_usage))
+        .route("/api/tasks/:id/usage", get(get_task_usage))
         // Health check
         .route("/health", get(health_check))
-        .layer(CorsLayer::permissive())
+        .layer(middleware::from_fn_with_state(
+            state.security.clone(),
+            require_auth,
+        ))
+        .layer(cors_layer(&state.security))
         .with_state(state)
 }
 
+/// Build the CORS layer from the security policy: an explicit origin allow-list
+/// when one is configured, otherwise the permissive default used on localhost.
+fn cors_layer(security: &ApiSecurity) -> CorsLayer {
+    if security.allowed_origins.is_empty() {
+        return CorsLayer::permissive();
+    }
+    let origins: Vec<header::HeaderValue> = security
+        .allowed_origins
+        .iter()
+        .filter_map(|o| o.parse().ok())
+        .collect();
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(tower_http::cors::Any)
+        .allow_headers(tower_http::cors::Any)
+}
+
+/// Reject any `/api/*` request that lacks a matching `Authorization: Bearer`
+/// token. `/health` and all non-`/api` routes stay open, and the whole check is
+/// skipped when no shared secret is configured.
+async fn require_auth<B>(
+    State(security): State<Arc<ApiSecurity>>,
+    req: Request<B>,
+    next: Next<B>,
+) -> Result<Response, ApiError> {
+    if !security.requires_auth() {
+        return Ok(next.run(req).await);
+    }
+
+    if !req.uri().path().starts_with("/api/") {
+        return Ok(next.run(req).await);
+    }
+
+    let presented = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    match presented {
+        Some(token) if security.verify(token) => Ok(next.run(req).await),
+        _ => Err(ApiError::unauthorized()),
+    }
+}
+
 // ===== Task Endpoints =====
 
 /// Request to create a new task
@@ -87,6 +273,43 @@ pub struct CreateTaskRequest {
     pub metadata: Option<serde_json::Value>,
 }
 
+impl CreateTaskRequest {
+    /// Build an [`AsyncTask`] from the request fields, applying optional
+    /// priority, dependencies, webhook, timeout, and metadata.
+    pub fn into_task(self) -> AsyncTask {
+        let mut task = AsyncTask::new(self.name, self.agent_name, self.agent_instructions);
+        task.description = self.description;
+        if let Some(priority_str) = self.priority {
+            task.priority = parse_priority(&priority_str);
+        }
+        if let Some(deps) = self.dependencies {
+            task.dependencies = deps;
+        }
+        if let Some(webhook) = self.webhook_url {
+            task.webhook_url = Some(webhook);
+        }
+        if let Some(timeout) = self.timeout_seconds {
+            task.timeout_seconds = Some(timeout);
+        }
+        if let Some(metadata) = self.metadata {
+            task.metadata = Some(metadata.to_string());
+        }
+        task
+    }
+}
+
+/// Parse a priority string, defaulting to [`TaskPriority::Normal`] for unknown
+/// values.
+pub(crate) fn parse_priority(priority: &str) -> TaskPriority {
+    match priority {
+        "low" => TaskPriority::Low,
+        "normal" => TaskPriority::Normal,
+        "high" => TaskPriority::High,
+        "critical" => TaskPriority::Critical,
+        _ => TaskPriority::Normal,
+    }
+}
+
 /// Response for task creation
 #[derive(Debug, Serialize)]
 pub struct CreateTaskResponse {
@@ -95,50 +318,30 @@ pub struct CreateTaskResponse {
     pub message: String,
 }
 
-async fn create_task(
-    State(state): State<ApiState>,
-    Json(req): Json<CreateTaskRequest>,
-) -> Result<Json<CreateTaskResponse>, ApiError> {
+/// Create and enqueue a task, shared by the REST and JSON-RPC surfaces.
+pub(crate) async fn create_task_inner(
+    state: &ApiState,
+    req: CreateTaskRequest,
+) -> Result<CreateTaskResponse, ApiError> {
     info!("Creating task: {}", req.name);
 
-    let mut task = AsyncTask::new(req.name, req.agent_name, req.agent_instructions);
-
-    task.description = req.description;
-
-    if let Some(priority_str) = req.priority {
-        task.priority = match priority_str.as_str() {
-            "low" => TaskPriority::Low,
-            "normal" => TaskPriority::Normal,
-            "high" => TaskPriority::High,
-            "critical" => TaskPriority::Critical,
-            _ => TaskPriority::Normal,
-        };
-    }
-
-    if let Some(deps) = req.dependencies {
-        task.dependencies = deps;
-    }
-
-    if let Some(webhook) = req.webhook_url {
-        task.webhook_url = Some(webhook);
-    }
-
-    if let Some(timeout) = req.timeout_seconds {
-        task.timeout_seconds = Some(timeout);
-    }
-
-    if let Some(metadata) = req.metadata {
-        task.metadata = Some(metadata.to_string());
-    }
+    let task = req.into_task();
 
     state.db.insert_task(&task)?;
     state.queue.submit_task(task.id)?;
 
-    Ok(Json(CreateTaskResponse {
+    Ok(CreateTaskResponse {
         task_id: task.id,
         status: task.status.as_str().to_string(),
         message: "Task created and queued for execution".to_string(),
-    }))
+    })
+}
+
+async fn create_task(
+    State(state): State<ApiState>,
+    Json(req): Json<CreateTaskRequest>,
+) -> Result<Json<CreateTaskResponse>, ApiError> {
+    Ok(Json(create_task_inner(&state, req).await?))
 }
 
 /// Response for task details
@@ -183,41 +386,76 @@ async fn retry_task(
     })))
 }
 
+/// Default page size for `GET /api/tasks` when `limit` is omitted.
+const DEFAULT_LIST_TASKS_LIMIT: usize = 100;
+
 /// Query parameters for listing tasks
 #[derive(Debug, Deserialize)]
 pub struct ListTasksQuery {
     pub status: Option<String>,
     pub workflow_id: Option<Uuid>,
+    pub agent_name: Option<String>,
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub min_priority: Option<String>,
+    /// One of `created_at`, `priority`, `status`. Defaults to `created_at`.
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub descending: bool,
     pub limit: Option<usize>,
+    /// Offset to resume from; echoes a prior response's `next_cursor`.
+    pub cursor: Option<usize>,
 }
 
 /// Response for listing tasks
 #[derive(Debug, Serialize)]
 pub struct ListTasksResponse {
     pub tasks: Vec<AsyncTask>,
+    /// Total tasks matching the filter, ignoring pagination.
     pub total: usize,
+    /// Offset to pass back as `cursor` for the next page, `None` when exhausted.
+    pub next_cursor: Option<usize>,
 }
 
 async fn list_tasks(
     State(state): State<ApiState>,
     Query(query): Query<ListTasksQuery>,
 ) -> Result<Json<ListTasksResponse>, ApiError> {
-    let tasks = if let Some(workflow_id) = query.workflow_id {
-        state.db.get_workflow_tasks(workflow_id)?
-    } else if let Some(status_str) = query.status {
-        if status_str == "pending" {
-            state.db.get_pending_tasks(query.limit.unwrap_or(100))?
-        } else {
-            // For other statuses, we'd need to add more DB methods
-            Vec::new()
-        }
-    } else {
-        state.db.get_pending_tasks(query.limit.unwrap_or(100))?
+    let filter = crate::db::TaskQueryFilter {
+        status: query
+            .status
+            .as_deref()
+            .map(|s| {
+                TaskStatus::from_str(s)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown task status '{}'", s))
+            })
+            .transpose()?,
+        workflow_id: query.workflow_id,
+        agent_name: query.agent_name,
+        created_after: query.created_after,
+        created_before: query.created_before,
+        min_priority: query.min_priority.as_deref().map(parse_priority),
+        sort_key: query
+            .sort
+            .as_deref()
+            .map(|s| {
+                crate::db::TaskSortKey::from_str(s)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown sort key '{}'", s))
+            })
+            .transpose()?
+            .unwrap_or_default(),
+        descending: query.descending,
+        limit: query.limit.unwrap_or(DEFAULT_LIST_TASKS_LIMIT),
+        offset: query.cursor.unwrap_or(0),
     };
 
-    let total = tasks.len();
+    let page = state.db.query_tasks(&filter)?;
 
-    Ok(Json(ListTasksResponse { tasks, total }))
+    Ok(Json(ListTasksResponse {
+        tasks: page.tasks,
+        total: page.total,
+        next_cursor: page.next_cursor,
+    }))
 }
 
 /// Response for task logs
@@ -255,6 +493,117 @@ async fn get_task_logs(
     }))
 }
 
+/// Stream a task's logs and status transitions as Server-Sent Events. The
+/// response opens with the backlog already recorded for the task (as `log`
+/// events) and then tails new events live, emitting `log` and `status` events
+/// until the task reaches a terminal status, at which point the stream ends.
+async fn stream_task_events(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before snapshotting the backlog so no event emitted during the
+    // read slips through the gap between snapshot and live tail.
+    let rx = state.db.subscribe_task_events();
+
+    let backlog = state.db.get_task_logs(id).unwrap_or_default();
+    let backlog_stream = stream::iter(backlog.into_iter().map(move |(_ts, level, message)| {
+        Ok(event_to_sse(&TaskEvent::Log {
+            task_id: id,
+            level,
+            message,
+        }))
+    }));
+
+    // Live tail filtered to this task; the stream terminates once a terminal
+    // status event is observed so dashboards close the connection cleanly.
+    let live = stream::unfold(Some(rx), move |slot| async move {
+        let mut rx = slot?;
+        loop {
+            match rx.recv().await {
+                Ok(event) if event.task_id() == id => {
+                    let done = matches!(
+                        &event,
+                        TaskEvent::Status { status, .. } if status.is_terminal()
+                    );
+                    let sse = event_to_sse(&event);
+                    return Some((Ok(sse), if done { None } else { Some(rx) }));
+                }
+                // Events for other tasks, or a lagged subscriber: keep tailing.
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(backlog_stream.chain(live))
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+/// Render a [`TaskEvent`] as an SSE event named `log` or `status` with the
+/// event serialized as the JSON data payload.
+fn event_to_sse(event: &TaskEvent) -> Event {
+    let name = match event {
+        TaskEvent::Log { .. } => "log",
+        TaskEvent::Status { .. } => "status",
+    };
+    Event::default()
+        .event(name)
+        .json_data(event)
+        .unwrap_or_else(|_| Event::default().event(name))
+}
+
+// ===== Artifact Endpoints =====
+
+/// Stream an artifact up for a task. The artifact name comes from the path and
+/// the request body is streamed chunk-by-chunk to disk, so large uploads never
+/// buffer in memory; `Content-Type` is recorded verbatim.
+async fn upload_artifact(
+    State(state): State<ApiState>,
+    Path((id, name)): Path<(Uuid, String)>,
+    headers: header::HeaderMap,
+    body: BodyStream,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+
+    let reference = state
+        .artifacts
+        .store_stream(id, &name, &content_type, body)
+        .await?;
+    Ok(Json(serde_json::json!({ "artifact": reference })))
+}
+
+/// Stream an artifact's bytes back to the caller without buffering the whole
+/// file in memory.
+async fn download_artifact(
+    State(state): State<ApiState>,
+    Path((id, name)): Path<(Uuid, String)>,
+) -> Result<Response, ApiError> {
+    let (content_type, _size, file) = state.artifacts.open_read(id, &name).await?;
+    let stream = ReaderStream::new(file);
+    let body = StreamBody::new(stream);
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        body,
+    )
+        .into_response())
+}
+
+/// List the artifact references recorded for a task.
+async fn list_task_artifacts(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let artifacts = state.artifacts.list_for_task(id)?;
+    Ok(Json(serde_json::json!({
+        "task_id": id,
+        "artifacts": artifacts
+    })))
+}
+
 // ===== Workflow Endpoints =====
 
 /// Request to create a workflow
@@ -353,36 +702,41 @@ pub struct AddWorkflowTaskRequest {
     pub webhook_url: Option<String>,
 }
 
+impl AddWorkflowTaskRequest {
+    /// Build the [`AsyncTask`] for this workflow-task request, returning it with
+    /// the target phase id.
+    pub fn into_task(self) -> (String, AsyncTask) {
+        let mut task = AsyncTask::new(self.name, self.agent_name, self.agent_instructions);
+        task.description = self.description;
+        if let Some(priority_str) = self.priority {
+            task.priority = parse_priority(&priority_str);
+        }
+        if let Some(deps) = self.dependencies {
+            task.dependencies = deps;
+        }
+        if let Some(webhook) = self.webhook_url {
+            task.webhook_url = Some(webhook);
+        }
+        (self.phase_id, task)
+    }
+}
+
+/// Add a task to a workflow phase, shared by the REST and JSON-RPC surfaces.
+pub(crate) async fn add_workflow_task_inner(
+    state: &ApiState,
+    workflow_id: Uuid,
+    req: AddWorkflowTaskRequest,
+) -> Result<Uuid, ApiError> {
+    let (phase_id, task) = req.into_task();
+    Ok(state.executor.add_phase_task(workflow_id, phase_id, task)?)
+}
+
 async fn add_workflow_task(
     State(state): State<ApiState>,
     Path(workflow_id): Path<Uuid>,
     Json(req): Json<AddWorkflowTaskRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let mut task = AsyncTask::new(req.name, req.agent_name, req.agent_instructions);
-
-    task.description = req.description;
-
-    if let Some(priority_str) = req.priority {
-        task.priority = match priority_str.as_str() {
-            "low" => TaskPriority::Low,
-            "normal" => TaskPriority::Normal,
-            "high" => TaskPriority::High,
-            "critical" => TaskPriority::Critical,
-            _ => TaskPriority::Normal,
-        };
-    }
-
-    if let Some(deps) = req.dependencies {
-        task.dependencies = deps;
-    }
-
-    if let Some(webhook) = req.webhook_url {
-        task.webhook_url = Some(webhook);
-    }
-
-    let task_id = state
-        .executor
-        .add_phase_task(workflow_id, req.phase_id, task)?;
+    let task_id = add_workflow_task_inner(&state, workflow_id, req).await?;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -415,6 +769,171 @@ async fn get_webhook_history(
     })))
 }
 
+// ===== Runner Endpoints =====
+
+/// Request to register a new runner
+#[derive(Debug, Deserialize)]
+pub struct RegisterRunnerRequest {
+    pub name: String,
+}
+
+async fn register_runner(
+    State(state): State<ApiState>,
+    Json(req): Json<RegisterRunnerRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let runner = state.runner.register(&req.name)?;
+    Ok(Json(serde_json::json!({ "runner": runner })))
+}
+
+async fn list_runners(
+    State(state): State<ApiState>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let runners = state.runner.list_runners()?;
+    Ok(Json(serde_json::json!({ "runners": runners })))
+}
+
+async fn runner_heartbeat(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.runner.heartbeat(id)?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+async fn claim_task(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    match state.runner.claim_task(id)? {
+        Some(task) => Ok(Json(serde_json::json!({ "task": task })).into_response()),
+        // Nothing ready: the runner should back off and poll again.
+        None => Ok(StatusCode::NO_CONTENT.into_response()),
+    }
+}
+
+/// A log chunk streamed back from a runner mid-execution
+#[derive(Debug, Deserialize)]
+pub struct StreamLogRequest {
+    pub chunk: String,
+}
+
+async fn stream_task_log(
+    State(state): State<ApiState>,
+    Path((id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<StreamLogRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.runner.append_log(task_id, id, &req.chunk)?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Final result or error submitted by a runner
+#[derive(Debug, Deserialize)]
+pub struct SubmitResultRequest {
+    pub success: bool,
+    pub result: Option<String>,
+    pub error: Option<String>,
+}
+
+async fn submit_task_result(
+    State(state): State<ApiState>,
+    Path((id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<SubmitResultRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .runner
+        .submit_result(task_id, id, req.success, req.result, req.error)?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Final result submitted against the task directly, carrying the holding
+/// runner's id in the body. This is the task-centric counterpart to the
+/// runner-scoped [`submit_task_result`] endpoint.
+#[derive(Debug, Deserialize)]
+pub struct SubmitResultDirectRequest {
+    pub runner_id: Uuid,
+    pub success: bool,
+    pub result: Option<String>,
+    pub error: Option<String>,
+}
+
+async fn submit_task_result_direct(
+    State(state): State<ApiState>,
+    Path(task_id): Path<Uuid>,
+    Json(req): Json<SubmitResultDirectRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .runner
+        .submit_result(task_id, req.runner_id, req.success, req.result, req.error)?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// ===== Agent Endpoints =====
+
+/// Request to register an agent in the fleet.
+#[derive(Debug, Deserialize)]
+pub struct RegisterAgentRequest {
+    pub name: String,
+}
+
+async fn register_agent(
+    State(state): State<ApiState>,
+    Json(req): Json<RegisterAgentRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let agent = state.agents.register(&req.name)?;
+    Ok(Json(serde_json::json!({ "agent": agent })))
+}
+
+async fn list_agents(
+    State(state): State<ApiState>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let agents = state.agents.list_agents()?;
+    Ok(Json(serde_json::json!({ "agents": agents })))
+}
+
+async fn agent_heartbeat(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.agents.heartbeat(&name)?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// ===== Usage Endpoints =====
+
+/// Query parameters for `GET /api/usage`.
+#[derive(Debug, Deserialize)]
+pub struct UsageQueryParams {
+    pub workflow_id: Option<Uuid>,
+    pub agent_name: Option<String>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+async fn get_usage(
+    State(state): State<ApiState>,
+    Query(params): Query<UsageQueryParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let totals = state.usage.query(&UsageQuery {
+        workflow_id: params.workflow_id,
+        agent_name: params.agent_name,
+        since: params.since,
+    })?;
+    Ok(Json(serde_json::json!({
+        "rates": state.usage.rates(),
+        "totals": totals,
+    })))
+}
+
+async fn get_task_usage(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let report = state
+        .usage
+        .task_usage(id)?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    Ok(Json(serde_json::json!({ "usage": report })))
+}
+
 // ===== Health Check =====
 
 async fn health_check() -> Json<serde_json::Value> {
@@ -439,12 +958,21 @@ mod tests {
         let webhook_manager = Arc::new(
             WebhookManager::new(db.clone(), WebhookConfig::default()).unwrap(),
         );
+        let runner = Arc::new(RunnerCoordinator::with_defaults(db.clone()));
+        let artifacts = Arc::new(ArtifactStore::with_defaults(db.clone()));
+        let agents = Arc::new(AgentRegistry::with_defaults(db.clone()));
+        let usage = Arc::new(UsageMeter::with_defaults(db.clone()));
 
         ApiState {
             db,
             queue,
             executor,
             webhook_manager,
+            runner,
+            artifacts,
+            agents,
+            usage,
+            security: Arc::new(ApiSecurity::disabled()),
         }
     }
 
@@ -465,4 +993,94 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_bearer_auth_guards_api_but_not_health() {
+        let mut state = create_test_state().await;
+        state.security = Arc::new(ApiSecurity {
+            tokens: vec!["s3cret".to_string()],
+            allowed_origins: Vec::new(),
+        });
+        let app = create_router(state);
+
+        // /health stays open.
+        let health = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+
+        // /api without a token is rejected.
+        let missing = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/tasks")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+
+        // /api with the right token passes through.
+        let ok = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/tasks")
+                    .header(header::AUTHORIZATION, "Bearer s3cret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_batch_create_tasks() {
+        let state = create_test_state().await;
+        let app = create_router(state);
+
+        // A batch with two task.create calls and one notification (no id).
+        let body = serde_json::json!([
+            {"jsonrpc": "2.0", "method": "task.create", "id": 1,
+             "params": {"name": "a", "agent_name": "agent", "agent_instructions": "do a"}},
+            {"jsonrpc": "2.0", "method": "task.create",
+             "params": {"name": "b", "agent_name": "agent", "agent_instructions": "do b"}},
+            {"jsonrpc": "2.0", "method": "task.create", "id": 2,
+             "params": {"name": "c", "agent_name": "agent", "agent_instructions": "do c"}}
+        ]);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/rpc")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        // The notification produced no response; only the two id'd requests did.
+        let arr = parsed.as_array().unwrap();
+        assert_eq!(arr.len(), 2);
+        for resp in arr {
+            assert!(resp.get("result").and_then(|r| r.get("task_id")).is_some());
+        }
+    }
 }
@@ -0,0 +1,340 @@
+use crate::db::Database;
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A reference to a stored artifact, safe to embed in a webhook payload in
+/// place of inlining a large or binary result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactReference {
+    pub name: String,
+    pub size: u64,
+    pub content_type: String,
+    /// Hex-encoded SHA-256 of the stored bytes.
+    pub checksum: String,
+    /// Download URL served by the artifact endpoints on `ApiState`.
+    pub url: String,
+}
+
+/// Artifact store configuration.
+#[derive(Debug, Clone)]
+pub struct ArtifactConfig {
+    /// Root directory under which per-task artifact directories are created.
+    pub root_dir: PathBuf,
+    /// Base URL used to construct download references (no trailing slash).
+    pub base_url: String,
+    /// Age past a task's artifact creation after which GC sweeps it.
+    pub retention_seconds: i64,
+}
+
+impl Default for ArtifactConfig {
+    fn default() -> Self {
+        Self {
+            root_dir: PathBuf::from("./artifacts"),
+            base_url: "http://localhost:3000".to_string(),
+            retention_seconds: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Stores large or binary task outputs on disk, recording metadata in the
+/// `artifacts` table and serving them back through streaming endpoints.
+pub struct ArtifactStore {
+    db: Arc<Database>,
+    config: ArtifactConfig,
+}
+
+impl ArtifactStore {
+    pub fn new(db: Arc<Database>, config: ArtifactConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn with_defaults(db: Arc<Database>) -> Self {
+        Self::new(db, ArtifactConfig::default())
+    }
+
+    /// Directory reserved for a task's artifacts.
+    fn task_dir(&self, task_id: Uuid) -> PathBuf {
+        self.config.root_dir.join(task_id.to_string())
+    }
+
+    /// Reserve (create if absent) the per-task artifact directory and return
+    /// its path. Tolerates an already-existing directory so concurrent uploads
+    /// for the same task don't race.
+    pub fn reserve_artifact_dir(&self, task_id: Uuid) -> Result<PathBuf> {
+        let dir = self.task_dir(task_id);
+        match std::fs::create_dir_all(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("creating artifact dir {}", dir.display()))
+            }
+        }
+        Ok(dir)
+    }
+
+    fn download_url(&self, task_id: Uuid, name: &str) -> String {
+        format!("{}/api/tasks/{}/artifacts/{}", self.config.base_url, task_id, name)
+    }
+
+    /// Persist an artifact for a task and return a reference to it.
+    pub fn store(
+        &self,
+        task_id: Uuid,
+        name: &str,
+        content_type: &str,
+        bytes: &[u8],
+    ) -> Result<ArtifactReference> {
+        let dir = self.task_dir(task_id);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating artifact dir {}", dir.display()))?;
+
+        let path = dir.join(name);
+        std::fs::write(&path, bytes)
+            .with_context(|| format!("writing artifact {}", path.display()))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        let checksum: String = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        let size = bytes.len() as u64;
+
+        self.record_metadata(task_id, name, content_type, size, &checksum, &path)?;
+
+        info!("Stored artifact '{}' ({} bytes) for task {}", name, size, task_id);
+        Ok(ArtifactReference {
+            name: name.to_string(),
+            size,
+            content_type: content_type.to_string(),
+            checksum,
+            url: self.download_url(task_id, name),
+        })
+    }
+
+    /// Persist an artifact by streaming a chunked body directly to disk,
+    /// hashing and sizing incrementally so arbitrarily large outputs never need
+    /// to be buffered in memory. Records the same metadata as [`store`].
+    ///
+    /// [`store`]: Self::store
+    pub async fn store_stream<S, E>(
+        &self,
+        task_id: Uuid,
+        name: &str,
+        content_type: &str,
+        mut stream: S,
+    ) -> Result<ArtifactReference>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        let dir = self.reserve_artifact_dir(task_id)?;
+        let path = dir.join(name);
+
+        let mut file = tokio::fs::File::create(&path)
+            .await
+            .with_context(|| format!("creating artifact {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        let mut size: u64 = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| anyhow::anyhow!("reading upload stream: {e}"))?;
+            hasher.update(&chunk);
+            size += chunk.len() as u64;
+            file.write_all(&chunk)
+                .await
+                .with_context(|| format!("writing artifact {}", path.display()))?;
+        }
+        file.flush().await?;
+
+        let checksum: String = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        self.record_metadata(task_id, name, content_type, size, &checksum, &path)?;
+
+        info!("Streamed artifact '{}' ({} bytes) for task {}", name, size, task_id);
+        Ok(ArtifactReference {
+            name: name.to_string(),
+            size,
+            content_type: content_type.to_string(),
+            checksum,
+            url: self.download_url(task_id, name),
+        })
+    }
+
+    /// Insert (replacing any same-named prior) the metadata row for an
+    /// artifact, so listings survive restarts.
+    fn record_metadata(
+        &self,
+        task_id: Uuid,
+        name: &str,
+        content_type: &str,
+        size: u64,
+        checksum: &str,
+        path: &Path,
+    ) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        // Replace any prior artifact with the same name for this task.
+        conn.execute(
+            "DELETE FROM artifacts WHERE task_id = ? AND name = ?",
+            duckdb::params![task_id.to_string(), name],
+        )?;
+        conn.execute(
+            "INSERT INTO artifacts (task_id, name, content_type, size, checksum, path, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            duckdb::params![
+                task_id.to_string(),
+                name,
+                content_type,
+                size as i64,
+                checksum,
+                path.to_string_lossy().to_string(),
+                Utc::now(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Open an artifact for streaming download, returning its content type,
+    /// size, and an async file handle the caller can wrap in a response stream
+    /// — the bytes are never buffered in memory.
+    pub async fn open_read(&self, task_id: Uuid, name: &str) -> Result<(String, u64, tokio::fs::File)> {
+        let (content_type, path, size): (String, String, i64) = {
+            let conn = self.db.get_conn()?;
+            conn.query_row(
+                "SELECT content_type, path, size FROM artifacts WHERE task_id = ? AND name = ?",
+                duckdb::params![task_id.to_string(), name],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .with_context(|| format!("artifact '{}' not found for task {}", name, task_id))?
+        };
+
+        let file = tokio::fs::File::open(&path)
+            .await
+            .with_context(|| format!("opening artifact {}", path))?;
+        Ok((content_type, size as u64, file))
+    }
+
+    /// Read an artifact's bytes and content type back.
+    pub fn load(&self, task_id: Uuid, name: &str) -> Result<(String, Vec<u8>)> {
+        let conn = self.db.get_conn()?;
+        let (content_type, path): (String, String) = conn
+            .query_row(
+                "SELECT content_type, path FROM artifacts WHERE task_id = ? AND name = ?",
+                duckdb::params![task_id.to_string(), name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .with_context(|| format!("artifact '{}' not found for task {}", name, task_id))?;
+
+        let bytes = std::fs::read(&path)
+            .with_context(|| format!("reading artifact {}", path))?;
+        Ok((content_type, bytes))
+    }
+
+    /// List artifact references recorded for a task.
+    pub fn list_for_task(&self, task_id: Uuid) -> Result<Vec<ArtifactReference>> {
+        let conn = self.db.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, size, content_type, checksum FROM artifacts WHERE task_id = ? ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map(duckdb::params![task_id.to_string()], |row| {
+            let name: String = row.get(0)?;
+            let size: i64 = row.get(1)?;
+            let content_type: String = row.get(2)?;
+            let checksum: String = row.get(3)?;
+            Ok((name, size, content_type, checksum))
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let (name, size, content_type, checksum) = row?;
+            let url = self.download_url(task_id, &name);
+            out.push(ArtifactReference {
+                name,
+                size: size as u64,
+                content_type,
+                checksum,
+                url,
+            });
+        }
+        Ok(out)
+    }
+
+    /// Sweep artifacts older than the configured retention, removing both the
+    /// files and their metadata rows. Returns the number of artifacts removed.
+    pub fn gc(&self) -> Result<usize> {
+        let cutoff: DateTime<Utc> =
+            Utc::now() - ChronoDuration::seconds(self.config.retention_seconds);
+
+        let stale = {
+            let conn = self.db.get_conn()?;
+            let mut stmt =
+                conn.prepare("SELECT path FROM artifacts WHERE created_at < ?")?;
+            let rows = stmt.query_map(duckdb::params![cutoff], |row| {
+                let path: String = row.get(0)?;
+                Ok(path)
+            })?;
+            let mut out = Vec::new();
+            for row in rows {
+                out.push(row?);
+            }
+            out
+        };
+
+        for path in &stale {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!("Failed to remove stale artifact {}: {}", path, e);
+            }
+        }
+
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "DELETE FROM artifacts WHERE created_at < ?",
+            duckdb::params![cutoff],
+        )?;
+
+        Ok(stale.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (ArtifactStore, PathBuf) {
+        let db = Arc::new(Database::in_memory().unwrap());
+        // Isolate each test under a unique-ish temp dir derived from a uuid.
+        let dir = std::env::temp_dir().join(format!("orchestr8-artifacts-{}", Uuid::new_v4()));
+        let config = ArtifactConfig {
+            root_dir: dir.clone(),
+            base_url: "http://localhost:3000".to_string(),
+            retention_seconds: 3600,
+        };
+        (ArtifactStore::new(db, config), dir)
+    }
+
+    #[test]
+    fn test_store_and_load_roundtrip() {
+        let (store, dir) = store();
+        let task_id = Uuid::new_v4();
+
+        let reference = store
+            .store(task_id, "out.txt", "text/plain", b"hello")
+            .unwrap();
+        assert_eq!(reference.size, 5);
+        assert_eq!(reference.checksum.len(), 64);
+
+        let (content_type, bytes) = store.load(task_id, "out.txt").unwrap();
+        assert_eq!(content_type, "text/plain");
+        assert_eq!(bytes, b"hello");
+
+        let listed = store.list_for_task(task_id).unwrap();
+        assert_eq!(listed.len(), 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
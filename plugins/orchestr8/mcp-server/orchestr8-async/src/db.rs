@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use duckdb::{params, Connection, ToSql};
 use r2d2::{Pool, PooledConnection};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 pub type DbPool = Pool<r2d2_duckdb::DuckdbConnectionManager>;
@@ -18,6 +22,10 @@ pub enum TaskStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Parked because an upstream dependency failed fatally.
+    Blocked,
+    /// Cooperatively paused; excluded from scheduling until resumed.
+    Paused,
 }
 
 impl TaskStatus {
@@ -28,6 +36,8 @@ impl TaskStatus {
             TaskStatus::Completed => "completed",
             TaskStatus::Failed => "failed",
             TaskStatus::Cancelled => "cancelled",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Paused => "paused",
         }
     }
 
@@ -38,6 +48,42 @@ impl TaskStatus {
             "completed" => Some(TaskStatus::Completed),
             "failed" => Some(TaskStatus::Failed),
             "cancelled" => Some(TaskStatus::Cancelled),
+            "blocked" => Some(TaskStatus::Blocked),
+            "paused" => Some(TaskStatus::Paused),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a terminal state a task never leaves.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+        )
+    }
+}
+
+/// Classification of a task failure, used to decide whether a workflow can
+/// retry the task in place or must halt the phase and block dependents.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    Retryable,
+    Fatal,
+}
+
+impl ErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::Retryable => "retryable",
+            ErrorClass::Fatal => "fatal",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "retryable" => Some(ErrorClass::Retryable),
+            "fatal" => Some(ErrorClass::Fatal),
             _ => None,
         }
     }
@@ -77,6 +123,149 @@ impl TaskPriority {
     }
 }
 
+/// Retry backoff strategy for failed tasks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackoffStrategy {
+    Fixed,
+    Exponential,
+    /// Exponential growth with full jitter: the delay is a uniformly random
+    /// value in `[0, min(max, base * factor^attempt)]` to spread retries out
+    /// and avoid a thundering herd.
+    ExponentialJitter,
+}
+
+impl BackoffStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackoffStrategy::Fixed => "fixed",
+            BackoffStrategy::Exponential => "exponential",
+            BackoffStrategy::ExponentialJitter => "exponential_jitter",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "fixed" => Some(BackoffStrategy::Fixed),
+            "exponential" => Some(BackoffStrategy::Exponential),
+            "exponential_jitter" => Some(BackoffStrategy::ExponentialJitter),
+            _ => None,
+        }
+    }
+}
+
+/// A one-shot future fire time or a recurring cron pattern, for
+/// [`AsyncTask::with_schedule_kind`]. The task itself stores these exactly as
+/// it always has — a one-off via `scheduled_at` ([`AsyncTask::with_delay`])
+/// and a recurrence via the `schedule` cron string ([`AsyncTask::with_cron`]);
+/// this just gives callers a single type to build either from.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    Once(DateTime<Utc>),
+    Cron(String),
+}
+
+/// A user-defined attribute value, typed per Taskwarrior's UDA model.
+/// Persisted in `task_udas` alongside a `value_type` tag so round-tripping
+/// through SQL doesn't lose the original type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UdaValue {
+    String(String),
+    Number(f64),
+    Date(DateTime<Utc>),
+    /// Duration in seconds.
+    Duration(i64),
+}
+
+impl UdaValue {
+    fn value_type(&self) -> &'static str {
+        match self {
+            UdaValue::String(_) => "string",
+            UdaValue::Number(_) => "number",
+            UdaValue::Date(_) => "date",
+            UdaValue::Duration(_) => "duration",
+        }
+    }
+
+    fn to_storage_string(&self) -> String {
+        match self {
+            UdaValue::String(s) => s.clone(),
+            UdaValue::Number(n) => n.to_string(),
+            UdaValue::Date(d) => d.to_rfc3339(),
+            UdaValue::Duration(secs) => secs.to_string(),
+        }
+    }
+
+    fn from_storage(value_type: &str, value: &str) -> Option<Self> {
+        match value_type {
+            "string" => Some(UdaValue::String(value.to_string())),
+            "number" => value.parse::<f64>().ok().map(UdaValue::Number),
+            "date" => DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|d| UdaValue::Date(d.with_timezone(&Utc))),
+            "duration" => value.parse::<i64>().ok().map(UdaValue::Duration),
+            _ => None,
+        }
+    }
+}
+
+/// One task in the Taskwarrior 2.6 `export`/`import` JSON format. Fields we
+/// don't model natively (and our own `metadata`/UDA keys, on export) round-trip
+/// through `extra` so interchange with `task import`/`task export` doesn't
+/// silently drop data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TaskwarriorTask {
+    uuid: String,
+    status: String,
+    description: String,
+    entry: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    depends: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+/// Outcome of [`Database::import_tasks_json`]: a task whose `uuid` fails to
+/// parse (or whose `depends` list references one) is recorded in `errors`
+/// rather than aborting the rest of the batch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskImportSummary {
+    pub imported: usize,
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
+
+/// Render a UTC timestamp in Taskwarrior's compact `YYYYMMDDTHHMMSSZ` form.
+fn format_tw_date(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Parse a Taskwarrior compact-UTC timestamp, e.g. as produced by [`format_tw_date`].
+fn parse_tw_date(s: &str) -> Option<DateTime<Utc>> {
+    chrono::NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+/// Map a Taskwarrior status onto ours. `deleted` has no direct equivalent, so
+/// it lands on `Cancelled`; `waiting` (a task whose `wait` date hasn't yet
+/// arrived) is just `Pending` here, since we track eligibility via
+/// `scheduled_at` rather than a separate virtual status. Anything else is
+/// tried against our own status names first, so round-tripping a task we
+/// exported ourselves (`running`, `blocked`, ...) preserves it exactly.
+fn tw_status_to_task_status(s: &str) -> TaskStatus {
+    match s {
+        "deleted" => TaskStatus::Cancelled,
+        "waiting" => TaskStatus::Pending,
+        other => TaskStatus::from_str(other).unwrap_or(TaskStatus::Pending),
+    }
+}
+
 /// Async task record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AsyncTask {
@@ -100,6 +289,45 @@ pub struct AsyncTask {
     pub max_retries: i32,
     pub timeout_seconds: Option<i32>,
     pub metadata: Option<String>,
+    /// Earliest time this task may be re-dispatched after a failed attempt.
+    pub next_retry_at: Option<DateTime<Utc>>,
+    pub backoff_strategy: BackoffStrategy,
+    pub backoff_base_seconds: i64,
+    pub backoff_max_seconds: i64,
+    /// Multiplier applied per attempt for the exponential strategies.
+    pub backoff_factor: i64,
+    /// Classification of the most recent failure, if any.
+    pub error_class: Option<ErrorClass>,
+    /// Execution progress in the range 0.0–1.0, updated by running agents.
+    pub progress: f64,
+    /// Cron expression making this task recurring. When set, the scheduler
+    /// re-enqueues a fresh execution on each occurrence instead of completing it.
+    pub schedule: Option<String>,
+    /// Executor kind used to look up the `TaskRunner` that handles this task.
+    pub kind: String,
+    /// Earliest time this task becomes eligible for dispatch. Defaults to
+    /// creation time; set it in the future via [`AsyncTask::with_delay`] to
+    /// delay a one-off task, independent of the `schedule` cron field.
+    pub scheduled_at: DateTime<Utc>,
+    /// Hex-encoded SHA-256 over the dedup-relevant fields, set by
+    /// [`Database::insert_task_unique`] so a retried submission can be
+    /// recognized as a duplicate of in-flight work.
+    pub uniq_hash: Option<String>,
+    /// Identity of the worker that atomically claimed this task via
+    /// [`Database::claim_task`]/[`Database::claim_next_tasks`]. Lets stuck-worker
+    /// recovery attribute an abandoned `running` row to the worker that lost it.
+    pub claimed_by: Option<String>,
+    /// Accumulated CPU/wall execution time charged to this task, in seconds.
+    /// Updated by the executor and rolled into per-scope totals by the usage
+    /// aggregator.
+    pub cpu_seconds: f64,
+    /// Number of agent/tool invocations made while executing this task.
+    pub invocations: i64,
+    /// User-defined attributes keyed by name, loaded from `task_udas` via
+    /// [`Database::get_task_udas`]. Schema-light extension point for domain
+    /// data (cost estimates, SLA deadlines, external ticket ids) that should
+    /// be queryable at the SQL layer instead of buried in `metadata`.
+    pub udas: HashMap<String, UdaValue>,
 }
 
 impl AsyncTask {
@@ -129,9 +357,53 @@ impl AsyncTask {
             max_retries: 3,
             timeout_seconds: None,
             metadata: None,
+            next_retry_at: None,
+            backoff_strategy: BackoffStrategy::Exponential,
+            backoff_base_seconds: 5,
+            backoff_max_seconds: 300,
+            backoff_factor: 2,
+            error_class: None,
+            progress: 0.0,
+            schedule: None,
+            kind: "default".to_string(),
+            scheduled_at: Utc::now(),
+            uniq_hash: None,
+            claimed_by: None,
+            cpu_seconds: 0.0,
+            invocations: 0,
+            udas: HashMap::new(),
         }
     }
 
+    /// Hex-encoded SHA-256 over the fields that make two submissions
+    /// "the same work": agent, instructions, and workflow/phase placement.
+    fn dedup_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.agent_name.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.agent_instructions.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(
+            self.workflow_id
+                .map(|id| id.to_string())
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+        hasher.update(b"\0");
+        hasher.update(self.phase_id.as_deref().unwrap_or_default().as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Set the executor kind used to route this task to a [`TaskRunner`].
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = kind.into();
+        self
+    }
+
     pub fn with_workflow(mut self, workflow_id: Uuid) -> Self {
         self.workflow_id = Some(workflow_id);
         self
@@ -162,11 +434,128 @@ impl AsyncTask {
         self
     }
 
+    /// Mark this task recurring on the given cron expression.
+    pub fn with_schedule(mut self, cron: String) -> Self {
+        self.schedule = Some(cron);
+        self
+    }
+
+    /// Mark this task recurring on the given cron expression, rejecting an
+    /// expression the `cron` crate can't parse.
+    pub fn with_cron(mut self, cron_expr: &str) -> Result<Self> {
+        cron::Schedule::from_str(cron_expr)
+            .with_context(|| format!("invalid cron expression '{}'", cron_expr))?;
+        self.schedule = Some(cron_expr.to_string());
+        Ok(self)
+    }
+
+    /// Delay this task's first eligibility for dispatch until `at`.
+    pub fn with_delay(mut self, at: DateTime<Utc>) -> Self {
+        self.scheduled_at = at;
+        self
+    }
+
+    /// Apply a [`Schedule`] in one call: `Once` delays via [`AsyncTask::with_delay`],
+    /// `Cron` validates and stores a recurrence via [`AsyncTask::with_cron`].
+    pub fn with_schedule_kind(self, schedule: Schedule) -> Result<Self> {
+        match schedule {
+            Schedule::Once(at) => Ok(self.with_delay(at)),
+            Schedule::Cron(expr) => self.with_cron(&expr),
+        }
+    }
+
+    /// Derive [`AsyncTask::uniq_hash`] from the agent name and description,
+    /// borrowed from backie's `uniq()` concept: a narrower cousin of
+    /// [`AsyncTask::dedup_hash`] that ignores workflow/phase placement, so the
+    /// same logical unit of work is deduplicated even if it's resubmitted
+    /// under a different workflow phase. `Database::insert_task_unique` skips
+    /// insertion when a `pending` or `running` task already has this hash and
+    /// returns that task's id; `Database::insert_task` does not check it.
+    pub fn with_uniqueness(mut self) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(self.agent_name.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.description.as_deref().unwrap_or_default().as_bytes());
+        self.uniq_hash = Some(
+            hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        );
+        self
+    }
+
     pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
         self.metadata = Some(metadata.to_string());
         self
     }
 
+    pub fn with_retry_policy(
+        mut self,
+        max_retries: i32,
+        strategy: BackoffStrategy,
+        base_seconds: i64,
+        factor: i64,
+        max_seconds: i64,
+    ) -> Self {
+        self.max_retries = max_retries;
+        self.backoff_strategy = strategy;
+        self.backoff_base_seconds = base_seconds;
+        self.backoff_factor = factor;
+        self.backoff_max_seconds = max_seconds;
+        self
+    }
+
+    /// Capped exponential delay `min(max, base * factor^attempt)`, shared by the
+    /// exponential strategies before any jitter is applied.
+    fn capped_exponential_seconds(&self) -> i64 {
+        let base = self.backoff_base_seconds.max(0);
+        let factor = self.backoff_factor.max(1);
+        let growth = factor.saturating_pow(self.retry_count.max(0) as u32);
+        base.saturating_mul(growth).min(self.backoff_max_seconds)
+    }
+
+    /// Compute the delay in seconds before the next retry attempt.
+    ///
+    /// `Fixed` waits a constant `base`, `Exponential` grows as
+    /// `min(max, base * factor^attempt)` with a small deterministic ±10% jitter,
+    /// and `ExponentialJitter` draws a uniformly random value in `[0, delay]` to
+    /// decorrelate retries across tasks failing at the same instant.
+    pub fn next_retry_delay_seconds(&self) -> i64 {
+        match self.backoff_strategy {
+            BackoffStrategy::Fixed => self.backoff_base_seconds.max(0),
+            BackoffStrategy::Exponential => {
+                let raw = self.capped_exponential_seconds();
+                // ±10% jitter derived from the task id so it stays deterministic.
+                let jitter_span = (raw / 10).max(0);
+                if jitter_span == 0 {
+                    return raw;
+                }
+                let seed = self.id.as_u128() as i64;
+                let jitter = seed.rem_euclid(2 * jitter_span + 1) - jitter_span;
+                (raw + jitter).max(0)
+            }
+            BackoffStrategy::ExponentialJitter => {
+                let raw = self.capped_exponential_seconds();
+                if raw <= 0 {
+                    return 0;
+                }
+                // Full jitter: a uniform draw in [0, raw].
+                let entropy = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.subsec_nanos() as i64)
+                    .unwrap_or(0);
+                entropy.rem_euclid(raw + 1)
+            }
+        }
+    }
+
+    /// Timestamp at which this task becomes eligible for its next retry.
+    pub fn next_retry_at_from_now(&self) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::seconds(self.next_retry_delay_seconds())
+    }
+
     pub fn is_ready(&self) -> bool {
         self.status == TaskStatus::Pending
     }
@@ -181,6 +570,11 @@ impl AsyncTask {
     pub fn can_retry(&self) -> bool {
         self.status == TaskStatus::Failed && self.retry_count < self.max_retries
     }
+
+    /// Whether this task recurs on a cron schedule.
+    pub fn is_recurring(&self) -> bool {
+        self.schedule.is_some()
+    }
 }
 
 /// Workflow definition for coordinating multiple tasks
@@ -209,9 +603,246 @@ pub struct WorkflowPhase {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+/// Recurring/scheduled task entry. Holds a serialized task template that the
+/// scheduler clones into a fresh [`AsyncTask`] each time it fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub id: Uuid,
+    pub name: String,
+    pub cron: Option<String>,
+    pub interval_seconds: Option<i64>,
+    pub end_at: Option<DateTime<Utc>>,
+    pub next_fire_at: DateTime<Utc>,
+    /// JSON-serialized task template (see `scheduler::TaskTemplate`).
+    pub template: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Counts of a workflow's terminal tasks that have been pruned from the
+/// `tasks` table, retained so workflow status can still be derived.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrunedTaskCounts {
+    pub completed: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+}
+
+/// A live event emitted whenever a task's log or status changes. Broadcast to
+/// subscribers of [`Database::subscribe_task_events`] so clients can follow a
+/// running task in real time instead of polling [`Database::get_task_logs`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TaskEvent {
+    /// A log line was appended.
+    Log {
+        task_id: Uuid,
+        level: String,
+        message: String,
+    },
+    /// The task transitioned to a new status.
+    Status { task_id: Uuid, status: TaskStatus },
+}
+
+impl TaskEvent {
+    /// The task this event concerns; subscribers filter on it.
+    pub fn task_id(&self) -> Uuid {
+        match self {
+            TaskEvent::Log { task_id, .. } | TaskEvent::Status { task_id, .. } => *task_id,
+        }
+    }
+}
+
+/// Backlog buffer for the task-event broadcast channel. Sized generously so a
+/// briefly-stalled subscriber lags rather than losing its place outright.
+const TASK_EVENT_CAPACITY: usize = 1024;
+
+/// Column a [`Database::query_tasks`] result is ordered by. Each is paired
+/// with `id` as a tiebreaker so pagination cursors stay stable.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortKey {
+    CreatedAt,
+    Priority,
+    Status,
+}
+
+impl TaskSortKey {
+    fn column(&self) -> &'static str {
+        match self {
+            TaskSortKey::CreatedAt => "created_at",
+            TaskSortKey::Priority => "priority",
+            TaskSortKey::Status => "status",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "created_at" => Some(TaskSortKey::CreatedAt),
+            "priority" => Some(TaskSortKey::Priority),
+            "status" => Some(TaskSortKey::Status),
+            _ => None,
+        }
+    }
+}
+
+/// Filter predicate for [`Database::query_tasks`]. Every field is optional;
+/// an unset field imposes no restriction.
+#[derive(Debug, Clone, Default)]
+pub struct TaskQueryFilter {
+    pub status: Option<TaskStatus>,
+    pub workflow_id: Option<Uuid>,
+    pub agent_name: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only tasks at or above this priority.
+    pub min_priority: Option<TaskPriority>,
+    pub sort_key: TaskSortKey,
+    /// Descending order; ignored for `Status`, which is always ascending so
+    /// callers see a stable alphabetical grouping.
+    pub descending: bool,
+    /// Maximum number of rows to return; 0 returns every matching row.
+    pub limit: usize,
+    /// Offset into the filtered, sorted result set; the continuation point
+    /// for `ListTasksResponse::next_cursor`.
+    pub offset: usize,
+}
+
+impl Default for TaskSortKey {
+    fn default() -> Self {
+        TaskSortKey::CreatedAt
+    }
+}
+
+/// One page of a [`Database::query_tasks`] result.
+#[derive(Debug, Clone)]
+pub struct TaskPage {
+    pub tasks: Vec<AsyncTask>,
+    /// Count of tasks matching the filter, ignoring `limit`/`offset`.
+    pub total: usize,
+    /// Offset to request next, or `None` once the filter is exhausted.
+    pub next_cursor: Option<usize>,
+}
+
+/// Coefficients for [`Database::urgency`]'s weighted-sum score, in the spirit
+/// of Taskwarrior's urgency model. Higher wins; the defaults favor starving
+/// and blocking tasks over raw `priority` alone.
+#[derive(Debug, Clone, Copy)]
+pub struct UrgencyConfig {
+    pub priority_coeff: f64,
+    /// Multiplied by age in days, capped at `max_age_days` so very old tasks
+    /// don't dominate every other signal.
+    pub age_coeff: f64,
+    pub max_age_days: f64,
+    /// Multiplied by the number of other tasks that list this one as a
+    /// dependency — clearing a blocker unblocks more work.
+    pub blocking_coeff: f64,
+    pub retry_coeff: f64,
+    /// Subtracted outright when the task still has incomplete dependencies,
+    /// sinking it below anything actually ready to run.
+    pub blocked_penalty: f64,
+}
+
+impl Default for UrgencyConfig {
+    fn default() -> Self {
+        Self {
+            priority_coeff: 6.0,
+            age_coeff: 2.0,
+            max_age_days: 14.0,
+            blocking_coeff: 8.0,
+            retry_coeff: 1.0,
+            blocked_penalty: 50.0,
+        }
+    }
+}
+
+/// Compile-time tags for the schema generations covered by [`MIGRATIONS`].
+/// Sealed so only this module can name a generation — callers never construct
+/// `V1`/`V2` directly, they request one through [`Database::schema_handle`].
+mod schema_version {
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    pub trait SchemaVersion: sealed::Sealed {
+        const VERSION: u32;
+    }
+
+    /// Schema prior to migration 2 (no `idx_tasks_kind`).
+    pub struct V1;
+    /// Current schema: every migration in `MIGRATIONS` applied.
+    pub struct V2;
+
+    impl sealed::Sealed for V1 {}
+    impl sealed::Sealed for V2 {}
+    impl SchemaVersion for V1 {
+        const VERSION: u32 = 1;
+    }
+    impl SchemaVersion for V2 {
+        const VERSION: u32 = 2;
+    }
+}
+pub use schema_version::{SchemaVersion, V1, V2};
+
+/// A [`Database`] handle known, at compile time, to be at schema generation
+/// `V`, obtained via [`Database::schema_handle`] after it checks the `V`
+/// against the `schema_version` row [`Database::migrate`] recorded.
+/// Generation-specific helpers like [`SchemaHandle::row_to_task`] are only
+/// implemented for the versions they're valid against — there's no such impl
+/// for `V1`, so code written for today's columns can't be made to compile
+/// against an un-migrated handle.
+pub struct SchemaHandle<'a, V: SchemaVersion> {
+    db: &'a Database,
+    _version: std::marker::PhantomData<V>,
+}
+
+impl<'a> SchemaHandle<'a, V2> {
+    /// Map a `tasks` row under the current schema.
+    pub fn row_to_task(&self, row: &duckdb::Row) -> Result<AsyncTask> {
+        self.db.row_to_task(row)
+    }
+}
+
+/// One forward-only schema change, applied at most once. `up` runs against
+/// the raw connection inside the same transaction as the `schema_version` row
+/// that records it, so a failure partway through never leaves a step marked
+/// applied without having taken effect.
+struct Migration {
+    version: u32,
+    description: &'static str,
+    up: fn(&Connection) -> Result<()>,
+}
+
+/// Ordered, append-only migration history. Add new entries at the end with
+/// the next `version`; never edit or remove a published one — [`Database::migrate`]
+/// assumes versions already recorded in `schema_version` were applied exactly
+/// as written here.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "baseline schema, as created directly by init_schema",
+        up: |_conn| Ok(()),
+    },
+    Migration {
+        version: 2,
+        description: "add idx_tasks_kind to speed up per-runner-kind dispatch queries",
+        up: |conn| {
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_tasks_kind ON tasks(kind)",
+                [],
+            )?;
+            Ok(())
+        },
+    },
+];
+
 /// Database manager for async task persistence
 pub struct Database {
     pool: DbPool,
+    /// Fan-out for live task log and status events. Writers publish through
+    /// [`Database::add_task_log`] and [`Database::update_task_status`]; the SSE
+    /// endpoint subscribes. Sends are dropped silently when nobody is listening.
+    events: broadcast::Sender<TaskEvent>,
 }
 
 impl Database {
@@ -225,8 +856,12 @@ impl Database {
             .build(manager)
             .context("Failed to create connection pool")?;
 
-        let db = Self { pool };
+        let db = Self {
+            pool,
+            events: broadcast::channel(TASK_EVENT_CAPACITY).0,
+        };
         db.init_schema()?;
+        db.migrate()?;
         Ok(db)
     }
 
@@ -240,11 +875,114 @@ impl Database {
             .build(manager)
             .context("Failed to create connection pool")?;
 
-        let db = Self { pool };
+        let db = Self {
+            pool,
+            events: broadcast::channel(TASK_EVENT_CAPACITY).0,
+        };
         db.init_schema()?;
+        db.migrate()?;
         Ok(db)
     }
 
+    /// Apply every [`MIGRATIONS`] entry newer than what's recorded in
+    /// `schema_version`, each inside its own transaction alongside the
+    /// bookkeeping row that records it. Idempotent: re-running against an
+    /// already-migrated database applies nothing. Fails loudly, rather than
+    /// silently skipping ahead, if the on-disk version is newer than any
+    /// version this binary's `MIGRATIONS` list knows about.
+    fn migrate(&self) -> Result<u32> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version BIGINT PRIMARY KEY,
+                applied_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        let current: i64 = conn
+            .query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+        let current = current as u32;
+
+        let latest = MIGRATIONS.iter().map(|m| m.version).max().unwrap_or(0);
+        if current > latest {
+            anyhow::bail!(
+                "database schema is at version {current}, but this binary only knows \
+                 migrations up to version {latest} -- refusing to open it with an older binary"
+            );
+        }
+
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+            conn.execute("BEGIN TRANSACTION", [])?;
+            let applied = (|| -> Result<()> {
+                (migration.up)(&conn)?;
+                conn.execute(
+                    "INSERT INTO schema_version (version, applied_at) VALUES (?, ?)",
+                    params![migration.version as i64, Utc::now()],
+                )?;
+                Ok(())
+            })();
+
+            match applied {
+                Ok(()) => {
+                    conn.execute("COMMIT", [])?;
+                }
+                Err(e) => {
+                    conn.execute("ROLLBACK", [])?;
+                    return Err(e).with_context(|| {
+                        format!(
+                            "migration {} ({}) failed",
+                            migration.version, migration.description
+                        )
+                    });
+                }
+            }
+        }
+
+        Ok(current.max(latest))
+    }
+
+    /// Obtain a [`SchemaHandle`] asserting this database is at generation `V`,
+    /// checked against the `schema_version` row [`Database::migrate`] recorded.
+    pub fn schema_handle<V: SchemaVersion>(&self) -> Result<SchemaHandle<'_, V>> {
+        let conn = self.get_conn()?;
+        let current: i64 = conn
+            .query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+        if current as u32 != V::VERSION {
+            anyhow::bail!(
+                "database is at schema version {current}, not {}",
+                V::VERSION
+            );
+        }
+        Ok(SchemaHandle {
+            db: self,
+            _version: std::marker::PhantomData,
+        })
+    }
+
+    /// Subscribe to the live stream of task log and status events. Each
+    /// subscriber receives every event published after it subscribes; slow
+    /// consumers that fall more than [`TASK_EVENT_CAPACITY`] events behind are
+    /// signalled a lag rather than blocking writers.
+    pub fn subscribe_task_events(&self) -> broadcast::Receiver<TaskEvent> {
+        self.events.subscribe()
+    }
+
+    /// Broadcast a task event, ignoring the "no subscribers" case.
+    fn publish_event(&self, event: TaskEvent) {
+        let _ = self.events.send(event);
+    }
+
     pub fn get_conn(&self) -> Result<DbConn> {
         self.pool.get().context("Failed to get database connection")
     }
@@ -275,7 +1013,21 @@ impl Database {
                 retry_count INTEGER NOT NULL DEFAULT 0,
                 max_retries INTEGER NOT NULL DEFAULT 3,
                 timeout_seconds INTEGER,
-                metadata TEXT
+                metadata TEXT,
+                next_retry_at TIMESTAMP,
+                backoff_strategy VARCHAR NOT NULL DEFAULT 'exponential',
+                backoff_base_seconds BIGINT NOT NULL DEFAULT 5,
+                backoff_max_seconds BIGINT NOT NULL DEFAULT 300,
+                backoff_factor BIGINT NOT NULL DEFAULT 2,
+                error_class VARCHAR,
+                progress DOUBLE NOT NULL DEFAULT 0.0,
+                schedule VARCHAR,
+                kind VARCHAR NOT NULL DEFAULT 'default',
+                cpu_seconds DOUBLE NOT NULL DEFAULT 0.0,
+                invocations BIGINT NOT NULL DEFAULT 0,
+                scheduled_at TIMESTAMP NOT NULL DEFAULT now(),
+                uniq_hash VARCHAR,
+                claimed_by VARCHAR
             )",
             [],
         )?;
@@ -311,10 +1063,70 @@ impl Database {
             [],
         )?;
 
+        // User-defined attributes: schema-light, typed key/value pairs attached
+        // to a task beyond its fixed columns. One row per (task_id, name).
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_udas (
+                task_id VARCHAR NOT NULL,
+                name VARCHAR NOT NULL,
+                value_type VARCHAR NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (task_id, name)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_task_udas_name_value ON task_udas(name, value)",
+            [],
+        )?;
+
+        // Terminal-task tallies for workflows, recorded when a task row is pruned
+        // by the retention policy so workflow status can still be computed after
+        // the underlying rows are gone.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS workflow_task_counts (
+                workflow_id VARCHAR NOT NULL,
+                status VARCHAR NOT NULL,
+                count BIGINT NOT NULL DEFAULT 0,
+                PRIMARY KEY (workflow_id, status)
+            )",
+            [],
+        )?;
+
+        // Reusable workflow blueprints
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS workflow_templates (
+                name VARCHAR PRIMARY KEY,
+                description VARCHAR,
+                blueprint TEXT NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Recurring/scheduled task definitions
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schedules (
+                id VARCHAR PRIMARY KEY,
+                name VARCHAR NOT NULL,
+                cron VARCHAR,
+                interval_seconds BIGINT,
+                end_at TIMESTAMP,
+                next_fire_at TIMESTAMP NOT NULL,
+                template TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT TRUE,
+                created_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Monotonic sequence for incremental log tailing
+        conn.execute("CREATE SEQUENCE IF NOT EXISTS task_logs_seq START 1", [])?;
+
         // Task execution log
         conn.execute(
             "CREATE TABLE IF NOT EXISTS task_logs (
-                id INTEGER PRIMARY KEY,
+                seq BIGINT PRIMARY KEY DEFAULT nextval('task_logs_seq'),
                 task_id VARCHAR NOT NULL,
                 timestamp TIMESTAMP NOT NULL,
                 level VARCHAR NOT NULL,
@@ -324,46 +1136,192 @@ impl Database {
             [],
         )?;
 
-        // Webhook delivery log
+        // Webhook delivery queue: one row per task delivery, carrying a
+        // persisted backoff schedule so the worker never sleeps inline and the
+        // schedule survives process restarts.
+        conn.execute("CREATE SEQUENCE IF NOT EXISTS webhook_deliveries_seq START 1", [])?;
         conn.execute(
             "CREATE TABLE IF NOT EXISTS webhook_deliveries (
-                id INTEGER PRIMARY KEY,
+                id BIGINT PRIMARY KEY DEFAULT nextval('webhook_deliveries_seq'),
                 task_id VARCHAR NOT NULL,
                 webhook_url VARCHAR NOT NULL,
                 payload TEXT NOT NULL,
+                kind VARCHAR,
                 status_code INTEGER,
                 response TEXT,
-                attempted_at TIMESTAMP NOT NULL,
-                delivered_at TIMESTAMP
+                attempt_count INTEGER NOT NULL DEFAULT 0,
+                status VARCHAR NOT NULL DEFAULT 'pending',
+                next_attempt_at TIMESTAMP NOT NULL,
+                attempted_at TIMESTAMP,
+                delivered_at TIMESTAMP,
+                last_error TEXT,
+                secret_version VARCHAR
             )",
             [],
         )?;
 
-        // Create indexes for performance
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_workflow ON tasks(workflow_id)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks(priority DESC)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_created ON tasks(created_at)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_task_logs_task_id ON task_logs(task_id)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_task_id ON webhook_deliveries(task_id)", [])?;
-
-        Ok(())
-    }
-
-    // ===== Task Operations =====
-
-    /// Insert a new task
-    pub fn insert_task(&self, task: &AsyncTask) -> Result<()> {
-        let conn = self.get_conn()?;
-
-        let dependencies_json = serde_json::to_string(&task.dependencies)?;
+        // Notification subscriptions: per-task or per-workflow event routing
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS notification_subscriptions (
+                id VARCHAR PRIMARY KEY,
+                task_id VARCHAR,
+                workflow_id VARCHAR,
+                channel VARCHAR NOT NULL,
+                target VARCHAR NOT NULL,
+                events TEXT NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
 
+        // Notification delivery log with a durable retry queue
+        conn.execute("CREATE SEQUENCE IF NOT EXISTS notification_deliveries_seq START 1", [])?;
         conn.execute(
-            "INSERT INTO tasks (
-                id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
+            "CREATE TABLE IF NOT EXISTS notification_deliveries (
+                id BIGINT PRIMARY KEY DEFAULT nextval('notification_deliveries_seq'),
+                subscription_id VARCHAR NOT NULL,
+                event VARCHAR NOT NULL,
+                channel VARCHAR NOT NULL,
+                target VARCHAR NOT NULL,
+                payload TEXT NOT NULL,
+                status VARCHAR NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                next_attempt_at TIMESTAMP NOT NULL,
+                created_at TIMESTAMP NOT NULL,
+                delivered_at TIMESTAMP
+            )",
+            [],
+        )?;
+
+        // Fleet of external runners that claim and execute tasks remotely
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS runners (
+                id VARCHAR PRIMARY KEY,
+                name VARCHAR NOT NULL,
+                status VARCHAR NOT NULL DEFAULT 'online',
+                last_heartbeat TIMESTAMP NOT NULL,
+                registered_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Active task leases: one row per claimed task, renewed by heartbeats
+        // and reclaimed when the lease expires.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_leases (
+                task_id VARCHAR PRIMARY KEY,
+                runner_id VARCHAR NOT NULL,
+                leased_at TIMESTAMP NOT NULL,
+                heartbeat_at TIMESTAMP NOT NULL,
+                expires_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Agent fleet: one row per named agent, tracking its lifecycle state
+        // (registered/idle/busy/offline), heartbeat, and in-flight assignment so
+        // the scheduler only dispatches to healthy, idle agents.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS agents (
+                name VARCHAR PRIMARY KEY,
+                state VARCHAR NOT NULL DEFAULT 'registered',
+                current_task VARCHAR,
+                last_heartbeat TIMESTAMP NOT NULL,
+                registered_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Artifact metadata: large/binary task outputs stored out of band on
+        // disk, referenced from webhook payloads instead of inlined.
+        conn.execute("CREATE SEQUENCE IF NOT EXISTS artifacts_seq START 1", [])?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS artifacts (
+                id BIGINT PRIMARY KEY DEFAULT nextval('artifacts_seq'),
+                task_id VARCHAR NOT NULL,
+                name VARCHAR NOT NULL,
+                content_type VARCHAR NOT NULL,
+                size BIGINT NOT NULL,
+                checksum VARCHAR NOT NULL,
+                path VARCHAR NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Usage rollups: per-scope (workflow/agent) accumulated execution totals,
+        // maintained idempotently by the usage aggregator.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_rollup (
+                scope_kind VARCHAR NOT NULL,
+                scope_key VARCHAR NOT NULL,
+                cpu_seconds DOUBLE NOT NULL DEFAULT 0.0,
+                wall_seconds DOUBLE NOT NULL DEFAULT 0.0,
+                invocations BIGINT NOT NULL DEFAULT 0,
+                task_count BIGINT NOT NULL DEFAULT 0,
+                updated_at TIMESTAMP NOT NULL,
+                PRIMARY KEY (scope_kind, scope_key)
+            )",
+            [],
+        )?;
+
+        // Checkpoint for the usage aggregator: the `completed_at` high-water mark
+        // already folded into `usage_rollup`, so restarts don't double-count.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_checkpoint (
+                id INTEGER PRIMARY KEY,
+                last_processed TIMESTAMP NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create indexes for performance
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_workflow ON tasks(workflow_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_scheduled_at ON tasks(scheduled_at)", [])?;
+        // DuckDB doesn't support a `WHERE` clause on `CREATE INDEX`, so the
+        // partial-uniqueness invariant (only one in-flight task may carry a
+        // given dedup hash; completed/failed/cancelled rows don't block a
+        // later retry) is enforced in `insert_task_unique` via an explicit
+        // `SELECT ... WHERE uniq_hash = ? AND status IN (...)` instead. This
+        // plain index just speeds that lookup.
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_uniq_hash ON tasks(uniq_hash)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks(priority DESC)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_created ON tasks(created_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_next_retry ON tasks(next_retry_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_schedules_next_fire ON schedules(next_fire_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_task_logs_task_id ON task_logs(task_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_task_id ON webhook_deliveries(task_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_notification_subs_task ON notification_subscriptions(task_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_notification_subs_workflow ON notification_subscriptions(workflow_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_notification_deliveries_status ON notification_deliveries(status, next_attempt_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_task_leases_runner ON task_leases(runner_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_task_leases_expires ON task_leases(expires_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_artifacts_task ON artifacts(task_id)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_artifacts_created ON artifacts(created_at)", [])?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_agents_state ON agents(state)", [])?;
+
+        Ok(())
+    }
+
+    // ===== Task Operations =====
+
+    /// Insert a new task
+    pub fn insert_task(&self, task: &AsyncTask) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        let dependencies_json = serde_json::to_string(&task.dependencies)?;
+
+        conn.execute(
+            "INSERT INTO tasks (
+                id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
                 status, priority, dependencies, created_at, started_at, completed_at,
-                result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?::VARCHAR[], ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?::VARCHAR[], ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 task.id.to_string(),
                 task.name,
@@ -385,12 +1343,56 @@ impl Database {
                 task.max_retries,
                 task.timeout_seconds,
                 task.metadata,
+                task.next_retry_at,
+                task.backoff_strategy.as_str(),
+                task.backoff_base_seconds,
+                task.backoff_max_seconds,
+                task.backoff_factor,
+                task.error_class.map(|c| c.as_str()),
+                task.progress,
+                task.schedule,
+                task.kind,
+                task.cpu_seconds,
+                task.invocations,
+                task.scheduled_at,
+                task.uniq_hash,
+                task.claimed_by,
             ],
         )?;
 
         Ok(())
     }
 
+    /// Insert a task, deduplicating against other in-flight (`pending` or
+    /// `running`) tasks for the same agent/instructions/workflow/phase. If a
+    /// matching task is already active, returns its UUID instead of inserting
+    /// a second copy — safe for a caller to call again after e.g. a webhook
+    /// timeout without spawning a redundant agent run.
+    ///
+    /// If `task` already carries a `uniq_hash` (e.g. set via
+    /// [`AsyncTask::with_uniqueness`]), that hash is used as-is instead of
+    /// being recomputed from `task.dedup_hash()`.
+    pub fn insert_task_unique(&self, mut task: AsyncTask) -> Result<Uuid> {
+        let hash = task.uniq_hash.clone().unwrap_or_else(|| task.dedup_hash());
+
+        let conn = self.get_conn()?;
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT id FROM tasks WHERE uniq_hash = ? AND status IN ('pending', 'running')",
+                params![hash],
+                |row| row.get(0),
+            )
+            .ok();
+        if let Some(id) = existing {
+            return Ok(Uuid::parse_str(&id)?);
+        }
+
+        task.uniq_hash = Some(hash);
+        let id = task.id;
+        self.insert_task(&task)?;
+        Ok(id)
+    }
+
     /// Get task by ID
     pub fn get_task(&self, id: Uuid) -> Result<Option<AsyncTask>> {
         let conn = self.get_conn()?;
@@ -398,7 +1400,9 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
                     status, priority, dependencies, created_at, started_at, completed_at,
-                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
              FROM tasks WHERE id = ?",
         )?;
 
@@ -411,6 +1415,41 @@ impl Database {
         }
     }
 
+    /// Look up the most recently created task carrying a given `uniq_hash`,
+    /// regardless of status. Pairs with [`AsyncTask::with_uniqueness`] /
+    /// [`Database::insert_task_unique`] for callers that want to check
+    /// whether a logically-identical task has already been filed.
+    pub fn get_task_by_hash(&self, hash: &str) -> Result<Option<AsyncTask>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
+                    status, priority, dependencies, created_at, started_at, completed_at,
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
+             FROM tasks WHERE uniq_hash = ? ORDER BY created_at DESC LIMIT 1",
+        )?;
+
+        let mut rows = stmt.query(params![hash])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_task(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Add execution counters to a task, for the usage metering subsystem.
+    pub fn record_task_usage(&self, id: Uuid, cpu_seconds: f64, invocations: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks SET cpu_seconds = cpu_seconds + ?, invocations = invocations + ? WHERE id = ?",
+            params![cpu_seconds, invocations, id.to_string()],
+        )?;
+        Ok(())
+    }
+
     /// Update task status
     pub fn update_task_status(&self, id: Uuid, status: TaskStatus) -> Result<()> {
         let conn = self.get_conn()?;
@@ -437,6 +1476,7 @@ impl Database {
             }
         }
 
+        self.publish_event(TaskEvent::Status { task_id: id, status });
         Ok(())
     }
 
@@ -460,6 +1500,466 @@ impl Database {
         Ok(())
     }
 
+    /// Re-queue a failed task for another attempt: record the last error, bump
+    /// `retry_count`, move the task back to `pending`, and gate re-dispatch until
+    /// `next_retry_at`. Returns the computed next-retry time.
+    pub fn schedule_task_retry(&self, id: Uuid, error: &str) -> Result<Option<DateTime<Utc>>> {
+        let task = match self.get_task(id)? {
+            Some(task) => task,
+            None => return Ok(None),
+        };
+
+        if task.retry_count >= task.max_retries {
+            // Exhausted: leave the task failed with the last error.
+            self.update_task_error(id, error.to_string())?;
+            return Ok(None);
+        }
+
+        let next_retry_at = task.next_retry_at_from_now();
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks
+             SET status = ?, error = ?, retry_count = retry_count + 1,
+                 next_retry_at = ?, started_at = NULL, completed_at = NULL
+             WHERE id = ?",
+            params![
+                TaskStatus::Pending.as_str(),
+                error,
+                next_retry_at,
+                id.to_string(),
+            ],
+        )?;
+
+        Ok(Some(next_retry_at))
+    }
+
+    /// Sibling of [`Database::schedule_task_retry`] for callers that want to
+    /// override the task's own backoff policy with an explicit delay — e.g. a
+    /// caller that already computed its own backoff rather than relying on
+    /// [`AsyncTask::next_retry_at_from_now`]. Otherwise identical: bumps
+    /// `retry_count`, records `error`, returns the task to `pending` with
+    /// `next_retry_at = now + backoff_seconds`, and finalizes as `failed`
+    /// once `retry_count` reaches `max_retries`.
+    pub fn schedule_retry(
+        &self,
+        id: Uuid,
+        backoff_seconds: i64,
+        error: &str,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let task = match self.get_task(id)? {
+            Some(task) => task,
+            None => return Ok(None),
+        };
+
+        if task.retry_count >= task.max_retries {
+            self.update_task_error(id, error.to_string())?;
+            return Ok(None);
+        }
+
+        let next_retry_at = Utc::now() + chrono::Duration::seconds(backoff_seconds.max(0));
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks
+             SET status = ?, error = ?, retry_count = retry_count + 1,
+                 next_retry_at = ?, started_at = NULL, completed_at = NULL
+             WHERE id = ?",
+            params![
+                TaskStatus::Pending.as_str(),
+                error,
+                next_retry_at,
+                id.to_string(),
+            ],
+        )?;
+
+        Ok(Some(next_retry_at))
+    }
+
+    /// Re-queue a failed task using its own backoff policy, for callers that
+    /// have already recorded/classified the failure elsewhere and just need
+    /// the retry scheduled. Increments `retry_count`, resets `status` to
+    /// `pending`, clears `error`/`completed_at`, and sets `next_retry_at` via
+    /// [`AsyncTask::next_retry_at_from_now`] — the task's own
+    /// `backoff_strategy`/`backoff_base_seconds`/`backoff_factor`, capped at
+    /// `backoff_max_seconds` with jitter under [`BackoffStrategy::ExponentialJitter`].
+    /// Returns `None`, leaving the task `failed`, once `retry_count` reaches
+    /// `max_retries`. Sibling of [`Database::schedule_task_retry`], which also
+    /// records a new error message; use that one instead when the caller has
+    /// a fresh error to persist.
+    pub fn reschedule_for_retry(&self, id: Uuid) -> Result<Option<DateTime<Utc>>> {
+        let task = match self.get_task(id)? {
+            Some(task) => task,
+            None => return Ok(None),
+        };
+
+        if task.retry_count >= task.max_retries {
+            return Ok(None);
+        }
+
+        let next_retry_at = task.next_retry_at_from_now();
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks
+             SET status = ?, retry_count = retry_count + 1, next_retry_at = ?,
+                 error = NULL, completed_at = NULL, started_at = NULL
+             WHERE id = ?",
+            params![TaskStatus::Pending.as_str(), next_retry_at, id.to_string()],
+        )?;
+
+        Ok(Some(next_retry_at))
+    }
+
+    /// Record a failure and either schedule a backoff retry or move the task
+    /// to its terminal dead-letter state, using the task's own backoff policy
+    /// (`backoff_strategy`/`backoff_base_seconds`/`backoff_factor`/`backoff_max_seconds`,
+    /// set via [`AsyncTask::with_retry_policy`]). This repo has no separate
+    /// `Dead` status: exhausted retries land in `failed`, same as
+    /// [`Database::schedule_task_retry`], which this delegates to — `fail_task`
+    /// is kept as its own entry point so callers reaching for dead-letter
+    /// semantics have an obvious name, paired with
+    /// [`Database::requeue_dead_task`] for manual replay.
+    pub fn fail_task(&self, id: Uuid, error: &str) -> Result<Option<DateTime<Utc>>> {
+        self.schedule_task_retry(id, error)
+    }
+
+    /// Reset a `failed` task (one whose retries were exhausted) back to
+    /// `pending` for a fresh attempt: clears `error`/`completed_at` and
+    /// `retry_count`, and makes it immediately due. For manually replaying
+    /// dead-letter work after a fix has shipped, rather than waiting for a new
+    /// submission. Returns `false` if the task doesn't exist or isn't `failed`.
+    pub fn requeue_dead_task(&self, id: Uuid) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let affected = conn.execute(
+            "UPDATE tasks
+             SET status = ?, retry_count = 0, error = NULL, completed_at = NULL,
+                 started_at = NULL, next_retry_at = NULL, scheduled_at = ?
+             WHERE id = ? AND status = ?",
+            params![
+                TaskStatus::Pending.as_str(),
+                Utc::now(),
+                id.to_string(),
+                TaskStatus::Failed.as_str(),
+            ],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// Alias for [`Database::get_pending_tasks`]: `pending` tasks due now
+    /// (`scheduled_at` and `next_retry_at` both at-or-before now), so delayed
+    /// retries and future-scheduled tasks aren't picked up early.
+    pub fn get_runnable_tasks(&self, limit: usize) -> Result<Vec<AsyncTask>> {
+        self.get_pending_tasks(limit)
+    }
+
+    /// Return a recurring task to the pending pool for its next occurrence,
+    /// recording the last run's outcome but clearing the terminal state so the
+    /// scheduler re-dispatches it at `next_fire`. The retry counter is reset so a
+    /// transient failure on one run does not exhaust the schedule.
+    pub fn reschedule_recurring_task(
+        &self,
+        id: Uuid,
+        next_fire: DateTime<Utc>,
+        last_result: Option<String>,
+        last_error: Option<String>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks
+             SET status = ?, result = ?, error = ?, retry_count = 0,
+                 next_retry_at = ?, started_at = NULL, completed_at = NULL
+             WHERE id = ?",
+            params![
+                TaskStatus::Pending.as_str(),
+                last_result,
+                last_error,
+                next_fire,
+                id.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Clone-based alternative to [`Database::reschedule_recurring_task`]: given
+    /// a completed task with a `schedule` cron expression, computes its next
+    /// occurrence and inserts a fresh `Pending` task (new UUID) carrying over
+    /// the agent, instructions, and priority, scheduled at that occurrence.
+    /// Returns `None` without inserting anything if the cron expression has no
+    /// more occurrences, or if `task.schedule` is unset.
+    pub fn spawn_next_cron_occurrence(&self, task: &AsyncTask) -> Result<Option<AsyncTask>> {
+        let Some(cron_expr) = &task.schedule else {
+            return Ok(None);
+        };
+        let schedule = cron::Schedule::from_str(cron_expr)
+            .with_context(|| format!("invalid cron expression '{}'", cron_expr))?;
+        let Some(next) = schedule.upcoming(Utc).next() else {
+            return Ok(None);
+        };
+
+        let next_task = AsyncTask::new(
+            task.name.clone(),
+            task.agent_name.clone(),
+            task.agent_instructions.clone(),
+        )
+        .with_priority(task.priority)
+        .with_schedule(cron_expr.clone())
+        .with_delay(next);
+
+        self.insert_task(&next_task)?;
+        Ok(Some(next_task))
+    }
+
+    /// Record a fatal, classified failure on a task.
+    pub fn update_task_error_classified(
+        &self,
+        id: Uuid,
+        error: String,
+        class: ErrorClass,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks SET error = ?, error_class = ?, status = ?, completed_at = ? WHERE id = ?",
+            params![
+                error,
+                class.as_str(),
+                TaskStatus::Failed.as_str(),
+                Utc::now(),
+                id.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Block all not-yet-complete tasks in a workflow that depend (directly or
+    /// via their phase) on a fatally failed task, so they are not dispatched.
+    pub fn block_dependents(&self, workflow_id: Uuid, failed_task: &AsyncTask) -> Result<usize> {
+        let tasks = self.get_workflow_tasks(workflow_id)?;
+        let mut blocked = 0;
+
+        for task in &tasks {
+            if task.is_complete() || task.status == TaskStatus::Blocked {
+                continue;
+            }
+            let depends_on_task = task.dependencies.contains(&failed_task.id);
+            let depends_on_phase = match (&failed_task.phase_id, &task.phase_id) {
+                (Some(failed_phase), Some(task_phase)) if failed_phase != task_phase => {
+                    let phases = self.get_workflow_phases(workflow_id)?;
+                    phases
+                        .iter()
+                        .find(|p| p.phase_id == *task_phase)
+                        .map(|p| p.depends_on.contains(failed_phase))
+                        .unwrap_or(false)
+                }
+                _ => false,
+            };
+
+            if depends_on_task || depends_on_phase {
+                self.update_task_status(task.id, TaskStatus::Blocked)?;
+                blocked += 1;
+            }
+        }
+
+        Ok(blocked)
+    }
+
+    /// Atomically transition a single task from `pending` to `running`,
+    /// recording `claimed_by`. Returns `false` without changing anything if
+    /// the task wasn't `pending` (e.g. another worker already claimed it) —
+    /// the guard a caller should check before executing a task it popped off
+    /// a shared queue, closing the read-then-write race [`Database::get_pending_tasks`]
+    /// alone leaves open.
+    pub fn claim_task(&self, id: Uuid, claimed_by: &str) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let affected = conn.execute(
+            "UPDATE tasks SET status = 'running', started_at = now(), claimed_by = ?
+             WHERE id = ? AND status = 'pending'",
+            params![claimed_by, id.to_string()],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// Atomically claim up to `limit` due `pending` tasks for `worker_id` in
+    /// one statement, transitioning them straight to `running` and returning
+    /// only the rows this call won. Replaces the
+    /// `get_pending_tasks` + separate `update_task_status` pattern, which lets
+    /// two callers sharing one `Database` both select and start the same row.
+    pub fn claim_next_tasks(&self, worker_id: &str, limit: usize) -> Result<Vec<AsyncTask>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "UPDATE tasks
+             SET status = 'running', started_at = now(), claimed_by = ?
+             WHERE id IN (
+                 SELECT id FROM tasks
+                 WHERE status = 'pending' AND scheduled_at <= now()
+                 ORDER BY priority DESC, created_at ASC
+                 LIMIT ?
+             )
+             RETURNING id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
+                       status, priority, dependencies, created_at, started_at, completed_at,
+                       result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                       next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                       cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by",
+        )?;
+
+        let rows = stmt.query_map(params![worker_id, limit as i64], |row| {
+            Ok(self.row_to_task(row).unwrap())
+        })?;
+
+        let mut tasks = Vec::new();
+        for task in rows {
+            tasks.push(task?);
+        }
+        Ok(tasks)
+    }
+
+    /// Reset a task from `running` back to `pending`, clearing `started_at`
+    /// and `claimed_by`, without touching `retry_count` or recording an error.
+    /// For a forced shutdown interrupting a worker mid-run: the task wasn't a
+    /// failure, just cut off, so it should be re-picked exactly as it was.
+    /// Returns `false` if the task isn't currently `running`.
+    pub fn reset_running_to_pending(&self, id: Uuid) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let affected = conn.execute(
+            "UPDATE tasks SET status = ?, started_at = NULL, claimed_by = NULL
+             WHERE id = ? AND status = ?",
+            params![
+                TaskStatus::Pending.as_str(),
+                id.to_string(),
+                TaskStatus::Running.as_str(),
+            ],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// Reconcile `running` tasks abandoned by a crashed or hung worker: any
+    /// task still `running` after its own `timeout_seconds` (falling back to
+    /// `default_timeout` when unset) is either returned to `pending` for
+    /// another attempt, bumping `retry_count`, or moved to a terminal `failed`
+    /// state with `error = "reclaimed: worker timeout"` once retries are
+    /// exhausted. Returns the IDs of every task reclaimed so a caller can log
+    /// or alert. Mirrors [`crate::runner::RunnerCoordinator::reclaim_expired`]
+    /// for in-process workers, which orphan a task's `running` row instead of
+    /// releasing a lease.
+    pub fn reclaim_stale_running(&self, default_timeout: std::time::Duration) -> Result<Vec<Uuid>> {
+        let candidates = {
+            let conn = self.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT id FROM tasks WHERE status = 'running' AND started_at IS NOT NULL",
+            )?;
+            let ids: Vec<String> = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<duckdb::Result<_>>()?;
+            ids
+        };
+
+        let default_secs = default_timeout.as_secs() as i64;
+        let mut reclaimed = Vec::new();
+
+        for id_str in candidates {
+            let Ok(id) = Uuid::parse_str(&id_str) else {
+                continue;
+            };
+            let Some(task) = self.get_task(id)? else {
+                continue;
+            };
+            let Some(started_at) = task.started_at else {
+                continue;
+            };
+            let timeout_secs = task
+                .timeout_seconds
+                .map(|s| s as i64)
+                .unwrap_or(default_secs);
+            if Utc::now() - started_at < chrono::Duration::seconds(timeout_secs) {
+                continue;
+            }
+
+            let conn = self.get_conn()?;
+            if task.retry_count < task.max_retries {
+                conn.execute(
+                    "UPDATE tasks
+                     SET status = 'pending', retry_count = retry_count + 1,
+                         started_at = NULL, claimed_by = NULL
+                     WHERE id = ?",
+                    params![id.to_string()],
+                )?;
+            } else {
+                conn.execute(
+                    "UPDATE tasks
+                     SET status = 'failed', error = ?, completed_at = ?
+                     WHERE id = ?",
+                    params![
+                        "reclaimed: worker timeout",
+                        Utc::now(),
+                        id.to_string()
+                    ],
+                )?;
+            }
+            drop(conn);
+            self.add_task_log(
+                id,
+                "WARN",
+                "Task reclaimed: running past its timeout with no progress",
+                None,
+            )?;
+            reclaimed.push(id);
+        }
+
+        Ok(reclaimed)
+    }
+
+    /// Count other tasks whose `dependencies` list this task's id — i.e. how
+    /// many tasks this one is directly blocking.
+    pub fn count_dependents(&self, task_id: Uuid) -> Result<i64> {
+        let conn = self.get_conn()?;
+        let mut stmt =
+            conn.prepare("SELECT COUNT(*) FROM tasks WHERE list_contains(dependencies, ?)")?;
+        let count: i64 = stmt.query_row(params![task_id.to_string()], |row| row.get(0))?;
+        Ok(count)
+    }
+
+    /// Taskwarrior-style urgency score for dequeue ordering: a weighted sum of
+    /// priority, age (capped), how many tasks this one is blocking, and retry
+    /// count, minus a flat penalty while dependencies are unmet. Higher wins.
+    pub fn urgency(&self, task: &AsyncTask, now: DateTime<Utc>, config: &UrgencyConfig) -> Result<f64> {
+        let age_days = (now - task.created_at).num_seconds() as f64 / 86_400.0;
+        let age_days = age_days.clamp(0.0, config.max_age_days);
+        let dependents = self.count_dependents(task.id)? as f64;
+        let blocked = !self.are_dependencies_completed(task)?;
+
+        let priority_rank = match task.priority {
+            TaskPriority::Low => 1.0,
+            TaskPriority::Normal => 2.0,
+            TaskPriority::High => 3.0,
+            TaskPriority::Critical => 4.0,
+        };
+        let mut score = config.priority_coeff * priority_rank
+            + config.age_coeff * age_days
+            + config.blocking_coeff * dependents
+            + config.retry_coeff * task.retry_count as f64;
+        if blocked {
+            score -= config.blocked_penalty;
+        }
+        Ok(score)
+    }
+
+    /// The single most urgent task that's actually ready to run right now
+    /// (`pending`, due, and with all dependencies `Completed`), per
+    /// [`Database::urgency`]. Returns `None` if nothing qualifies.
+    pub fn get_next_ready_task(&self, config: &UrgencyConfig) -> Result<Option<AsyncTask>> {
+        let now = Utc::now();
+        let candidates = self.get_pending_tasks(500)?;
+
+        let mut best: Option<(f64, AsyncTask)> = None;
+        for task in candidates {
+            if self.are_dependencies_completed(&task)? {
+                let score = self.urgency(&task, now, config)?;
+                if best.as_ref().map(|(b, _)| score > *b).unwrap_or(true) {
+                    best = Some((score, task));
+                }
+            }
+        }
+        Ok(best.map(|(_, task)| task))
+    }
+
     /// Get pending tasks ordered by priority
     pub fn get_pending_tasks(&self, limit: usize) -> Result<Vec<AsyncTask>> {
         let conn = self.get_conn()?;
@@ -467,9 +1967,13 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
                     status, priority, dependencies, created_at, started_at, completed_at,
-                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
              FROM tasks
              WHERE status = 'pending'
+               AND (next_retry_at IS NULL OR next_retry_at <= now())
+               AND scheduled_at <= now()
              ORDER BY priority DESC, created_at ASC
              LIMIT ?",
         )?;
@@ -486,6 +1990,134 @@ impl Database {
         Ok(tasks)
     }
 
+    /// Get pending tasks routed to a specific worker pool. Pass a literal
+    /// agent name to get only that agent's tasks, or `"*"` for the catch-all
+    /// pool that serves every agent (mirrors [`Database::get_pending_tasks`]).
+    /// Backs [`crate::WorkerRoute`]-based dispatch so two workers can serve
+    /// `agent1` while a separate pool serves everything else, without one
+    /// agent's backlog starving the rest.
+    pub fn get_pending_tasks_for(&self, agent: &str, limit: usize) -> Result<Vec<AsyncTask>> {
+        if agent == "*" {
+            return self.get_pending_tasks(limit);
+        }
+
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
+                    status, priority, dependencies, created_at, started_at, completed_at,
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
+             FROM tasks
+             WHERE status = 'pending'
+               AND agent_name = ?
+               AND (next_retry_at IS NULL OR next_retry_at <= now())
+               AND scheduled_at <= now()
+             ORDER BY priority DESC, created_at ASC
+             LIMIT ?",
+        )?;
+
+        let rows = stmt.query_map(params![agent, limit as i64], |row| {
+            Ok(self.row_to_task(row).unwrap())
+        })?;
+
+        let mut tasks = Vec::new();
+        for task in rows {
+            tasks.push(task?);
+        }
+
+        Ok(tasks)
+    }
+
+    /// Filtered, sorted, paginated task listing backing `GET /api/tasks`.
+    /// Filters by any [`TaskStatus`], `workflow_id`, `agent_name`, a
+    /// created-at range, and a priority floor; sorted by the requested
+    /// [`TaskSortKey`]. `idx_tasks_status` and `idx_tasks_workflow` keep the
+    /// common filters index-backed rather than full scans.
+    pub fn query_tasks(&self, filter: &TaskQueryFilter) -> Result<TaskPage> {
+        let conn = self.get_conn()?;
+
+        let mut where_clause = String::from("WHERE 1 = 1");
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(status) = filter.status {
+            where_clause.push_str(" AND status = ?");
+            params.push(Box::new(status.as_str().to_string()));
+        }
+        if let Some(workflow_id) = filter.workflow_id {
+            where_clause.push_str(" AND workflow_id = ?");
+            params.push(Box::new(workflow_id.to_string()));
+        }
+        if let Some(agent_name) = &filter.agent_name {
+            where_clause.push_str(" AND agent_name = ?");
+            params.push(Box::new(agent_name.clone()));
+        }
+        if let Some(after) = filter.created_after {
+            where_clause.push_str(" AND created_at >= ?");
+            params.push(Box::new(after));
+        }
+        if let Some(before) = filter.created_before {
+            where_clause.push_str(" AND created_at <= ?");
+            params.push(Box::new(before));
+        }
+        if let Some(min_priority) = filter.min_priority {
+            where_clause.push_str(" AND priority >= ?");
+            params.push(Box::new(min_priority.as_i32()));
+        }
+
+        let total: i64 = {
+            let sql = format!("SELECT COUNT(*) FROM tasks {}", where_clause);
+            let param_refs: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+            conn.query_row(&sql, param_refs.as_slice(), |row| row.get(0))?
+        };
+
+        let direction = if filter.descending && filter.sort_key != TaskSortKey::Status {
+            "DESC"
+        } else {
+            "ASC"
+        };
+        let sql = format!(
+            "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
+                    status, priority, dependencies, created_at, started_at, completed_at,
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
+             FROM tasks {where_clause}
+             ORDER BY {column} {direction}, id ASC
+             {limit_clause}OFFSET ?",
+            where_clause = where_clause,
+            column = filter.sort_key.column(),
+            direction = direction,
+            limit_clause = if filter.limit == 0 { "" } else { "LIMIT ? " },
+        );
+
+        if filter.limit > 0 {
+            params.push(Box::new(filter.limit as i64));
+        }
+        params.push(Box::new(filter.offset as i64));
+        let param_refs: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(param_refs.as_slice(), |row| Ok(self.row_to_task(row).unwrap()))?;
+
+        let mut tasks = Vec::new();
+        for task in rows {
+            tasks.push(task?);
+        }
+
+        let next_cursor = if filter.offset + tasks.len() < total as usize {
+            Some(filter.offset + tasks.len())
+        } else {
+            None
+        };
+
+        Ok(TaskPage {
+            tasks,
+            total: total as usize,
+            next_cursor,
+        })
+    }
+
     /// Get tasks by workflow ID
     pub fn get_workflow_tasks(&self, workflow_id: Uuid) -> Result<Vec<AsyncTask>> {
         let conn = self.get_conn()?;
@@ -493,7 +2125,9 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
                     status, priority, dependencies, created_at, started_at, completed_at,
-                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
              FROM tasks
              WHERE workflow_id = ?
              ORDER BY created_at ASC",
@@ -518,7 +2152,9 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, name, description, workflow_id, phase_id, agent_name, agent_instructions,
                     status, priority, dependencies, created_at, started_at, completed_at,
-                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata
+                    result, error, webhook_url, retry_count, max_retries, timeout_seconds, metadata,
+                    next_retry_at, backoff_strategy, backoff_base_seconds, backoff_max_seconds, backoff_factor, error_class, progress, schedule, kind,
+                    cpu_seconds, invocations, scheduled_at, uniq_hash, claimed_by
              FROM tasks
              WHERE workflow_id = ? AND phase_id = ?
              ORDER BY created_at ASC",
@@ -565,6 +2201,26 @@ impl Database {
         Ok(true)
     }
 
+    /// Whether a named agent is currently able to take on a task. An agent that
+    /// has never registered is treated as available, preserving the behavior of
+    /// callers that dispatch to agents without a registry entry; a registered
+    /// agent must be `registered` or `idle` to accept work.
+    pub fn agent_accepts_tasks(&self, agent_name: &str) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let state: Option<String> = conn
+            .query_row(
+                "SELECT state FROM agents WHERE name = ?",
+                params![agent_name],
+                |row| row.get(0),
+            )
+            .ok();
+
+        Ok(match state {
+            Some(state) => state == "registered" || state == "idle",
+            None => true,
+        })
+    }
+
     // ===== Workflow Operations =====
 
     /// Insert a new workflow
@@ -636,6 +2292,184 @@ impl Database {
         Ok(())
     }
 
+    /// Prune a terminal task: fold its status into its workflow's pruned-task
+    /// tally (so workflow status stays computable), then delete the task row and
+    /// its logs. Returns `false` when the task no longer exists.
+    pub fn prune_task(&self, id: Uuid) -> Result<bool> {
+        let conn = self.get_conn()?;
+
+        let mut stmt =
+            conn.prepare("SELECT workflow_id, status FROM tasks WHERE id = ?")?;
+        let mut rows = stmt.query(params![id.to_string()])?;
+        let (workflow_id, status): (Option<String>, String) = match rows.next()? {
+            Some(row) => (row.get(0)?, row.get(1)?),
+            None => return Ok(false),
+        };
+        drop(rows);
+        drop(stmt);
+
+        if let Some(workflow_id) = workflow_id {
+            conn.execute(
+                "INSERT INTO workflow_task_counts (workflow_id, status, count)
+                 VALUES (?, ?, 1)
+                 ON CONFLICT (workflow_id, status) DO UPDATE SET count = count + 1",
+                params![workflow_id, status],
+            )?;
+        }
+
+        conn.execute("DELETE FROM task_logs WHERE task_id = ?", params![id.to_string()])?;
+        conn.execute("DELETE FROM task_udas WHERE task_id = ?", params![id.to_string()])?;
+        conn.execute("DELETE FROM tasks WHERE id = ?", params![id.to_string()])?;
+        Ok(true)
+    }
+
+    /// Delete a task outright, cascading to its `task_logs` and `task_udas`
+    /// rows so `get_task_logs`/`get_task_udas` don't leak orphaned entries.
+    /// An alias for [`Database::prune_task`] under the name the retention
+    /// policy in [`crate::RetentionMode`] is documented against; both apply
+    /// to a single task id and cascade identically.
+    pub fn delete_task(&self, id: Uuid) -> Result<bool> {
+        self.prune_task(id)
+    }
+
+    /// Terminal-task counts for a workflow that were recorded as rows were
+    /// pruned. Combined with the live task rows to compute workflow status.
+    pub fn get_pruned_task_counts(&self, workflow_id: Uuid) -> Result<PrunedTaskCounts> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT status, count FROM workflow_task_counts WHERE workflow_id = ?",
+        )?;
+        let mut rows = stmt.query(params![workflow_id.to_string()])?;
+
+        let mut counts = PrunedTaskCounts::default();
+        while let Some(row) = rows.next()? {
+            let status: String = row.get(0)?;
+            let count: i64 = row.get(1)?;
+            match TaskStatus::from_str(&status) {
+                Some(TaskStatus::Completed) => counts.completed += count,
+                Some(TaskStatus::Failed) => counts.failed += count,
+                Some(TaskStatus::Cancelled) => counts.cancelled += count,
+                _ => {}
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Prune every terminal task whose completion (or creation, if it never
+    /// started) predates `cutoff`. Returns the number of tasks removed.
+    pub fn prune_terminal_tasks_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id FROM tasks
+             WHERE status IN ('completed', 'failed', 'cancelled')
+               AND COALESCE(completed_at, created_at) < ?",
+        )?;
+        let ids: Vec<String> = stmt
+            .query_map(params![cutoff], |row| row.get(0))?
+            .collect::<duckdb::Result<_>>()?;
+        drop(stmt);
+
+        let mut pruned = 0;
+        for id in ids {
+            if let Ok(uuid) = Uuid::parse_str(&id) {
+                if self.prune_task(uuid)? {
+                    pruned += 1;
+                }
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Delete tasks older than `older_than` whose status is in `states`,
+    /// cascading to their `task_logs` and `webhook_deliveries` rows. Returns
+    /// the number of tasks removed. General-purpose maintenance operation for
+    /// operators to schedule; [`TaskQueue::sweep_expired_tasks`] covers the
+    /// common "all terminal statuses" case via [`Database::prune_terminal_tasks_before`].
+    pub fn prune_tasks(&self, older_than: std::time::Duration, states: &[TaskStatus]) -> Result<u64> {
+        if states.is_empty() {
+            return Ok(0);
+        }
+        let age = chrono::Duration::from_std(older_than).unwrap_or_else(|_| chrono::Duration::zero());
+        let cutoff = Utc::now() - age;
+        let ids = self.terminal_task_ids_before(states, cutoff)?;
+
+        let conn = self.get_conn()?;
+        for id in &ids {
+            conn.execute("DELETE FROM task_logs WHERE task_id = ?", params![id])?;
+            conn.execute("DELETE FROM task_udas WHERE task_id = ?", params![id])?;
+            conn.execute(
+                "DELETE FROM webhook_deliveries WHERE task_id = ?",
+                params![id],
+            )?;
+            conn.execute("DELETE FROM tasks WHERE id = ?", params![id])?;
+        }
+        Ok(ids.len() as u64)
+    }
+
+    /// IDs of tasks whose `status` is in `states` and whose `completed_at`
+    /// predates `cutoff`. Shared by [`Database::prune_tasks`] and
+    /// [`Database::archive_tasks`] so the two select the exact same rows.
+    fn terminal_task_ids_before(
+        &self,
+        states: &[TaskStatus],
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<String>> {
+        let placeholders = states.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id FROM tasks
+             WHERE status IN ({placeholders})
+               AND completed_at IS NOT NULL
+               AND completed_at < ?"
+        ))?;
+        let mut query_params: Vec<&dyn duckdb::ToSql> =
+            states.iter().map(|s| s.as_str() as &dyn duckdb::ToSql).collect();
+        query_params.push(&cutoff);
+        let ids = stmt
+            .query_map(query_params.as_slice(), |row| row.get(0))?
+            .collect::<duckdb::Result<_>>()?;
+        Ok(ids)
+    }
+
+    /// Export tasks older than `older_than` (completed/cancelled/failed, same
+    /// selection as [`Database::prune_tasks`]) to a Parquet file at `dest_path`
+    /// via DuckDB's native `COPY ... TO ... (FORMAT PARQUET)`, then delete them
+    /// from the hot tables. History is retained cheaply off the database file
+    /// instead of growing it forever.
+    pub fn archive_tasks(
+        &self,
+        older_than: std::time::Duration,
+        states: &[TaskStatus],
+        dest_path: &Path,
+    ) -> Result<u64> {
+        if states.is_empty() {
+            return Ok(0);
+        }
+        let age = chrono::Duration::from_std(older_than).unwrap_or_else(|_| chrono::Duration::zero());
+        let cutoff = Utc::now() - age;
+        let status_list = states
+            .iter()
+            .map(|s| format!("'{}'", s.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let dest = dest_path.to_string_lossy().replace('\'', "''");
+
+        let conn = self.get_conn()?;
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM tasks
+                        WHERE status IN ({status_list})
+                          AND completed_at IS NOT NULL
+                          AND completed_at < ?)
+                 TO '{dest}' (FORMAT PARQUET)"
+            ),
+            params![cutoff],
+        )?;
+        drop(conn);
+
+        self.prune_tasks(older_than, states)
+    }
+
     // ===== Phase Operations =====
 
     /// Insert a workflow phase
@@ -740,7 +2574,153 @@ impl Database {
             }
         }
 
-        Ok(true)
+        Ok(true)
+    }
+
+    // ===== Schedule Operations =====
+
+    /// Insert a new schedule entry
+    pub fn insert_schedule(&self, entry: &ScheduleEntry) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO schedules (id, name, cron, interval_seconds, end_at, next_fire_at, template, active, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                entry.id.to_string(),
+                entry.name,
+                entry.cron,
+                entry.interval_seconds,
+                entry.end_at,
+                entry.next_fire_at,
+                entry.template,
+                entry.active,
+                entry.created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get active schedules that are due to fire at or before `now`
+    pub fn get_due_schedules(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<ScheduleEntry>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, cron, interval_seconds, end_at, next_fire_at, template, active, created_at
+             FROM schedules
+             WHERE active = TRUE AND next_fire_at <= ?
+             ORDER BY next_fire_at ASC
+             LIMIT ?",
+        )?;
+
+        let rows = stmt.query_map(params![now, limit as i64], |row| {
+            Ok(Self::row_to_schedule(row).unwrap())
+        })?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// List schedules (optionally only active ones)
+    pub fn list_schedules(&self, active_only: bool) -> Result<Vec<ScheduleEntry>> {
+        let conn = self.get_conn()?;
+        let sql = if active_only {
+            "SELECT id, name, cron, interval_seconds, end_at, next_fire_at, template, active, created_at
+             FROM schedules WHERE active = TRUE ORDER BY created_at ASC"
+        } else {
+            "SELECT id, name, cron, interval_seconds, end_at, next_fire_at, template, active, created_at
+             FROM schedules ORDER BY created_at ASC"
+        };
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| Ok(Self::row_to_schedule(row).unwrap()))?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// Advance a schedule's next fire time
+    pub fn update_schedule_next_fire(&self, id: Uuid, next_fire_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE schedules SET next_fire_at = ? WHERE id = ?",
+            params![next_fire_at, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Deactivate a schedule (cancel or reached `end_at`)
+    pub fn deactivate_schedule(&self, id: Uuid) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE schedules SET active = FALSE WHERE id = ?",
+            params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_schedule(row: &duckdb::Row) -> Result<ScheduleEntry> {
+        let id_str: String = row.get(0)?;
+        Ok(ScheduleEntry {
+            id: Uuid::parse_str(&id_str)?,
+            name: row.get(1)?,
+            cron: row.get(2)?,
+            interval_seconds: row.get(3)?,
+            end_at: row.get(4)?,
+            next_fire_at: row.get(5)?,
+            template: row.get(6)?,
+            active: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
+
+    // ===== Workflow Template Operations =====
+
+    /// Insert or replace a reusable workflow template.
+    pub fn upsert_workflow_template(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        blueprint: &str,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO workflow_templates (name, description, blueprint, created_at)
+             VALUES (?, ?, ?, ?)",
+            params![name, description, blueprint, Utc::now()],
+        )?;
+        Ok(())
+    }
+
+    /// Fetch a workflow template blueprint by name.
+    pub fn get_workflow_template(&self, name: &str) -> Result<Option<(String, Option<String>)>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT blueprint, description FROM workflow_templates WHERE name = ?",
+        )?;
+        let mut rows = stmt.query(params![name])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some((row.get(0)?, row.get(1)?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List all workflow template names and descriptions.
+    pub fn list_workflow_templates(&self) -> Result<Vec<(String, Option<String>)>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, description FROM workflow_templates ORDER BY name ASC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
     }
 
     // ===== Logging Operations =====
@@ -750,8 +2730,8 @@ impl Database {
         let conn = self.get_conn()?;
 
         conn.execute(
-            "INSERT INTO task_logs (task_id, timestamp, level, message, metadata)
-             VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO task_logs (seq, task_id, timestamp, level, message, metadata)
+             VALUES (nextval('task_logs_seq'), ?, ?, ?, ?, ?)",
             params![
                 task_id.to_string(),
                 Utc::now(),
@@ -761,6 +2741,11 @@ impl Database {
             ],
         )?;
 
+        self.publish_event(TaskEvent::Log {
+            task_id,
+            level: level.to_string(),
+            message: message.to_string(),
+        });
         Ok(())
     }
 
@@ -769,7 +2754,7 @@ impl Database {
         let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare(
-            "SELECT timestamp, level, message FROM task_logs WHERE task_id = ? ORDER BY timestamp ASC",
+            "SELECT timestamp, level, message FROM task_logs WHERE task_id = ? ORDER BY seq ASC",
         )?;
 
         let rows = stmt.query_map(params![task_id.to_string()], |row| {
@@ -788,6 +2773,228 @@ impl Database {
         Ok(logs)
     }
 
+    /// Get task logs newer than `after_seq`, for incremental tailing. Each entry
+    /// carries its monotonic sequence number so callers can advance the cursor.
+    pub fn get_task_logs_after(
+        &self,
+        task_id: Uuid,
+        after_seq: i64,
+    ) -> Result<Vec<(i64, DateTime<Utc>, String, String)>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT seq, timestamp, level, message FROM task_logs
+             WHERE task_id = ? AND seq > ?
+             ORDER BY seq ASC",
+        )?;
+
+        let rows = stmt.query_map(params![task_id.to_string(), after_seq], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?;
+
+        let mut logs = Vec::new();
+        for log in rows {
+            logs.push(log?);
+        }
+
+        Ok(logs)
+    }
+
+    /// Update a task's execution progress (clamped to 0.0–1.0).
+    pub fn update_task_progress(&self, id: Uuid, progress: f64) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE tasks SET progress = ? WHERE id = ?",
+            params![progress.clamp(0.0, 1.0), id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    // ===== User-Defined Attributes =====
+
+    /// Set (or overwrite) a single user-defined attribute on a task.
+    pub fn set_task_uda(&self, task_id: Uuid, name: &str, value: UdaValue) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO task_udas (task_id, name, value_type, value)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT (task_id, name) DO UPDATE SET value_type = excluded.value_type, value = excluded.value",
+            params![
+                task_id.to_string(),
+                name,
+                value.value_type(),
+                value.to_storage_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All user-defined attributes set on a task, keyed by name.
+    pub fn get_task_udas(&self, task_id: Uuid) -> Result<HashMap<String, UdaValue>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT name, value_type, value FROM task_udas WHERE task_id = ?",
+        )?;
+        let rows = stmt.query_map(params![task_id.to_string()], |row| {
+            let name: String = row.get(0)?;
+            let value_type: String = row.get(1)?;
+            let value: String = row.get(2)?;
+            Ok((name, value_type, value))
+        })?;
+
+        let mut udas = HashMap::new();
+        for row in rows {
+            let (name, value_type, value) = row?;
+            if let Some(parsed) = UdaValue::from_storage(&value_type, &value) {
+                udas.insert(name, parsed);
+            }
+        }
+        Ok(udas)
+    }
+
+    /// Find tasks carrying a UDA named `name` whose stored value matches
+    /// `value` exactly (same type and representation).
+    pub fn find_tasks_by_uda(&self, name: &str, value: &UdaValue) -> Result<Vec<AsyncTask>> {
+        let task_ids = {
+            let conn = self.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT task_id FROM task_udas WHERE name = ? AND value_type = ? AND value = ?",
+            )?;
+            let ids: Vec<String> = stmt
+                .query_map(
+                    params![name, value.value_type(), value.to_storage_string()],
+                    |row| row.get(0),
+                )?
+                .collect::<duckdb::Result<_>>()?;
+            ids
+        };
+
+        let mut tasks = Vec::new();
+        for id_str in task_ids {
+            if let Ok(id) = Uuid::parse_str(&id_str) {
+                if let Some(task) = self.get_task(id)? {
+                    tasks.push(task);
+                }
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// Serialize tasks matching `filter` to a Taskwarrior 2.6 `export`-compatible
+    /// JSON array, so external Taskwarrior-aware tooling can inspect orchestr8
+    /// state. `metadata` and UDAs are flattened onto the object as plain string
+    /// attributes rather than a nested object, matching how `task export` emits
+    /// ad hoc UDAs.
+    pub fn export_tasks_json(&self, filter: &TaskQueryFilter) -> Result<String> {
+        let tasks = self.query_tasks(filter)?.tasks;
+        let mut out = Vec::with_capacity(tasks.len());
+        for task in &tasks {
+            out.push(self.task_to_taskwarrior(task)?);
+        }
+        Ok(serde_json::to_string(&out)?)
+    }
+
+    fn task_to_taskwarrior(&self, task: &AsyncTask) -> Result<TaskwarriorTask> {
+        let mut extra = HashMap::new();
+        if let Some(metadata) = &task.metadata {
+            if let Ok(serde_json::Value::Object(map)) =
+                serde_json::from_str::<serde_json::Value>(metadata)
+            {
+                for (key, value) in map {
+                    let rendered = match value {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    extra.insert(key, rendered);
+                }
+            }
+        }
+        for (name, value) in self.get_task_udas(task.id)? {
+            extra.insert(name, value.to_storage_string());
+        }
+
+        let depends = if task.dependencies.is_empty() {
+            None
+        } else {
+            Some(
+                task.dependencies
+                    .iter()
+                    .map(|dep| dep.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        };
+
+        Ok(TaskwarriorTask {
+            uuid: task.id.to_string(),
+            status: task.status.as_str().to_string(),
+            description: task.name.clone(),
+            entry: format_tw_date(task.created_at),
+            start: task.started_at.map(format_tw_date),
+            end: task.completed_at.map(format_tw_date),
+            depends,
+            extra,
+        })
+    }
+
+    /// Import a Taskwarrior 2.6 `export`-format JSON array, inserting a task for
+    /// each entry. A malformed `uuid`/`depends` on one entry is recorded in the
+    /// returned summary's `errors` rather than aborting the rest of the batch.
+    /// Unknown attributes (anything beyond `uuid`/`status`/`description`/`entry`/
+    /// `start`/`end`/`depends`) are preserved into the new task's `metadata`.
+    pub fn import_tasks_json(&self, json: &str) -> Result<TaskImportSummary> {
+        let raw: Vec<TaskwarriorTask> = serde_json::from_str(json)
+            .context("invalid taskwarrior export: expected a JSON array of tasks")?;
+
+        let mut summary = TaskImportSummary::default();
+        for tw in raw {
+            match self.import_one_taskwarrior(tw) {
+                Ok(()) => summary.imported += 1,
+                Err(e) => {
+                    summary.skipped += 1;
+                    summary.errors.push(e.to_string());
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    fn import_one_taskwarrior(&self, tw: TaskwarriorTask) -> Result<()> {
+        let id =
+            Uuid::parse_str(&tw.uuid).with_context(|| format!("invalid uuid '{}'", tw.uuid))?;
+
+        let mut task = AsyncTask::new(
+            tw.description.clone(),
+            "imported".to_string(),
+            String::new(),
+        );
+        task.id = id;
+        task.status = tw_status_to_task_status(&tw.status);
+        task.created_at = parse_tw_date(&tw.entry).unwrap_or_else(Utc::now);
+        task.started_at = tw.start.as_deref().and_then(parse_tw_date);
+        task.completed_at = tw.end.as_deref().and_then(parse_tw_date);
+
+        if let Some(depends) = &tw.depends {
+            task.dependencies = depends
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(Uuid::parse_str)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .with_context(|| format!("invalid depends list '{}'", depends))?;
+        }
+
+        if !tw.extra.is_empty() {
+            let metadata: serde_json::Map<String, serde_json::Value> = tw
+                .extra
+                .into_iter()
+                .map(|(k, v)| (k, serde_json::Value::String(v)))
+                .collect();
+            task.metadata = Some(serde_json::Value::Object(metadata).to_string());
+        }
+
+        self.insert_task(&task)
+    }
+
     // ===== Helper Methods =====
 
     fn row_to_task(&self, row: &duckdb::Row) -> Result<AsyncTask> {
@@ -796,9 +3003,10 @@ impl Database {
         let status_str: String = row.get(7)?;
         let priority_int: i32 = row.get(8)?;
         let dependencies_json: String = row.get(9)?;
+        let id = Uuid::parse_str(&id_str)?;
 
         Ok(AsyncTask {
-            id: Uuid::parse_str(&id_str)?,
+            id,
             name: row.get(1)?,
             description: row.get(2)?,
             workflow_id: workflow_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
@@ -818,6 +3026,33 @@ impl Database {
             max_retries: row.get(17)?,
             timeout_seconds: row.get(18)?,
             metadata: row.get(19)?,
+            next_retry_at: row.get(20)?,
+            backoff_strategy: {
+                let s: Option<String> = row.get(21)?;
+                s.as_deref()
+                    .and_then(BackoffStrategy::from_str)
+                    .unwrap_or(BackoffStrategy::Exponential)
+            },
+            backoff_base_seconds: row.get::<_, Option<i64>>(22)?.unwrap_or(5),
+            backoff_max_seconds: row.get::<_, Option<i64>>(23)?.unwrap_or(300),
+            backoff_factor: row.get::<_, Option<i64>>(24)?.unwrap_or(2),
+            error_class: {
+                let s: Option<String> = row.get(25)?;
+                s.as_deref().and_then(ErrorClass::from_str)
+            },
+            progress: row.get::<_, Option<f64>>(26)?.unwrap_or(0.0),
+            schedule: row.get(27)?,
+            kind: row
+                .get::<_, Option<String>>(28)?
+                .unwrap_or_else(|| "default".to_string()),
+            cpu_seconds: row.get::<_, Option<f64>>(29)?.unwrap_or(0.0),
+            invocations: row.get::<_, Option<i64>>(30)?.unwrap_or(0),
+            scheduled_at: row
+                .get::<_, Option<DateTime<Utc>>>(31)?
+                .unwrap_or_else(Utc::now),
+            uniq_hash: row.get(32)?,
+            claimed_by: row.get(33)?,
+            udas: self.get_task_udas(id)?,
         })
     }
 
@@ -890,6 +3125,141 @@ mod tests {
         assert!(updated.started_at.is_some());
     }
 
+    #[test]
+    fn test_task_events_published() {
+        let db = Database::in_memory().unwrap();
+        let task = AsyncTask::new(
+            "Observed".to_string(),
+            "agent".to_string(),
+            "watch me".to_string(),
+        );
+        db.insert_task(&task).unwrap();
+
+        let mut rx = db.subscribe_task_events();
+
+        db.add_task_log(task.id, "INFO", "hello", None).unwrap();
+        db.update_task_status(task.id, TaskStatus::Running).unwrap();
+
+        match rx.try_recv().unwrap() {
+            TaskEvent::Log {
+                task_id,
+                level,
+                message,
+            } => {
+                assert_eq!(task_id, task.id);
+                assert_eq!(level, "INFO");
+                assert_eq!(message, "hello");
+            }
+            other => panic!("expected a log event, got {other:?}"),
+        }
+
+        match rx.try_recv().unwrap() {
+            TaskEvent::Status { task_id, status } => {
+                assert_eq!(task_id, task.id);
+                assert_eq!(status, TaskStatus::Running);
+            }
+            other => panic!("expected a status event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_schedule_task_retry() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new(
+            "Retryable".to_string(),
+            "agent".to_string(),
+            "Do it".to_string(),
+        )
+        .with_retry_policy(2, BackoffStrategy::Fixed, 10, 2, 60);
+
+        db.insert_task(&task).unwrap();
+
+        // First failure re-queues the task as pending with a future next_retry_at.
+        let next = db.schedule_task_retry(task.id, "boom").unwrap();
+        assert!(next.is_some());
+        let requeued = db.get_task(task.id).unwrap().unwrap();
+        assert_eq!(requeued.status, TaskStatus::Pending);
+        assert_eq!(requeued.retry_count, 1);
+        assert!(requeued.next_retry_at.is_some());
+
+        // Second failure still within budget.
+        assert!(db.schedule_task_retry(task.id, "boom").unwrap().is_some());
+
+        // Third failure exhausts retries and leaves the task failed.
+        let exhausted = db.schedule_task_retry(task.id, "final").unwrap();
+        assert!(exhausted.is_none());
+        let failed = db.get_task(task.id).unwrap().unwrap();
+        assert_eq!(failed.status, TaskStatus::Failed);
+        assert_eq!(failed.error, Some("final".to_string()));
+    }
+
+    #[test]
+    fn test_reschedule_for_retry_clears_error_without_new_message() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new(
+            "Retryable".to_string(),
+            "agent".to_string(),
+            "Do it".to_string(),
+        )
+        .with_retry_policy(1, BackoffStrategy::Fixed, 10, 2, 60);
+        db.insert_task(&task).unwrap();
+        db.update_task_error(task.id, "transient".to_string())
+            .unwrap();
+
+        let next = db.reschedule_for_retry(task.id).unwrap();
+        assert!(next.is_some());
+        let requeued = db.get_task(task.id).unwrap().unwrap();
+        assert_eq!(requeued.status, TaskStatus::Pending);
+        assert_eq!(requeued.retry_count, 1);
+        assert!(requeued.error.is_none());
+
+        // Retries are exhausted now, so another reschedule is refused.
+        assert!(db.reschedule_for_retry(task.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_backoff_delay_policies() {
+        let base = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string());
+
+        // Fixed ignores the attempt count.
+        let fixed = AsyncTask {
+            backoff_strategy: BackoffStrategy::Fixed,
+            backoff_base_seconds: 7,
+            retry_count: 4,
+            ..base.clone()
+        };
+        assert_eq!(fixed.next_retry_delay_seconds(), 7);
+
+        // Exponential grows as base * factor^attempt, capped at max.
+        let exp = AsyncTask {
+            backoff_strategy: BackoffStrategy::Exponential,
+            backoff_base_seconds: 10,
+            backoff_factor: 3,
+            backoff_max_seconds: 1000,
+            retry_count: 2,
+            ..base.clone()
+        };
+        // 10 * 3^2 = 90, within ±10% jitter.
+        let delay = exp.next_retry_delay_seconds();
+        assert!((81..=99).contains(&delay), "unexpected delay {delay}");
+
+        // Full jitter stays within [0, capped delay].
+        let jitter = AsyncTask {
+            backoff_strategy: BackoffStrategy::ExponentialJitter,
+            backoff_base_seconds: 4,
+            backoff_factor: 2,
+            backoff_max_seconds: 1000,
+            retry_count: 3,
+            ..base
+        };
+        // Cap is 4 * 2^3 = 32.
+        for _ in 0..50 {
+            assert!((0..=32).contains(&jitter.next_retry_delay_seconds()));
+        }
+    }
+
     #[test]
     fn test_workflow_and_phases() {
         let db = Database::in_memory().unwrap();
@@ -924,4 +3294,478 @@ mod tests {
         assert_eq!(phases.len(), 1);
         assert_eq!(phases[0].phase_id, "phase1");
     }
+
+    #[test]
+    fn test_query_tasks_filters_and_paginates() {
+        let db = Database::in_memory().unwrap();
+
+        for (name, agent, priority) in [
+            ("a", "agent-a", TaskPriority::Low),
+            ("b", "agent-a", TaskPriority::High),
+            ("c", "agent-b", TaskPriority::Normal),
+        ] {
+            let mut task = AsyncTask::new(name.to_string(), agent.to_string(), "do".to_string());
+            task.priority = priority;
+            db.insert_task(&task).unwrap();
+        }
+
+        let page = db
+            .query_tasks(&TaskQueryFilter {
+                agent_name: Some("agent-a".to_string()),
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.tasks.len(), 2);
+        assert!(page.next_cursor.is_none());
+
+        let first_page = db
+            .query_tasks(&TaskQueryFilter {
+                limit: 2,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(first_page.tasks.len(), 2);
+        assert_eq!(first_page.total, 3);
+        assert_eq!(first_page.next_cursor, Some(2));
+
+        let second_page = db
+            .query_tasks(&TaskQueryFilter {
+                limit: 2,
+                offset: first_page.next_cursor.unwrap(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(second_page.tasks.len(), 1);
+        assert!(second_page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_delayed_task_excluded_until_due() {
+        let db = Database::in_memory().unwrap();
+
+        let future = AsyncTask::new("future".to_string(), "agent".to_string(), "do".to_string())
+            .with_delay(Utc::now() + chrono::Duration::hours(1));
+        db.insert_task(&future).unwrap();
+
+        let due = AsyncTask::new("due".to_string(), "agent".to_string(), "do".to_string());
+        db.insert_task(&due).unwrap();
+
+        let pending = db.get_pending_tasks(10).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, due.id);
+    }
+
+    #[test]
+    fn test_with_cron_rejects_invalid_expression() {
+        let task = AsyncTask::new("t".to_string(), "agent".to_string(), "do".to_string());
+        assert!(task.with_cron("not a cron expression").is_err());
+    }
+
+    #[test]
+    fn test_spawn_next_cron_occurrence() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new("recurring".to_string(), "agent".to_string(), "do".to_string())
+            .with_cron("0 * * * * *")
+            .unwrap();
+        db.insert_task(&task).unwrap();
+
+        let next = db.spawn_next_cron_occurrence(&task).unwrap().unwrap();
+        assert_ne!(next.id, task.id);
+        assert_eq!(next.agent_name, task.agent_name);
+        assert_eq!(next.schedule, task.schedule);
+        assert!(next.scheduled_at > task.created_at);
+
+        let plain_task = AsyncTask::new("one-off".to_string(), "agent".to_string(), "do".to_string());
+        assert!(db.spawn_next_cron_occurrence(&plain_task).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_pending_tasks_for_filters_by_agent_with_wildcard() {
+        let db = Database::in_memory().unwrap();
+
+        let a = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        let b = AsyncTask::new("t".to_string(), "agent-b".to_string(), "do it".to_string());
+        db.insert_task(&a).unwrap();
+        db.insert_task(&b).unwrap();
+
+        let for_a = db.get_pending_tasks_for("agent-a", 10).unwrap();
+        assert_eq!(for_a.len(), 1);
+        assert_eq!(for_a[0].id, a.id);
+
+        let for_wildcard = db.get_pending_tasks_for("*", 10).unwrap();
+        assert_eq!(for_wildcard.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_task_unique_dedupes_in_flight_retries() {
+        let db = Database::in_memory().unwrap();
+
+        let first = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        let first_id = db.insert_task_unique(first).unwrap();
+
+        // A retried submission with the same agent/instructions is recognized
+        // as the same in-flight work and doesn't spawn a second task.
+        let retry = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        let retry_id = db.insert_task_unique(retry).unwrap();
+        assert_eq!(first_id, retry_id);
+
+        let page = db.query_tasks(&TaskQueryFilter::default()).unwrap();
+        assert_eq!(page.total, 1);
+
+        // Once the original completes, a later submission is free to run again.
+        db.update_task_status(first_id, TaskStatus::Completed).unwrap();
+        let after_completion =
+            AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        let new_id = db.insert_task_unique(after_completion).unwrap();
+        assert_ne!(new_id, first_id);
+    }
+
+    #[test]
+    fn test_with_uniqueness_dedupes_across_workflow_phases() {
+        let db = Database::in_memory().unwrap();
+
+        let mut first = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        first.description = Some("send the weekly report".to_string());
+        first = first.with_uniqueness().with_phase("phase-1".to_string());
+        let first_id = db.insert_task_unique(first).unwrap();
+
+        // Same agent/description but a different phase: dedup_hash() alone
+        // would treat this as distinct work, but with_uniqueness() hashes
+        // only agent + description, so it's recognized as a re-submission.
+        let mut retry = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        retry.description = Some("send the weekly report".to_string());
+        retry = retry.with_uniqueness().with_phase("phase-2".to_string());
+        let retry_id = db.insert_task_unique(retry).unwrap();
+        assert_eq!(first_id, retry_id);
+
+        let stored = db.get_task_by_hash(&db.get_task(first_id).unwrap().unwrap().uniq_hash.unwrap());
+        assert_eq!(stored.unwrap().unwrap().id, first_id);
+        assert!(db.get_task_by_hash("no-such-hash").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_claim_task_wins_once() {
+        let db = Database::in_memory().unwrap();
+        let task = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string());
+        let id = task.id;
+        db.insert_task(&task).unwrap();
+
+        assert!(db.claim_task(id, "worker-1").unwrap());
+        // A second claim of the now-running task loses the race.
+        assert!(!db.claim_task(id, "worker-2").unwrap());
+
+        let claimed = db.get_task(id).unwrap().unwrap();
+        assert_eq!(claimed.status, TaskStatus::Running);
+        assert_eq!(claimed.claimed_by, Some("worker-1".to_string()));
+    }
+
+    #[test]
+    fn test_claim_next_tasks_respects_priority_and_limit() {
+        let db = Database::in_memory().unwrap();
+        let low = AsyncTask::new("t".to_string(), "agent-a".to_string(), "low".to_string())
+            .with_priority(TaskPriority::Low);
+        let high = AsyncTask::new("t".to_string(), "agent-a".to_string(), "high".to_string())
+            .with_priority(TaskPriority::High);
+        let high_id = high.id;
+        db.insert_task(&low).unwrap();
+        db.insert_task(&high).unwrap();
+
+        let claimed = db.claim_next_tasks("worker-1", 1).unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, high_id);
+        assert_eq!(claimed[0].status, TaskStatus::Running);
+        assert_eq!(claimed[0].claimed_by, Some("worker-1".to_string()));
+
+        // The lower-priority task is still pending and unclaimed.
+        let remaining = db.query_tasks(&TaskQueryFilter::default()).unwrap();
+        let still_pending = remaining
+            .tasks
+            .iter()
+            .find(|t| t.id != high_id)
+            .unwrap();
+        assert_eq!(still_pending.status, TaskStatus::Pending);
+        assert!(still_pending.claimed_by.is_none());
+    }
+
+    #[test]
+    fn test_prune_tasks_cascades_and_filters_by_status() {
+        let db = Database::in_memory().unwrap();
+
+        let done = AsyncTask::new("t".to_string(), "agent-a".to_string(), "done".to_string());
+        let done_id = done.id;
+        db.insert_task(&done).unwrap();
+        db.update_task_status(done_id, TaskStatus::Completed)
+            .unwrap();
+        db.add_task_log(done_id, "INFO", "finished", None).unwrap();
+
+        let running = AsyncTask::new("t".to_string(), "agent-a".to_string(), "running".to_string());
+        let running_id = running.id;
+        db.insert_task(&running).unwrap();
+        db.update_task_status(running_id, TaskStatus::Running)
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let removed = db
+            .prune_tasks(
+                std::time::Duration::from_secs(0),
+                &[TaskStatus::Completed, TaskStatus::Cancelled],
+            )
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(db.get_task(done_id).unwrap().is_none());
+        assert!(db.get_task_logs(done_id).unwrap().is_empty());
+        // The still-running task is untouched regardless of age, since its
+        // status isn't in the pruned set.
+        assert!(db.get_task(running_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_reclaim_stale_running_retries_then_fails() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do it".to_string())
+            .with_retry_policy(1, BackoffStrategy::Fixed, 10, 2, 60);
+        let id = task.id;
+        db.insert_task(&task).unwrap();
+        db.update_task_status(id, TaskStatus::Running).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        // First reclaim: still within budget, so it's retried as pending.
+        let reclaimed = db
+            .reclaim_stale_running(std::time::Duration::from_millis(0))
+            .unwrap();
+        assert_eq!(reclaimed, vec![id]);
+        let retried = db.get_task(id).unwrap().unwrap();
+        assert_eq!(retried.status, TaskStatus::Pending);
+        assert_eq!(retried.retry_count, 1);
+
+        // Run it again and exhaust the single retry budget.
+        db.update_task_status(id, TaskStatus::Running).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let reclaimed = db
+            .reclaim_stale_running(std::time::Duration::from_millis(0))
+            .unwrap();
+        assert_eq!(reclaimed, vec![id]);
+        let failed = db.get_task(id).unwrap().unwrap();
+        assert_eq!(failed.status, TaskStatus::Failed);
+        assert_eq!(failed.error, Some("reclaimed: worker timeout".to_string()));
+    }
+
+    #[test]
+    fn test_get_next_ready_task_prefers_blocking_over_blocked() {
+        let db = Database::in_memory().unwrap();
+        let config = UrgencyConfig::default();
+
+        // A low-priority task that several others depend on should outrank a
+        // normal-priority, non-blocking task.
+        let blocker = AsyncTask::new("t".to_string(), "a".to_string(), "blocker".to_string())
+            .with_priority(TaskPriority::Low);
+        let blocker_id = blocker.id;
+        db.insert_task(&blocker).unwrap();
+
+        let plain = AsyncTask::new("t".to_string(), "a".to_string(), "plain".to_string())
+            .with_priority(TaskPriority::Normal);
+        db.insert_task(&plain).unwrap();
+
+        for _ in 0..3 {
+            let dependent = AsyncTask::new("t".to_string(), "a".to_string(), "dep".to_string())
+                .with_dependencies(vec![blocker_id]);
+            db.insert_task(&dependent).unwrap();
+        }
+
+        let ready = db.get_next_ready_task(&config).unwrap().unwrap();
+        assert_eq!(ready.id, blocker_id);
+
+        // A task with unmet dependencies is never selected, regardless of its
+        // own score.
+        let blocked = AsyncTask::new("t".to_string(), "a".to_string(), "blocked".to_string())
+            .with_priority(TaskPriority::Critical)
+            .with_dependencies(vec![Uuid::new_v4()]);
+        db.insert_task(&blocked).unwrap();
+        let still_blocker = db.get_next_ready_task(&config).unwrap().unwrap();
+        assert_eq!(still_blocker.id, blocker_id);
+    }
+
+    #[test]
+    fn test_task_udas_round_trip_and_find() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string());
+        let id = task.id;
+        db.insert_task(&task).unwrap();
+
+        db.set_task_uda(id, "ticket", UdaValue::String("ORCH-42".to_string()))
+            .unwrap();
+        db.set_task_uda(id, "cost_estimate", UdaValue::Number(12.5))
+            .unwrap();
+
+        let udas = db.get_task_udas(id).unwrap();
+        assert_eq!(
+            udas.get("ticket"),
+            Some(&UdaValue::String("ORCH-42".to_string()))
+        );
+        assert_eq!(udas.get("cost_estimate"), Some(&UdaValue::Number(12.5)));
+
+        // Round-trips through `get_task`/`row_to_task` too.
+        let reloaded = db.get_task(id).unwrap().unwrap();
+        assert_eq!(reloaded.udas.get("ticket"), udas.get("ticket"));
+
+        let found = db
+            .find_tasks_by_uda("ticket", &UdaValue::String("ORCH-42".to_string()))
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, id);
+
+        // Overwriting a UDA replaces rather than duplicates it.
+        db.set_task_uda(id, "ticket", UdaValue::String("ORCH-43".to_string()))
+            .unwrap();
+        let udas = db.get_task_udas(id).unwrap();
+        assert_eq!(udas.len(), 2);
+        assert_eq!(
+            udas.get("ticket"),
+            Some(&UdaValue::String("ORCH-43".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_taskwarrior_export_import_round_trip() {
+        let db = Database::in_memory().unwrap();
+
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string())
+            .with_metadata(serde_json::json!({"ticket": "ORCH-1"}));
+        let id = task.id;
+        db.insert_task(&task).unwrap();
+        db.update_task_status(id, TaskStatus::Completed).unwrap();
+        db.set_task_uda(id, "cost_estimate", UdaValue::Number(4.0))
+            .unwrap();
+
+        let filter = TaskQueryFilter {
+            limit: 10,
+            ..Default::default()
+        };
+        let exported = db.export_tasks_json(&filter).unwrap();
+        assert!(exported.contains(&format!("\"uuid\":\"{}\"", id)));
+        assert!(exported.contains("\"status\":\"completed\""));
+        assert!(exported.contains("\"ticket\":\"ORCH-1\""));
+        assert!(exported.contains("\"cost_estimate\":\"4\""));
+
+        let other_db = Database::in_memory().unwrap();
+        let summary = other_db.import_tasks_json(&exported).unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 0);
+        assert!(summary.errors.is_empty());
+
+        let imported = other_db.get_task(id).unwrap().unwrap();
+        assert_eq!(imported.status, TaskStatus::Completed);
+        assert_eq!(imported.name, "t");
+        let metadata: serde_json::Value =
+            serde_json::from_str(&imported.metadata.unwrap()).unwrap();
+        assert_eq!(metadata["ticket"], "ORCH-1");
+        assert_eq!(metadata["cost_estimate"], "4");
+    }
+
+    #[test]
+    fn test_taskwarrior_import_collects_bad_uuid_errors() {
+        let db = Database::in_memory().unwrap();
+        let batch = serde_json::json!([
+            {"uuid": "not-a-uuid", "status": "pending", "description": "bad", "entry": "20240101T000000Z"},
+            {"uuid": Uuid::new_v4().to_string(), "status": "deleted", "description": "good", "entry": "20240101T000000Z"},
+        ])
+        .to_string();
+
+        let summary = db.import_tasks_json(&batch).unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.errors.len(), 1);
+        assert!(summary.errors[0].contains("not-a-uuid"));
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent_and_applies_every_migration() {
+        let db = Database::in_memory().unwrap();
+
+        let conn = db.get_conn().unwrap();
+        let version: i64 = conn
+            .query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.last().unwrap().version as i64);
+
+        // Re-running migrate (as happens on every `Database::in_memory`/`new`)
+        // applies nothing new and doesn't error.
+        let reapplied = db.migrate().unwrap();
+        assert_eq!(reapplied as i64, version);
+    }
+
+    #[test]
+    fn test_migrate_rejects_a_future_on_disk_version() {
+        let db = Database::in_memory().unwrap();
+        let conn = db.get_conn().unwrap();
+        conn.execute(
+            "INSERT INTO schema_version (version, applied_at) VALUES (?, ?)",
+            params![999_i64, Utc::now()],
+        )
+        .unwrap();
+        drop(conn);
+
+        let err = db.migrate().unwrap_err();
+        assert!(err.to_string().contains("999"));
+    }
+
+    #[test]
+    fn test_schema_handle_requires_matching_version() {
+        let db = Database::in_memory().unwrap();
+
+        // Migrated in_memory databases are at the latest version, V2.
+        assert!(db.schema_handle::<V2>().is_ok());
+        // V1 no longer matches once migration 2 has applied.
+        assert!(db.schema_handle::<V1>().is_err());
+    }
+
+    #[test]
+    fn test_schedule_retry_with_explicit_backoff() {
+        let db = Database::in_memory().unwrap();
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string())
+            .with_retry_policy(1, BackoffStrategy::Fixed, 10, 2, 60);
+        let id = task.id;
+        db.insert_task(&task).unwrap();
+
+        let next = db.schedule_retry(id, 30, "boom").unwrap().unwrap();
+        assert!(next > Utc::now() + chrono::Duration::seconds(25));
+        let retried = db.get_task(id).unwrap().unwrap();
+        assert_eq!(retried.status, TaskStatus::Pending);
+        assert_eq!(retried.retry_count, 1);
+        assert_eq!(retried.error, Some("boom".to_string()));
+
+        // Retry budget (max_retries = 1) is now exhausted.
+        let result = db.schedule_retry(id, 30, "boom again").unwrap();
+        assert_eq!(result, None);
+        let failed = db.get_task(id).unwrap().unwrap();
+        assert_eq!(failed.status, TaskStatus::Failed);
+    }
+
+    #[test]
+    fn test_with_schedule_kind_once_and_cron() {
+        let at = Utc::now() + chrono::Duration::hours(2);
+        let once = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string())
+            .with_schedule_kind(Schedule::Once(at))
+            .unwrap();
+        assert_eq!(once.scheduled_at, at);
+        assert!(once.schedule.is_none());
+
+        let cron = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string())
+            .with_schedule_kind(Schedule::Cron("0 * * * * *".to_string()))
+            .unwrap();
+        assert_eq!(cron.schedule.as_deref(), Some("0 * * * * *"));
+
+        let bad = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string())
+            .with_schedule_kind(Schedule::Cron("not a cron expression".to_string()));
+        assert!(bad.is_err());
+    }
 }
@@ -0,0 +1,107 @@
+use crate::db::{AsyncTask, Database};
+use crate::queue::TaskResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// Why a [`CancellationToken`] was tripped. Distinguishes a graceful pause
+/// (checkpoint and stop, resumable later) from a hard abort (stop now, mark
+/// cancelled).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    Pause,
+    Abort,
+}
+
+/// A cooperative cancellation handle shared between the queue and a running
+/// [`TaskRunner`]. Long-running runners poll [`CancellationToken::reason`]
+/// between units of work and stop when it returns `Some`.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<AtomicU8>,
+}
+
+impl CancellationToken {
+    const RUNNING: u8 = 0;
+    const PAUSE: u8 = 1;
+    const ABORT: u8 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request a graceful pause. A later [`abort`](Self::abort) still wins.
+    pub fn pause(&self) {
+        let _ = self.state.compare_exchange(
+            Self::RUNNING,
+            Self::PAUSE,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+    }
+
+    /// Request a hard abort. Overrides a pending pause.
+    pub fn abort(&self) {
+        self.state.store(Self::ABORT, Ordering::SeqCst);
+    }
+
+    /// The reason the token was tripped, or `None` while still running.
+    pub fn reason(&self) -> Option<CancelReason> {
+        match self.state.load(Ordering::SeqCst) {
+            Self::PAUSE => Some(CancelReason::Pause),
+            Self::ABORT => Some(CancelReason::Abort),
+            _ => None,
+        }
+    }
+
+    /// Whether any cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+}
+
+/// Shared dependencies handed to every [`TaskRunner`] when it executes a task.
+///
+/// Cloned per invocation (all fields are cheap `Arc`s), so runners can hold the
+/// context for the lifetime of a long-running task without blocking others.
+#[derive(Clone)]
+pub struct AppContext {
+    pub db: Arc<Database>,
+}
+
+impl AppContext {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+}
+
+/// A handler for a particular [`AsyncTask::kind`]. Implementors do the real
+/// work of a task — invoking an agent, calling out to a tool, running a
+/// computation — and return a [`TaskResult`] the queue persists and fans out.
+#[async_trait]
+pub trait TaskRunner: Send + Sync {
+    async fn run(
+        &self,
+        task: &AsyncTask,
+        ctx: &AppContext,
+        cancel: &CancellationToken,
+    ) -> TaskResult;
+}
+
+/// Map of task kind to the runner responsible for it. Workers look up the
+/// runner for `task.kind` and fall back to an error result when none is
+/// registered.
+pub type RunnerRegistry = HashMap<String, Arc<dyn TaskRunner>>;
+
+/// Build a [`TaskResult`] for a task whose kind has no registered runner.
+pub(crate) fn unknown_kind_result(task: &AsyncTask) -> TaskResult {
+    TaskResult {
+        task_id: task.id,
+        success: false,
+        result: None,
+        error: Some(format!("No runner registered for task kind '{}'", task.kind)),
+        // A missing runner is a configuration fault, not a transient error.
+        error_class: Some(crate::db::ErrorClass::Fatal),
+    }
+}
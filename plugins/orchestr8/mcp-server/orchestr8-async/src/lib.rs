@@ -1,11 +1,37 @@
+pub mod agents;
 pub mod api;
+pub mod artifacts;
 pub mod db;
+pub mod executor;
 pub mod mcp;
+pub mod notifier;
 pub mod queue;
+pub mod rpc;
+pub mod runner;
+pub mod scheduler;
+pub mod usage;
 pub mod webhook;
 
-pub use db::{AsyncTask, Database, TaskPriority, TaskStatus, Workflow, WorkflowPhase};
-pub use queue::{TaskQueue, WorkflowExecutor, WorkflowStatus};
+pub use db::{
+    AsyncTask, BackoffStrategy, Database, ErrorClass, PrunedTaskCounts, Schedule, SchemaHandle,
+    SchemaVersion, ScheduleEntry, TaskEvent, TaskImportSummary, TaskPage, TaskPriority,
+    TaskQueryFilter, TaskSortKey, TaskStatus, UdaValue, UrgencyConfig, Workflow, WorkflowPhase,
+    V1, V2,
+};
+pub use queue::{
+    RetentionConfig, RetentionMode, ShutdownSummary, SubmissionPhase, SubmissionTask, TaskFailure,
+    TaskQueue, TemplatePhase, TemplateTask, WorkerRoute, WorkflowExecutor, WorkflowStatus,
+    WorkflowSubmission, WorkflowTemplate,
+};
+pub use notifier::{
+    NotificationChannel, NotificationEvent, NotificationSubscription, Notifier, NotifierConfig,
+};
+pub use agents::{Agent, AgentConfig, AgentRegistry, AgentState};
+pub use executor::{AppContext, CancelReason, CancellationToken, RunnerRegistry, TaskRunner};
+pub use artifacts::{ArtifactConfig, ArtifactReference, ArtifactStore};
+pub use runner::{Runner, RunnerConfig, RunnerCoordinator, RunnerStatus};
+pub use scheduler::{Scheduler, TaskTemplate};
+pub use usage::{TaskUsageReport, UsageMeter, UsageQuery, UsageRates, UsageTotals};
 pub use webhook::{WebhookConfig, WebhookManager, WebhookPayload};
 
 use anyhow::Result;
@@ -16,17 +42,36 @@ pub async fn init_system(db_path: &str, worker_count: usize) -> Result<AsyncSyst
     let db = Arc::new(Database::new(db_path)?);
     let queue = Arc::new(TaskQueue::new(db.clone(), worker_count));
     let executor = Arc::new(WorkflowExecutor::new(db.clone(), queue.clone()));
-    let webhook_manager = Arc::new(WebhookManager::with_defaults(db.clone())?);
+    let scheduler = Arc::new(Scheduler::new(db.clone(), queue.clone()));
+    let artifacts = Arc::new(ArtifactStore::with_defaults(db.clone()));
+    let webhook_manager = Arc::new(
+        WebhookManager::with_defaults(db.clone())?.with_artifact_store(artifacts.clone()),
+    );
+    let notifier = Arc::new(Notifier::with_defaults(db.clone())?);
+    let runner = Arc::new(RunnerCoordinator::with_defaults(db.clone()));
+    let agents = Arc::new(AgentRegistry::with_defaults(db.clone()));
+    let usage = Arc::new(UsageMeter::with_defaults(db.clone()));
 
     // Start workers
     queue.start().await?;
+    scheduler.start().await?;
     webhook_manager.start_worker().await?;
+    notifier.start_worker().await?;
+    runner.start_reaper().await?;
+    agents.start_reaper().await?;
+    usage.start_aggregator().await?;
 
     Ok(AsyncSystem {
         db,
         queue,
         executor,
+        scheduler,
         webhook_manager,
+        notifier,
+        runner,
+        artifacts,
+        agents,
+        usage,
     })
 }
 
@@ -35,7 +80,13 @@ pub struct AsyncSystem {
     pub db: Arc<Database>,
     pub queue: Arc<TaskQueue>,
     pub executor: Arc<WorkflowExecutor>,
+    pub scheduler: Arc<Scheduler>,
     pub webhook_manager: Arc<WebhookManager>,
+    pub notifier: Arc<Notifier>,
+    pub runner: Arc<RunnerCoordinator>,
+    pub artifacts: Arc<ArtifactStore>,
+    pub agents: Arc<AgentRegistry>,
+    pub usage: Arc<UsageMeter>,
 }
 
 impl AsyncSystem {
@@ -46,6 +97,11 @@ impl AsyncSystem {
             queue: self.queue.clone(),
             executor: self.executor.clone(),
             webhook_manager: self.webhook_manager.clone(),
+            runner: self.runner.clone(),
+            artifacts: self.artifacts.clone(),
+            agents: self.agents.clone(),
+            usage: self.usage.clone(),
+            security: Arc::new(api::ApiSecurity::from_env()),
         }
     }
 
@@ -54,6 +110,15 @@ impl AsyncSystem {
         self.queue.shutdown()?;
         Ok(())
     }
+
+    /// Shutdown the system gracefully, draining in-flight tasks within the given
+    /// timeout and returning a summary of what completed versus was abandoned.
+    pub async fn shutdown_graceful(
+        &self,
+        drain_timeout: std::time::Duration,
+    ) -> Result<ShutdownSummary> {
+        self.queue.shutdown_graceful(drain_timeout).await
+    }
 }
 
 #[cfg(test)]
@@ -76,9 +141,31 @@ mod tests {
                     2,
                 )),
             )),
+            scheduler: Arc::new(Scheduler::new(
+                Arc::new(Database::in_memory().unwrap()),
+                Arc::new(TaskQueue::new(
+                    Arc::new(Database::in_memory().unwrap()),
+                    2,
+                )),
+            )),
             webhook_manager: Arc::new(
                 WebhookManager::with_defaults(Arc::new(Database::in_memory().unwrap())).unwrap(),
             ),
+            notifier: Arc::new(
+                Notifier::with_defaults(Arc::new(Database::in_memory().unwrap())).unwrap(),
+            ),
+            runner: Arc::new(RunnerCoordinator::with_defaults(Arc::new(
+                Database::in_memory().unwrap(),
+            ))),
+            artifacts: Arc::new(ArtifactStore::with_defaults(Arc::new(
+                Database::in_memory().unwrap(),
+            ))),
+            agents: Arc::new(AgentRegistry::with_defaults(Arc::new(
+                Database::in_memory().unwrap(),
+            ))),
+            usage: Arc::new(UsageMeter::with_defaults(Arc::new(
+                Database::in_memory().unwrap(),
+            ))),
         };
 
         assert!(system.db.get_conn().is_ok());
@@ -84,17 +84,204 @@ async fn run_api_server(system: orchestr8_async::AsyncSystem) -> Result<()> {
         .parse::<SocketAddr>()
         .context("Invalid address")?;
 
-    info!("Starting API server on http://{}", addr);
-
     let app = create_router(system.api_state());
 
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .context("Failed to bind to address")?;
-
-    axum::serve(listener, app)
-        .await
-        .context("Failed to run server")?;
+    // Opt into TLS when both cert and key are configured; otherwise keep
+    // serving plaintext (current behavior, still the default for localhost).
+    match tls_config_from_env()? {
+        Some(config) => {
+            info!("Starting API server on https://{}", addr);
+            axum_server::bind_rustls(addr, config)
+                .serve(app.into_make_service())
+                .await
+                .context("Failed to run TLS server")?;
+        }
+        None => {
+            info!("Starting API server on http://{}", addr);
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .context("Failed to bind to address")?;
+            axum::serve(listener, app)
+                .await
+                .context("Failed to run server")?;
+        }
+    }
 
     Ok(())
 }
+
+// Build a rustls server config from `ORCHESTR8_TLS_CERT`/`ORCHESTR8_TLS_KEY`
+// PEM files, or `None` to keep serving plaintext. When set, also honors:
+//   - `ORCHESTR8_TLS_MIN_VERSION` ("1.2" or "1.3", default "1.2")
+//   - `ORCHESTR8_TLS_CLIENT_CA`, a CA bundle PEM; when present, clients must
+//     present a certificate signed by it (mutual TLS).
+fn tls_config_from_env() -> Result<Option<axum_server::tls_rustls::RustlsConfig>> {
+    let (cert_path, key_path) = match (
+        env::var("ORCHESTR8_TLS_CERT").ok(),
+        env::var("ORCHESTR8_TLS_KEY").ok(),
+    ) {
+        (Some(cert), Some(key)) => (cert, key),
+        _ => return Ok(None),
+    };
+
+    let certs = load_certs(&cert_path)?;
+    let key = load_private_key(&key_path)?;
+
+    let protocol_versions = tls_protocol_versions(env::var("ORCHESTR8_TLS_MIN_VERSION").as_deref());
+
+    let builder = rustls::ServerConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(protocol_versions)
+        .context("Unsupported TLS protocol version")?;
+
+    let server_config = match env::var("ORCHESTR8_TLS_CLIENT_CA").ok() {
+        Some(ca_path) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for cert in load_certs(&ca_path)? {
+                roots.add(&cert).context("Invalid client CA certificate")?;
+            }
+            let verifier = rustls::server::AllowAnyAuthenticatedClient::new(roots);
+            builder
+                .with_client_cert_verifier(Arc::new(verifier))
+                .with_single_cert(certs, key)
+                .context("Invalid TLS certificate/key pair")?
+        }
+        None => builder
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .context("Invalid TLS certificate/key pair")?,
+    };
+
+    Ok(Some(axum_server::tls_rustls::RustlsConfig::from_config(
+        Arc::new(server_config),
+    )))
+}
+
+fn load_certs(path: &str) -> Result<Vec<rustls::Certificate>> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {path}"))?;
+    let mut reader = std::io::BufReader::new(file);
+    let certs = rustls_pemfile::certs(&mut reader)
+        .with_context(|| format!("Failed to parse certificates from {path}"))?;
+    Ok(certs.into_iter().map(rustls::Certificate).collect())
+}
+
+fn load_private_key(path: &str) -> Result<rustls::PrivateKey> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {path}"))?;
+    let mut reader = std::io::BufReader::new(file);
+    let mut keys = rustls_pemfile::pkcs8_private_keys(&mut reader)
+        .with_context(|| format!("Failed to parse a PKCS8 private key from {path}"))?;
+    if keys.is_empty() {
+        anyhow::bail!("No private key found in {path}");
+    }
+    Ok(rustls::PrivateKey(keys.remove(0)))
+}
+
+/// Protocol versions to pass to `rustls::ServerConfig::builder()` for a given
+/// `ORCHESTR8_TLS_MIN_VERSION` value. `with_protocol_versions` enables
+/// *exactly* the versions passed rather than "this version and up", so
+/// honoring "minimum" means listing every version from the floor up to the
+/// newest we support -- rustls then negotiates the highest one the client
+/// also offers. A min of "1.2" (the default, including an unset/unrecognized
+/// value) must still allow a 1.3 handshake.
+fn tls_protocol_versions(
+    min_version: Result<&str, env::VarError>,
+) -> &'static [&'static rustls::SupportedProtocolVersion] {
+    match min_version {
+        Ok("1.3") => &[&rustls::version::TLS13],
+        _ => &[&rustls::version::TLS13, &rustls::version::TLS12],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_1_2_still_lists_1_3() {
+        let versions = tls_protocol_versions(Ok("1.2"));
+        assert!(versions.contains(&&rustls::version::TLS13));
+        assert!(versions.contains(&&rustls::version::TLS12));
+    }
+
+    #[test]
+    fn unset_defaults_to_1_2_and_still_lists_1_3() {
+        let versions = tls_protocol_versions(Err(env::VarError::NotPresent));
+        assert!(versions.contains(&&rustls::version::TLS13));
+        assert!(versions.contains(&&rustls::version::TLS12));
+    }
+
+    #[test]
+    fn min_1_3_excludes_1_2() {
+        let versions = tls_protocol_versions(Ok("1.3"));
+        assert_eq!(versions, &[&rustls::version::TLS13]);
+    }
+
+    /// End-to-end regression test for the bug this review comment caught: a
+    /// server built with the default ("1.2") min version must still complete
+    /// a handshake against a client that offers only TLS 1.3. Drives the
+    /// handshake over in-memory buffers -- no sockets, no real server.
+    #[test]
+    fn min_1_2_server_accepts_1_3_only_client() {
+        use rcgen::generate_simple_self_signed;
+        use std::io::Cursor;
+
+        let cert = generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_der = rustls::Certificate(cert.serialize_der().unwrap());
+        let key_der = rustls::PrivateKey(cert.serialize_private_key_der());
+
+        let server_config = rustls::ServerConfig::builder()
+            .with_safe_default_cipher_suites()
+            .with_safe_default_kx_groups()
+            .with_protocol_versions(tls_protocol_versions(Ok("1.2")))
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert_der.clone()], key_der)
+            .unwrap();
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(&cert_der).unwrap();
+        let client_config = rustls::ClientConfig::builder()
+            .with_safe_default_cipher_suites()
+            .with_safe_default_kx_groups()
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let server_name: rustls::ServerName = "localhost".try_into().unwrap();
+        let mut server = rustls::ServerConnection::new(Arc::new(server_config)).unwrap();
+        let mut client = rustls::ClientConnection::new(Arc::new(client_config), server_name).unwrap();
+
+        // Pump the handshake through in-memory buffers until both sides
+        // report it's complete, or give up after a generous cap.
+        for _ in 0..10 {
+            if !client.is_handshaking() && !server.is_handshaking() {
+                break;
+            }
+
+            let mut to_server = Vec::new();
+            client.write_tls(&mut to_server).unwrap();
+            let mut cursor = Cursor::new(to_server);
+            while (cursor.position() as usize) < cursor.get_ref().len() {
+                server.read_tls(&mut cursor).unwrap();
+            }
+            let _ = server.process_new_packets().unwrap();
+
+            let mut to_client = Vec::new();
+            server.write_tls(&mut to_client).unwrap();
+            let mut cursor = Cursor::new(to_client);
+            while (cursor.position() as usize) < cursor.get_ref().len() {
+                client.read_tls(&mut cursor).unwrap();
+            }
+            let _ = client.process_new_packets().unwrap();
+        }
+
+        assert!(!client.is_handshaking());
+        assert!(!server.is_handshaking());
+        assert_eq!(
+            client.protocol_version(),
+            Some(rustls::ProtocolVersion::TLSv1_3)
+        );
+    }
+}
@@ -10,6 +10,40 @@ use tokio::io::{stdin, stdout};
 use tracing::{info, error};
 use uuid::Uuid;
 
+/// Parse a `{channel, target, events}` notification spec into a subscription
+/// scoped to either a task or a workflow.
+fn parse_notification(
+    spec: &Value,
+    task_id: Option<Uuid>,
+    workflow_id: Option<Uuid>,
+) -> Result<crate::notifier::NotificationSubscription> {
+    use crate::notifier::{NotificationChannel, NotificationEvent, NotificationSubscription};
+
+    let channel = spec["channel"].as_str().context("Missing notification channel")?;
+    let channel = NotificationChannel::from_str(channel)?;
+    let target = spec["target"]
+        .as_str()
+        .context("Missing notification target")?
+        .to_string();
+
+    let events = spec["events"]
+        .as_array()
+        .context("Missing notification events")?
+        .iter()
+        .filter_map(|v| v.as_str())
+        .map(NotificationEvent::from_str)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(NotificationSubscription {
+        id: Uuid::new_v4(),
+        task_id,
+        workflow_id,
+        channel,
+        target,
+        events,
+    })
+}
+
 /// MCP server implementation for orchestr8-async
 pub struct Orchestr8McpServer {
     system: Arc<AsyncSystem>,
@@ -87,6 +121,56 @@ impl Orchestr8McpServer {
                     "timeout_seconds": {
                         "type": "integer",
                         "description": "Optional timeout in seconds"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Maximum automatic retries for a failed task (default: 3)"
+                    },
+                    "backoff": {
+                        "type": "object",
+                        "description": "Retry backoff policy",
+                        "properties": {
+                            "strategy": {
+                                "type": "string",
+                                "enum": ["fixed", "exponential"],
+                                "description": "Backoff strategy (default: exponential)"
+                            },
+                            "base_seconds": {
+                                "type": "integer",
+                                "description": "Base delay in seconds"
+                            },
+                            "max_seconds": {
+                                "type": "integer",
+                                "description": "Maximum delay in seconds (exponential only)"
+                            }
+                        }
+                    },
+                    "notifications": {
+                        "type": "array",
+                        "description": "Event subscriptions delivered over multiple channels",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "channel": {
+                                    "type": "string",
+                                    "enum": ["webhook", "slack", "email"],
+                                    "description": "Delivery backend"
+                                },
+                                "target": {
+                                    "type": "string",
+                                    "description": "Channel target (URL for webhook/slack, address for email)"
+                                },
+                                "events": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "string",
+                                        "enum": ["task_completed", "task_failed", "workflow_completed", "phase_failed"]
+                                    },
+                                    "description": "Events to deliver; only task_completed and task_failed are emitted, so workflow_completed and phase_failed subscriptions are stored but never fire"
+                                }
+                            },
+                            "required": ["channel", "target", "events"]
+                        }
                     }
                 },
                 "required": ["name", "agent_name", "agent_instructions"]
@@ -121,7 +205,36 @@ impl Orchestr8McpServer {
                         task.timeout_seconds = Some(timeout as i32);
                     }
 
+                    if let Some(max_retries) = args["max_retries"].as_i64() {
+                        task.max_retries = max_retries as i32;
+                    }
+
+                    if let Some(backoff) = args["backoff"].as_object() {
+                        if let Some(strategy) = backoff.get("strategy").and_then(|v| v.as_str()) {
+                            if let Some(strategy) = crate::db::BackoffStrategy::from_str(strategy) {
+                                task.backoff_strategy = strategy;
+                            }
+                        }
+                        if let Some(base) = backoff.get("base_seconds").and_then(|v| v.as_i64()) {
+                            task.backoff_base_seconds = base;
+                        }
+                        if let Some(factor) = backoff.get("factor").and_then(|v| v.as_i64()) {
+                            task.backoff_factor = factor;
+                        }
+                        if let Some(max) = backoff.get("max_seconds").and_then(|v| v.as_i64()) {
+                            task.backoff_max_seconds = max;
+                        }
+                    }
+
                     system.db.insert_task(&task)?;
+
+                    if let Some(notifications) = args["notifications"].as_array() {
+                        for spec in notifications {
+                            let sub = parse_notification(spec, Some(task.id), None)?;
+                            system.notifier.register(&sub)?;
+                        }
+                    }
+
                     system.queue.submit_task(task.id)?;
 
                     Ok(vec![json!({
@@ -168,6 +281,10 @@ impl Orchestr8McpServer {
                         task.created_at
                     );
 
+                    if task.status == crate::db::TaskStatus::Running {
+                        output.push_str(&format!("Progress: {:.0}%\n", task.progress * 100.0));
+                    }
+
                     if let Some(started) = task.started_at {
                         output.push_str(&format!("Started: {}\n", started));
                     }
@@ -176,6 +293,19 @@ impl Orchestr8McpServer {
                         output.push_str(&format!("Completed: {}\n", completed));
                     }
 
+                    if task.retry_count > 0 || task.max_retries > 0 {
+                        output.push_str(&format!(
+                            "Retries: {}/{}\n",
+                            task.retry_count, task.max_retries
+                        ));
+                    }
+
+                    if let Some(next_retry_at) = task.next_retry_at {
+                        if task.status == crate::db::TaskStatus::Pending {
+                            output.push_str(&format!("Next retry at: {}\n", next_retry_at));
+                        }
+                    }
+
                     if let Some(result) = &task.result {
                         output.push_str(&format!("\nResult:\n{}\n", result));
                     }
@@ -201,6 +331,78 @@ impl Orchestr8McpServer {
 
         let system = Arc::clone(&self.system);
 
+        // task_logs - Incrementally tail task logs using a sequence cursor
+        router.add_tool(
+            "task_logs",
+            "Fetch task log lines newer than a cursor. Returns only new lines plus the next cursor, for cheap tailing of long-running tasks.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "task_id": {"type": "string", "description": "UUID of the task"},
+                    "after_seq": {
+                        "type": "integer",
+                        "description": "Return only log lines with a sequence greater than this (default: 0)"
+                    }
+                },
+                "required": ["task_id"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let task_id_str = args["task_id"].as_str().context("Missing task_id")?;
+                    let task_id = Uuid::parse_str(task_id_str).context("Invalid task ID")?;
+                    let after_seq = args["after_seq"].as_i64().unwrap_or(0);
+
+                    let logs = system.db.get_task_logs_after(task_id, after_seq)?;
+                    let next_cursor = logs.last().map(|(seq, ..)| *seq).unwrap_or(after_seq);
+
+                    let mut output = String::new();
+                    for (seq, timestamp, level, message) in &logs {
+                        output.push_str(&format!("[{}] [{}] {}: {}\n", seq, timestamp, level, message));
+                    }
+                    output.push_str(&format!("\ncursor: {}\n", next_cursor));
+
+                    Ok(vec![json!({"type": "text", "text": output})])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // task_progress - Update the progress of a running task
+        router.add_tool(
+            "task_progress",
+            "Update the progress (0.0–1.0) of a running task so callers can track completion",
+            json!({
+                "type": "object",
+                "properties": {
+                    "task_id": {"type": "string", "description": "UUID of the task"},
+                    "progress": {
+                        "type": "number",
+                        "description": "Progress fraction between 0.0 and 1.0"
+                    }
+                },
+                "required": ["task_id", "progress"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let task_id_str = args["task_id"].as_str().context("Missing task_id")?;
+                    let task_id = Uuid::parse_str(task_id_str).context("Invalid task ID")?;
+                    let progress = args["progress"].as_f64().context("Missing progress")?;
+
+                    system.db.update_task_progress(task_id, progress)?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Task {} progress updated to {:.0}%", task_id, progress.clamp(0.0, 1.0) * 100.0)
+                    })])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
         // task_cancel - Cancel a running task
         router.add_tool(
             "task_cancel",
@@ -230,6 +432,132 @@ impl Orchestr8McpServer {
                 })
             },
         );
+
+        let system = Arc::clone(&self.system);
+
+        // task_schedule - Register a recurring or interval-based task
+        router.add_tool(
+            "task_schedule",
+            "Schedule a task to run recurrently via cron or a fixed interval. Returns a schedule ID.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Task name"},
+                    "agent_name": {"type": "string", "description": "Name of the agent to execute"},
+                    "agent_instructions": {"type": "string", "description": "Instructions for the agent"},
+                    "priority": {
+                        "type": "string",
+                        "enum": ["low", "normal", "high", "critical"],
+                        "description": "Task priority (default: normal)"
+                    },
+                    "webhook_url": {"type": "string", "description": "Optional webhook URL to receive results"},
+                    "timeout_seconds": {"type": "integer", "description": "Optional timeout in seconds"},
+                    "cron": {"type": "string", "description": "Cron expression defining the cadence"},
+                    "interval_seconds": {"type": "integer", "description": "Fallback fixed interval in seconds"},
+                    "end_at": {"type": "string", "description": "Optional RFC3339 timestamp after which the schedule stops"}
+                },
+                "required": ["name", "agent_name", "agent_instructions"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let name = args["name"].as_str().context("Missing name")?;
+                    let agent_name = args["agent_name"].as_str().context("Missing agent_name")?;
+                    let agent_instructions = args["agent_instructions"].as_str().context("Missing agent_instructions")?;
+
+                    let priority = args["priority"].as_str().map(|p| match p {
+                        "low" => TaskPriority::Low,
+                        "high" => TaskPriority::High,
+                        "critical" => TaskPriority::Critical,
+                        _ => TaskPriority::Normal,
+                    });
+
+                    let template = crate::TaskTemplate {
+                        name: name.to_string(),
+                        agent_name: agent_name.to_string(),
+                        agent_instructions: agent_instructions.to_string(),
+                        priority,
+                        webhook_url: args["webhook_url"].as_str().map(|s| s.to_string()),
+                        timeout_seconds: args["timeout_seconds"].as_i64().map(|t| t as i32),
+                        metadata: None,
+                    };
+
+                    let cron = args["cron"].as_str().map(|s| s.to_string());
+                    let interval_seconds = args["interval_seconds"].as_i64();
+                    let end_at = args["end_at"]
+                        .as_str()
+                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&chrono::Utc));
+
+                    let schedule_id = system
+                        .scheduler
+                        .create_schedule(template, cron, interval_seconds, end_at)?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Schedule created: {}\nSchedule ID: {}\n\nUse schedule_list to view and schedule_cancel to stop.", name, schedule_id)
+                    })])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // schedule_list - List registered schedules
+        router.add_tool(
+            "schedule_list",
+            "List all registered task schedules and their next fire times",
+            json!({"type": "object", "properties": {}}),
+            move |_args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let schedules = system.scheduler.list()?;
+
+                    let mut output = format!("Found {} schedules:\n\n", schedules.len());
+                    for s in &schedules {
+                        let cadence = s
+                            .cron
+                            .clone()
+                            .unwrap_or_else(|| format!("every {}s", s.interval_seconds.unwrap_or(0)));
+                        output.push_str(&format!(
+                            "ID: {}\nName: {}\nCadence: {}\nNext fire: {}\nActive: {}\n\n",
+                            s.id, s.name, cadence, s.next_fire_at, s.active
+                        ));
+                    }
+
+                    Ok(vec![json!({"type": "text", "text": output})])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // schedule_cancel - Cancel a schedule
+        router.add_tool(
+            "schedule_cancel",
+            "Cancel a registered task schedule",
+            json!({
+                "type": "object",
+                "properties": {
+                    "schedule_id": {"type": "string", "description": "UUID of the schedule to cancel"}
+                },
+                "required": ["schedule_id"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let schedule_id_str = args["schedule_id"].as_str().context("Missing schedule_id")?;
+                    let schedule_id = Uuid::parse_str(schedule_id_str).context("Invalid schedule ID")?;
+
+                    system.scheduler.cancel(schedule_id)?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Schedule {} cancelled", schedule_id)
+                    })])
+                })
+            },
+        );
     }
 
     fn register_workflow_tools(&self, router: &mut Router) {
@@ -249,6 +577,33 @@ impl Orchestr8McpServer {
                     "description": {
                         "type": "string",
                         "description": "Optional workflow description"
+                    },
+                    "notifications": {
+                        "type": "array",
+                        "description": "Workflow-scoped event subscriptions delivered over multiple channels",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "channel": {
+                                    "type": "string",
+                                    "enum": ["webhook", "slack", "email"],
+                                    "description": "Delivery backend"
+                                },
+                                "target": {
+                                    "type": "string",
+                                    "description": "Channel target (URL for webhook/slack, address for email)"
+                                },
+                                "events": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "string",
+                                        "enum": ["task_completed", "task_failed", "workflow_completed", "phase_failed"]
+                                    },
+                                    "description": "Events to deliver; only task_completed and task_failed are emitted, so workflow_completed and phase_failed subscriptions are stored but never fire"
+                                }
+                            },
+                            "required": ["channel", "target", "events"]
+                        }
                     }
                 },
                 "required": ["name"]
@@ -263,6 +618,13 @@ impl Orchestr8McpServer {
                         .executor
                         .create_workflow(name.to_string(), description)?;
 
+                    if let Some(notifications) = args["notifications"].as_array() {
+                        for spec in notifications {
+                            let sub = parse_notification(spec, None, Some(workflow_id))?;
+                            system.notifier.register(&sub)?;
+                        }
+                    }
+
                     Ok(vec![json!({
                         "type": "text",
                         "text": format!("Workflow created: {}\nWorkflow ID: {}\n\nUse workflow_add_phase to add phases, then workflow_add_task to add tasks.", name, workflow_id)
@@ -437,6 +799,38 @@ impl Orchestr8McpServer {
 
         let system = Arc::clone(&self.system);
 
+        // workflow_resume - Resume an interrupted workflow without re-running completed work
+        router.add_tool(
+            "workflow_resume",
+            "Resume an interrupted workflow: re-dispatch only pending/running/retryable-failed tasks whose dependencies are satisfied, leaving completed work untouched",
+            json!({
+                "type": "object",
+                "properties": {
+                    "workflow_id": {
+                        "type": "string",
+                        "description": "UUID of the workflow to resume"
+                    }
+                },
+                "required": ["workflow_id"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let workflow_id_str = args["workflow_id"].as_str().context("Missing workflow_id")?;
+                    let workflow_id = Uuid::parse_str(workflow_id_str).context("Invalid workflow ID")?;
+
+                    let resumed = system.executor.resume_workflow(workflow_id)?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Workflow {} resumed: {} task(s) re-dispatched", workflow_id, resumed)
+                    })])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
         // workflow_status - Get workflow status
         router.add_tool(
             "workflow_status",
@@ -479,6 +873,26 @@ impl Orchestr8McpServer {
                         output.push_str(&format!("Running: {} tasks\n", status.running_tasks));
                     }
 
+                    if status.blocked_tasks > 0 {
+                        output.push_str(&format!("Blocked: {} tasks\n", status.blocked_tasks));
+                    }
+
+                    if !status.failures.is_empty() {
+                        output.push_str("\nFailures:\n");
+                        for failure in &status.failures {
+                            let class = failure
+                                .error_class
+                                .map(|c| c.as_str())
+                                .unwrap_or("unclassified");
+                            output.push_str(&format!(
+                                "  - {} [{}]: {}\n",
+                                failure.name,
+                                class,
+                                failure.error.as_deref().unwrap_or("")
+                            ));
+                        }
+                    }
+
                     output.push_str("\nPhases:\n");
                     for phase in &status.phases {
                         output.push_str(&format!(
@@ -498,6 +912,152 @@ impl Orchestr8McpServer {
         );
     }
 
+        let system = Arc::clone(&self.system);
+
+        // workflow_submit - Build an entire workflow graph atomically
+        router.add_tool(
+            "workflow_submit",
+            "Create a complete workflow graph (metadata, phases with depends_on, tasks with inter-task dependencies) in one validated call. Rejects duplicate phase IDs, unknown dependency references, and cycles before committing.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Workflow name"},
+                    "description": {"type": "string", "description": "Optional workflow description"},
+                    "phases": {
+                        "type": "array",
+                        "description": "Phases with optional depends_on phase IDs",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "phase_id": {"type": "string"},
+                                "name": {"type": "string"},
+                                "depends_on": {"type": "array", "items": {"type": "string"}}
+                            },
+                            "required": ["phase_id", "name"]
+                        }
+                    },
+                    "tasks": {
+                        "type": "array",
+                        "description": "Tasks referencing a phase_id, with dependencies given by task name",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string"},
+                                "phase_id": {"type": "string"},
+                                "agent_name": {"type": "string"},
+                                "agent_instructions": {"type": "string"},
+                                "dependencies": {"type": "array", "items": {"type": "string"}},
+                                "priority": {"type": "string", "enum": ["low", "normal", "high", "critical"]}
+                            },
+                            "required": ["name", "phase_id", "agent_name", "agent_instructions"]
+                        }
+                    }
+                },
+                "required": ["name"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let submission: crate::queue::WorkflowSubmission =
+                        serde_json::from_value(args.clone()).context("Invalid workflow submission")?;
+
+                    let (workflow_id, name_to_id) = system.executor.submit_workflow(&submission)?;
+
+                    let mapping: serde_json::Map<String, Value> = name_to_id
+                        .into_iter()
+                        .map(|(name, id)| (name, Value::String(id.to_string())))
+                        .collect();
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!(
+                            "Workflow submitted: {}\nWorkflow ID: {}\nTask name to ID map:\n{}\n\nUse workflow_start to begin execution.",
+                            submission.name,
+                            workflow_id,
+                            serde_json::to_string_pretty(&mapping)?
+                        )
+                    })])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // workflow_template_save - Store a reusable workflow blueprint
+        router.add_tool(
+            "workflow_template_save",
+            "Store a reusable multi-phase workflow blueprint (phases, dependencies, task stubs) that workflow_instantiate can materialize later",
+            json!({
+                "type": "object",
+                "properties": {
+                    "template": {
+                        "type": "object",
+                        "description": "Workflow template: {name, description?, phases:[{phase_id,name,depends_on}], tasks:[{phase_id,name,agent_name,agent_instructions}]}"
+                    }
+                },
+                "required": ["template"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let template_val = args.get("template").context("Missing template")?;
+                    let template: crate::queue::WorkflowTemplate =
+                        serde_json::from_value(template_val.clone()).context("Invalid template")?;
+
+                    system.db.upsert_workflow_template(
+                        &template.name,
+                        template.description.as_deref(),
+                        &serde_json::to_string(&template)?,
+                    )?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Template '{}' saved with {} phase(s) and {} task(s)", template.name, template.phases.len(), template.tasks.len())
+                    })])
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // workflow_instantiate - Materialize a workflow from a template
+        router.add_tool(
+            "workflow_instantiate",
+            "Materialize a concrete workflow from a stored template in one call, applying {{key}} parameter substitutions",
+            json!({
+                "type": "object",
+                "properties": {
+                    "template_name": {"type": "string", "description": "Name of the stored template"},
+                    "parameters": {"type": "object", "description": "Substitution parameters for {{key}} placeholders"}
+                },
+                "required": ["template_name"]
+            }),
+            move |args: Value| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let template_name = args["template_name"].as_str().context("Missing template_name")?;
+                    let params = args["parameters"]
+                        .as_object()
+                        .cloned()
+                        .unwrap_or_default();
+
+                    let (blueprint, _desc) = system
+                        .db
+                        .get_workflow_template(template_name)?
+                        .context("Template not found")?;
+                    let template: crate::queue::WorkflowTemplate = serde_json::from_str(&blueprint)?;
+
+                    let workflow_id = system.executor.instantiate_template(&template, &params)?;
+
+                    Ok(vec![json!({
+                        "type": "text",
+                        "text": format!("Workflow instantiated from template '{}'\nWorkflow ID: {}\n\nUse workflow_start to begin execution.", template_name, workflow_id)
+                    })])
+                })
+            },
+        );
+    }
+
     fn register_query_tools(&self, router: &mut Router) {
         let system = Arc::clone(&self.system);
 
@@ -553,9 +1113,78 @@ impl Orchestr8McpServer {
         );
     }
 
-    fn register_resources(&self, _router: &mut Router) {
-        // Resources can be added here for read-only access to data
-        // For example: task definitions, workflow templates, etc.
+    fn register_resources(&self, router: &mut Router) {
+        let system = Arc::clone(&self.system);
+
+        // workflow://templates - Catalog of stored workflow blueprints
+        router.add_resource(
+            "workflow://templates",
+            "Workflow Templates",
+            "Catalog of reusable workflow blueprints available to workflow_instantiate",
+            "application/json",
+            move |_uri: String| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let templates = system.db.list_workflow_templates()?;
+                    let catalog: Vec<Value> = templates
+                        .into_iter()
+                        .map(|(name, description)| {
+                            json!({
+                                "uri": format!("workflow://templates/{}", name),
+                                "name": name,
+                                "description": description,
+                            })
+                        })
+                        .collect();
+                    Ok(serde_json::to_string_pretty(&catalog)?)
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // task://{uuid} - Live rendering of a task's current state
+        router.add_resource(
+            "task://{uuid}",
+            "Task State",
+            "Current state of a task rendered as JSON",
+            "application/json",
+            move |uri: String| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let id = uri
+                        .strip_prefix("task://")
+                        .and_then(|s| Uuid::parse_str(s).ok())
+                        .context("Invalid task resource URI")?;
+                    let task = system
+                        .db
+                        .get_task(id)?
+                        .context("Task not found")?;
+                    Ok(serde_json::to_string_pretty(&task)?)
+                })
+            },
+        );
+
+        let system = Arc::clone(&self.system);
+
+        // workflow://{uuid} - Live rendering of a workflow's current state
+        router.add_resource(
+            "workflow://{uuid}",
+            "Workflow State",
+            "Current state of a workflow, including phases and task progress, rendered as JSON",
+            "application/json",
+            move |uri: String| {
+                let system = Arc::clone(&system);
+                Box::pin(async move {
+                    let id = uri
+                        .strip_prefix("workflow://")
+                        .and_then(|s| Uuid::parse_str(s).ok())
+                        .context("Invalid workflow resource URI")?;
+                    let status = system.executor.get_workflow_status(id)?;
+                    Ok(serde_json::to_string_pretty(&status)?)
+                })
+            },
+        );
     }
 }
 
@@ -580,12 +1209,35 @@ mod tests {
                     2,
                 )),
             )),
+            scheduler: Arc::new(crate::Scheduler::new(
+                Arc::new(crate::Database::in_memory().unwrap()),
+                Arc::new(crate::TaskQueue::new(
+                    Arc::new(crate::Database::in_memory().unwrap()),
+                    2,
+                )),
+            )),
             webhook_manager: Arc::new(
                 crate::WebhookManager::with_defaults(Arc::new(
                     crate::Database::in_memory().unwrap(),
                 ))
                 .unwrap(),
             ),
+            notifier: Arc::new(
+                crate::Notifier::with_defaults(Arc::new(crate::Database::in_memory().unwrap()))
+                    .unwrap(),
+            ),
+            runner: Arc::new(crate::RunnerCoordinator::with_defaults(Arc::new(
+                crate::Database::in_memory().unwrap(),
+            ))),
+            artifacts: Arc::new(crate::ArtifactStore::with_defaults(Arc::new(
+                crate::Database::in_memory().unwrap(),
+            ))),
+            agents: Arc::new(crate::AgentRegistry::with_defaults(Arc::new(
+                crate::Database::in_memory().unwrap(),
+            ))),
+            usage: Arc::new(crate::UsageMeter::with_defaults(Arc::new(
+                crate::Database::in_memory().unwrap(),
+            ))),
         });
 
         let server = Orchestr8McpServer::new(system);
@@ -0,0 +1,658 @@
+use crate::db::{AsyncTask, Database};
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Lifecycle events that a subscription can listen for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    TaskCompleted,
+    TaskFailed,
+    WorkflowCompleted,
+    PhaseFailed,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::TaskCompleted => "task_completed",
+            NotificationEvent::TaskFailed => "task_failed",
+            NotificationEvent::WorkflowCompleted => "workflow_completed",
+            NotificationEvent::PhaseFailed => "phase_failed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "task_completed" => Ok(NotificationEvent::TaskCompleted),
+            "task_failed" => Ok(NotificationEvent::TaskFailed),
+            "workflow_completed" => Ok(NotificationEvent::WorkflowCompleted),
+            "phase_failed" => Ok(NotificationEvent::PhaseFailed),
+            other => Err(anyhow!("Unknown notification event: {}", other)),
+        }
+    }
+}
+
+/// Delivery backend for a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    /// Generic HTTP webhook receiving the raw JSON payload.
+    Webhook,
+    /// Slack-style incoming webhook receiving a `{ "text": ... }` body.
+    Slack,
+    /// Email delivered via SMTP; `target` is the recipient address.
+    Email,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Webhook => "webhook",
+            NotificationChannel::Slack => "slack",
+            NotificationChannel::Email => "email",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "webhook" => Ok(NotificationChannel::Webhook),
+            "slack" => Ok(NotificationChannel::Slack),
+            "email" => Ok(NotificationChannel::Email),
+            other => Err(anyhow!("Unknown notification channel: {}", other)),
+        }
+    }
+}
+
+/// A per-task or per-workflow subscription routing a set of events to one
+/// channel target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSubscription {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub workflow_id: Option<Uuid>,
+    pub channel: NotificationChannel,
+    pub target: String,
+    pub events: Vec<NotificationEvent>,
+}
+
+impl NotificationSubscription {
+    /// Build a task-scoped subscription.
+    pub fn for_task(
+        task_id: Uuid,
+        channel: NotificationChannel,
+        target: String,
+        events: Vec<NotificationEvent>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            task_id: Some(task_id),
+            workflow_id: None,
+            channel,
+            target,
+            events,
+        }
+    }
+
+    /// Build a workflow-scoped subscription.
+    pub fn for_workflow(
+        workflow_id: Uuid,
+        channel: NotificationChannel,
+        target: String,
+        events: Vec<NotificationEvent>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            task_id: None,
+            workflow_id: Some(workflow_id),
+            channel,
+            target,
+            events,
+        }
+    }
+
+    fn matches(&self, event: NotificationEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// Notification delivery configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotifierConfig {
+    pub max_retries: u32,
+    pub retry_delay_seconds: u64,
+    pub timeout_seconds: u64,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_from: String,
+}
+
+impl Default for NotifierConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_delay_seconds: 5,
+            timeout_seconds: 30,
+            smtp_host: "localhost".to_string(),
+            smtp_port: 25,
+            smtp_from: "orchestr8@localhost".to_string(),
+        }
+    }
+}
+
+/// Routed, multi-channel event notifier. Generalizes the single `webhook_url`
+/// delivery path into a subscription-driven fan-out backed by a durable retry
+/// queue so a down endpoint does not lose notifications.
+pub struct Notifier {
+    db: Arc<Database>,
+    client: Client,
+    config: NotifierConfig,
+}
+
+impl Notifier {
+    pub fn new(db: Arc<Database>, config: NotifierConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self { db, client, config })
+    }
+
+    pub fn with_defaults(db: Arc<Database>) -> Result<Self> {
+        Self::new(db, NotifierConfig::default())
+    }
+
+    /// Persist a subscription.
+    pub fn register(&self, sub: &NotificationSubscription) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        let events: Vec<&str> = sub.events.iter().map(|e| e.as_str()).collect();
+
+        conn.execute(
+            "INSERT INTO notification_subscriptions
+                (id, task_id, workflow_id, channel, target, events, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            duckdb::params![
+                sub.id.to_string(),
+                sub.task_id.map(|id| id.to_string()),
+                sub.workflow_id.map(|id| id.to_string()),
+                sub.channel.as_str(),
+                sub.target,
+                serde_json::to_string(&events)?,
+                Utc::now(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Load subscriptions scoped to a task.
+    pub fn subscriptions_for_task(&self, task_id: Uuid) -> Result<Vec<NotificationSubscription>> {
+        self.load_subscriptions("task_id", task_id)
+    }
+
+    /// Load subscriptions scoped to a workflow.
+    pub fn subscriptions_for_workflow(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<NotificationSubscription>> {
+        self.load_subscriptions("workflow_id", workflow_id)
+    }
+
+    fn load_subscriptions(
+        &self,
+        column: &str,
+        id: Uuid,
+    ) -> Result<Vec<NotificationSubscription>> {
+        let conn = self.db.get_conn()?;
+        let sql = format!(
+            "SELECT id, task_id, workflow_id, channel, target, events
+             FROM notification_subscriptions WHERE {} = ?",
+            column
+        );
+        let mut stmt = conn.prepare(&sql)?;
+
+        let rows = stmt.query_map(duckdb::params![id.to_string()], |row| {
+            let id_str: String = row.get(0)?;
+            let task_id: Option<String> = row.get(1)?;
+            let workflow_id: Option<String> = row.get(2)?;
+            let channel: String = row.get(3)?;
+            let target: String = row.get(4)?;
+            let events: String = row.get(5)?;
+            Ok((id_str, task_id, workflow_id, channel, target, events))
+        })?;
+
+        let mut subs = Vec::new();
+        for row in rows {
+            let (id_str, task_id, workflow_id, channel, target, events) = row?;
+            let event_strs: Vec<String> = serde_json::from_str(&events).unwrap_or_default();
+            let events = event_strs
+                .iter()
+                .filter_map(|e| NotificationEvent::from_str(e).ok())
+                .collect();
+
+            subs.push(NotificationSubscription {
+                id: Uuid::parse_str(&id_str)?,
+                task_id: task_id.as_deref().and_then(|s| Uuid::parse_str(s).ok()),
+                workflow_id: workflow_id.as_deref().and_then(|s| Uuid::parse_str(s).ok()),
+                channel: NotificationChannel::from_str(&channel)?,
+                target,
+                events,
+            });
+        }
+
+        Ok(subs)
+    }
+
+    /// Fan a task event out to every matching subscription, enqueueing one
+    /// delivery per channel and attempting immediate delivery. Failures stay in
+    /// the retry queue for the worker to pick up.
+    pub async fn notify_task(&self, event: NotificationEvent, task: &AsyncTask) -> Result<()> {
+        let mut subs = self.subscriptions_for_task(task.id)?;
+        if let Some(workflow_id) = task.workflow_id {
+            subs.extend(self.subscriptions_for_workflow(workflow_id)?);
+        }
+
+        let payload = build_task_payload(event, task);
+
+        for sub in subs.iter().filter(|s| s.matches(event)) {
+            if self.already_enqueued(sub.id, event)? {
+                continue;
+            }
+            let delivery_id = self.enqueue(sub, event, &payload)?;
+            if let Err(e) = self.attempt(delivery_id, sub, &payload).await {
+                warn!("Notification {} deferred to retry queue: {}", delivery_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// True if a delivery for this subscription/event pair already exists, so
+    /// repeated polling never double-sends.
+    fn already_enqueued(&self, subscription_id: Uuid, event: NotificationEvent) -> Result<bool> {
+        let conn = self.db.get_conn()?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM notification_deliveries
+             WHERE subscription_id = ? AND event = ?",
+            duckdb::params![subscription_id.to_string(), event.as_str()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Insert a pending delivery row and return its id.
+    fn enqueue(
+        &self,
+        sub: &NotificationSubscription,
+        event: NotificationEvent,
+        payload: &serde_json::Value,
+    ) -> Result<i64> {
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "INSERT INTO notification_deliveries
+                (id, subscription_id, event, channel, target, payload, status, attempts, next_attempt_at, created_at)
+             VALUES (nextval('notification_deliveries_seq'), ?, ?, ?, ?, ?, 'pending', 0, ?, ?)",
+            duckdb::params![
+                sub.id.to_string(),
+                event.as_str(),
+                sub.channel.as_str(),
+                sub.target,
+                serde_json::to_string(payload)?,
+                Utc::now(),
+                Utc::now(),
+            ],
+        )?;
+
+        let id: i64 = conn.query_row(
+            "SELECT currval('notification_deliveries_seq')",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    /// Attempt a single delivery, updating its queue row with the outcome.
+    async fn attempt(
+        &self,
+        delivery_id: i64,
+        sub: &NotificationSubscription,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        match self.send(sub, payload).await {
+            Ok(()) => {
+                info!(
+                    "Notification {} delivered via {} to {}",
+                    delivery_id,
+                    sub.channel.as_str(),
+                    sub.target
+                );
+                let conn = self.db.get_conn()?;
+                conn.execute(
+                    "UPDATE notification_deliveries
+                     SET status = 'delivered', attempts = attempts + 1, delivered_at = ?
+                     WHERE id = ?",
+                    duckdb::params![Utc::now(), delivery_id],
+                )?;
+                Ok(())
+            }
+            Err(e) => {
+                let conn = self.db.get_conn()?;
+                let attempts: i32 = conn.query_row(
+                    "SELECT attempts FROM notification_deliveries WHERE id = ?",
+                    duckdb::params![delivery_id],
+                    |row| row.get(0),
+                )?;
+                let attempts = attempts + 1;
+                let exhausted = attempts as u32 >= self.config.max_retries;
+                let status = if exhausted { "failed" } else { "pending" };
+                let next_attempt_at = Utc::now()
+                    + ChronoDuration::seconds(
+                        self.config.retry_delay_seconds as i64 * attempts as i64,
+                    );
+
+                conn.execute(
+                    "UPDATE notification_deliveries
+                     SET status = ?, attempts = ?, last_error = ?, next_attempt_at = ?
+                     WHERE id = ?",
+                    duckdb::params![
+                        status,
+                        attempts,
+                        e.to_string(),
+                        next_attempt_at,
+                        delivery_id
+                    ],
+                )?;
+
+                Err(e)
+            }
+        }
+    }
+
+    /// Dispatch a payload to the appropriate channel backend.
+    async fn send(
+        &self,
+        sub: &NotificationSubscription,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        match sub.channel {
+            NotificationChannel::Webhook => self.send_http(&sub.target, payload).await,
+            NotificationChannel::Slack => {
+                let body = serde_json::json!({ "text": format_slack_text(payload) });
+                self.send_http(&sub.target, &body).await
+            }
+            NotificationChannel::Email => self.send_email(&sub.target, payload).await,
+        }
+    }
+
+    async fn send_http(&self, url: &str, payload: &serde_json::Value) -> Result<()> {
+        debug!("POST notification to {}", url);
+        let response = self
+            .client
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .context("Failed to send HTTP notification")?;
+
+        let status = response.status().as_u16();
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Notification endpoint returned {}: {}", status, body))
+        }
+    }
+
+    /// Deliver an email by speaking minimal SMTP to the configured relay. TLS
+    /// and authentication are intentionally out of scope and can be layered on
+    /// when a submission relay requires them.
+    async fn send_email(&self, to: &str, payload: &serde_json::Value) -> Result<()> {
+        let addr = format!("{}:{}", self.config.smtp_host, self.config.smtp_port);
+        let stream = TcpStream::connect(&addr)
+            .await
+            .with_context(|| format!("Failed to connect to SMTP relay {}", addr))?;
+        let (read_half, mut write_half) = stream.into_split();
+        let mut reader = BufReader::new(read_half);
+
+        read_smtp_reply(&mut reader, 220).await?;
+        write_smtp_line(&mut write_half, &format!("HELO {}", self.config.smtp_host)).await?;
+        read_smtp_reply(&mut reader, 250).await?;
+        write_smtp_line(&mut write_half, &format!("MAIL FROM:<{}>", self.config.smtp_from)).await?;
+        read_smtp_reply(&mut reader, 250).await?;
+        write_smtp_line(&mut write_half, &format!("RCPT TO:<{}>", to)).await?;
+        read_smtp_reply(&mut reader, 250).await?;
+        write_smtp_line(&mut write_half, "DATA").await?;
+        read_smtp_reply(&mut reader, 354).await?;
+
+        let (subject, body) = format_email(payload);
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\n\r\n{}\r\n.",
+            self.config.smtp_from, to, subject, body
+        );
+        write_smtp_line(&mut write_half, &message).await?;
+        read_smtp_reply(&mut reader, 250).await?;
+        write_smtp_line(&mut write_half, "QUIT").await?;
+
+        Ok(())
+    }
+
+    /// Start the background worker that drains the retry queue.
+    pub async fn start_worker(&self) -> Result<()> {
+        info!("Starting notification delivery worker");
+
+        let notifier = Arc::new(Self::new(Arc::clone(&self.db), self.config.clone())?);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+
+                if let Err(e) = notifier.poll_task_events().await {
+                    error!("Error polling task events for notifications: {}", e);
+                }
+                if let Err(e) = notifier.drain_pending().await {
+                    error!("Error draining notification queue: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Scan for terminal tasks that carry subscriptions and fan out their
+    /// completion/failure events. Idempotent: `notify_task` skips events that
+    /// have already been enqueued.
+    pub async fn poll_task_events(&self) -> Result<()> {
+        let task_ids = {
+            let conn = self.db.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT DISTINCT t.id
+                 FROM tasks t
+                 WHERE t.status IN ('completed', 'failed')
+                   AND (
+                       EXISTS (SELECT 1 FROM notification_subscriptions s WHERE s.task_id = t.id)
+                       OR (t.workflow_id IS NOT NULL AND EXISTS (
+                           SELECT 1 FROM notification_subscriptions s WHERE s.workflow_id = t.workflow_id))
+                   )
+                 ORDER BY t.completed_at ASC
+                 LIMIT 100",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let id: String = row.get(0)?;
+                Ok(id)
+            })?;
+            let mut ids = Vec::new();
+            for row in rows {
+                ids.push(row?);
+            }
+            ids
+        };
+
+        for id in task_ids {
+            let task_id = Uuid::parse_str(&id)?;
+            if let Some(task) = self.db.get_task(task_id)? {
+                let event = match task.status {
+                    crate::db::TaskStatus::Completed => NotificationEvent::TaskCompleted,
+                    crate::db::TaskStatus::Failed => NotificationEvent::TaskFailed,
+                    _ => continue,
+                };
+                if let Err(e) = self.notify_task(event, &task).await {
+                    warn!("Failed to fan out notification for task {}: {}", task_id, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-attempt every delivery that is due and not yet exhausted.
+    pub async fn drain_pending(&self) -> Result<()> {
+        let due = {
+            let conn = self.db.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT id, subscription_id, channel, target, payload
+                 FROM notification_deliveries
+                 WHERE status = 'pending' AND next_attempt_at <= ?
+                 ORDER BY next_attempt_at ASC
+                 LIMIT 100",
+            )?;
+            let rows = stmt.query_map(duckdb::params![Utc::now()], |row| {
+                let id: i64 = row.get(0)?;
+                let sub_id: String = row.get(1)?;
+                let channel: String = row.get(2)?;
+                let target: String = row.get(3)?;
+                let payload: String = row.get(4)?;
+                Ok((id, sub_id, channel, target, payload))
+            })?;
+            let mut out = Vec::new();
+            for row in rows {
+                out.push(row?);
+            }
+            out
+        };
+
+        for (id, sub_id, channel, target, payload) in due {
+            let sub = NotificationSubscription {
+                id: Uuid::parse_str(&sub_id)?,
+                task_id: None,
+                workflow_id: None,
+                channel: NotificationChannel::from_str(&channel)?,
+                target,
+                events: Vec::new(),
+            };
+            let payload: serde_json::Value = serde_json::from_str(&payload)?;
+            if let Err(e) = self.attempt(id, &sub, &payload).await {
+                warn!("Notification {} still failing: {}", id, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the canonical JSON payload describing a task event.
+fn build_task_payload(event: NotificationEvent, task: &AsyncTask) -> serde_json::Value {
+    serde_json::json!({
+        "event": event.as_str(),
+        "task_id": task.id,
+        "task_name": task.name,
+        "workflow_id": task.workflow_id,
+        "status": task.status.as_str(),
+        "result": task.result,
+        "error": task.error,
+        "completed_at": task.completed_at,
+    })
+}
+
+fn format_slack_text(payload: &serde_json::Value) -> String {
+    let event = payload["event"].as_str().unwrap_or("event");
+    let name = payload["task_name"].as_str().unwrap_or("task");
+    let status = payload["status"].as_str().unwrap_or("unknown");
+    format!("*{}*: `{}` is now _{}_", event, name, status)
+}
+
+fn format_email(payload: &serde_json::Value) -> (String, String) {
+    let event = payload["event"].as_str().unwrap_or("event");
+    let name = payload["task_name"].as_str().unwrap_or("task");
+    let subject = format!("[orchestr8] {} - {}", event, name);
+    let body = serde_json::to_string_pretty(payload).unwrap_or_default();
+    (subject, body)
+}
+
+async fn write_smtp_line<W: AsyncWriteExt + Unpin>(writer: &mut W, line: &str) -> Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\r\n").await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+async fn read_smtp_reply<R: AsyncBufReadExt + Unpin>(reader: &mut R, expected: u16) -> Result<()> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let code: u16 = line
+        .get(0..3)
+        .and_then(|c| c.parse().ok())
+        .ok_or_else(|| anyhow!("Malformed SMTP reply: {}", line.trim()))?;
+    if code == expected {
+        Ok(())
+    } else {
+        Err(anyhow!("SMTP relay returned {}: {}", code, line.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trip() {
+        for event in [
+            NotificationEvent::TaskCompleted,
+            NotificationEvent::TaskFailed,
+            NotificationEvent::WorkflowCompleted,
+            NotificationEvent::PhaseFailed,
+        ] {
+            assert_eq!(NotificationEvent::from_str(event.as_str()).unwrap(), event);
+        }
+    }
+
+    #[test]
+    fn test_subscription_matches_event() {
+        let sub = NotificationSubscription::for_task(
+            Uuid::new_v4(),
+            NotificationChannel::Slack,
+            "https://hooks.example/abc".to_string(),
+            vec![NotificationEvent::TaskFailed],
+        );
+        assert!(sub.matches(NotificationEvent::TaskFailed));
+        assert!(!sub.matches(NotificationEvent::TaskCompleted));
+    }
+
+    #[tokio::test]
+    async fn test_register_and_load_subscription() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let notifier = Notifier::with_defaults(db).unwrap();
+
+        let task_id = Uuid::new_v4();
+        let sub = NotificationSubscription::for_task(
+            task_id,
+            NotificationChannel::Webhook,
+            "https://example.com/hook".to_string(),
+            vec![NotificationEvent::TaskCompleted, NotificationEvent::TaskFailed],
+        );
+        notifier.register(&sub).unwrap();
+
+        let loaded = notifier.subscriptions_for_task(task_id).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].target, "https://example.com/hook");
+        assert_eq!(loaded[0].events.len(), 2);
+    }
+}
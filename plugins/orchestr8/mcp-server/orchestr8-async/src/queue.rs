@@ -1,10 +1,17 @@
-use crate::db::{AsyncTask, Database, TaskPriority, TaskStatus, Workflow, WorkflowPhase};
+use crate::db::{AsyncTask, Database, ErrorClass, TaskPriority, TaskStatus, Workflow, WorkflowPhase};
+use crate::executor::{AppContext, CancelReason, CancellationToken, RunnerRegistry};
 use anyhow::{Context, Result};
+use chrono::Utc;
 use crossbeam::channel::{bounded, Receiver, Sender};
-use std::sync::Arc;
+use crossbeam::deque::{Injector, Steal, Stealer, Worker};
+use std::collections::{HashMap, HashSet};
+use std::iter;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex as TokioMutex, Notify, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -15,16 +22,111 @@ pub struct TaskResult {
     pub success: bool,
     pub result: Option<String>,
     pub error: Option<String>,
+    /// Failure classification; defaults to retryable when unset.
+    pub error_class: Option<ErrorClass>,
+}
+
+/// Summary of a graceful shutdown: how much work drained cleanly versus how
+/// much was still queued when the drain deadline elapsed.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub struct ShutdownSummary {
+    /// Results still in the result channel after the loops stopped, flushed
+    /// through `process_task_result` by the shutdown itself. Results the
+    /// result processor handled earlier in the drain are not counted.
+    pub completed: usize,
+    /// Execute commands still queued when the deadline elapsed.
+    pub abandoned: usize,
+    /// Tasks still `Running` when the drain timed out and their worker was
+    /// force-aborted; reset to `Pending` so they're re-picked on restart
+    /// instead of being stranded.
+    pub reset_to_pending: usize,
+}
+
+/// Policy governing whether terminal task rows are kept or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetentionMode {
+    /// Keep every task row indefinitely (historical default).
+    #[default]
+    KeepAll,
+    /// Delete any task row once it reaches a terminal state.
+    RemoveAll,
+    /// Delete successful completions but keep failures for debugging.
+    RemoveDoneSuccessOnly,
+}
+
+impl RetentionMode {
+    /// Whether a task in the given terminal `status` should be pruned.
+    fn prunes(&self, status: TaskStatus) -> bool {
+        match self {
+            RetentionMode::KeepAll => false,
+            RetentionMode::RemoveAll => status.is_terminal(),
+            RetentionMode::RemoveDoneSuccessOnly => status == TaskStatus::Completed,
+        }
+    }
+}
+
+/// Retention configuration: an immediate-on-terminal [`RetentionMode`] plus an
+/// optional age-based sweep applied by the scheduler.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetentionConfig {
+    pub mode: RetentionMode,
+    /// When set, terminal tasks older than this are swept regardless of `mode`.
+    pub ttl: Option<Duration>,
+}
+
+/// A dedicated worker pool serving tasks from one agent, keyed by
+/// `agent_name`, or (for the `"*"` wildcard filter) every agent without its
+/// own dedicated pool. Each route gets its own work-stealing deque network,
+/// so e.g. two workers can serve `agent1` while one serves everything else,
+/// and a flood of slow `agent1` tasks can't starve the rest of the pool.
+#[derive(Debug, Clone)]
+pub struct WorkerRoute {
+    pub agent_filter: String,
+    pub worker_count: usize,
+}
+
+impl WorkerRoute {
+    pub fn new(agent_filter: impl Into<String>, worker_count: usize) -> Self {
+        Self {
+            agent_filter: agent_filter.into(),
+            worker_count,
+        }
+    }
+}
+
+/// One [`WorkerRoute`] paired with its own injector, so tasks pushed for that
+/// route never contend with another route's deques.
+struct RoutePool {
+    route: WorkerRoute,
+    injector: Arc<Injector<TaskCommand>>,
 }
 
 /// Background job queue manager
 pub struct TaskQueue {
     db: Arc<Database>,
-    tx: Sender<TaskCommand>,
-    rx: Arc<RwLock<Receiver<TaskCommand>>>,
-    worker_count: usize,
+    /// One pool per configured [`WorkerRoute`]. `schedule_pending_tasks` and
+    /// the direct task-command methods route to the pool whose
+    /// `agent_filter` matches a task's agent, falling back to the `"*"`
+    /// catch-all pool.
+    pools: Arc<Vec<RoutePool>>,
+    /// Woken whenever work is injected so parked workers resume promptly.
+    wake: Arc<Notify>,
     result_tx: Sender<TaskResult>,
     result_rx: Arc<RwLock<Receiver<TaskResult>>>,
+    /// Set once a graceful shutdown begins; polled by the scheduler, result
+    /// processor, and worker loops so they stop accepting new work.
+    shutdown: Arc<AtomicBool>,
+    /// Handles for every spawned loop, joined during a graceful shutdown.
+    handles: Arc<TokioMutex<Vec<JoinHandle<()>>>>,
+    /// Runners keyed by task kind; workers dispatch each task to its runner.
+    runners: Arc<RunnerRegistry>,
+    /// Dependencies passed to every runner invocation.
+    ctx: AppContext,
+    /// Policy for pruning terminal task rows.
+    retention: RetentionConfig,
+    /// Cancellation tokens for tasks currently being executed, so pause/abort
+    /// commands can reach an in-flight runner.
+    cancels: Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
 }
 
 /// Commands for task queue management
@@ -33,78 +135,254 @@ enum TaskCommand {
     Execute(Uuid),
     Cancel(Uuid),
     Retry(Uuid),
-    Shutdown,
+    /// Gracefully pause: signal a running task to checkpoint, or park a
+    /// not-yet-started task.
+    Pause(Uuid),
+    /// Return a paused task to the pending pool.
+    Resume(Uuid),
+    /// Force-stop immediately and mark the task cancelled.
+    Abort(Uuid),
+}
+
+/// Per-empty-steal increment for a worker's backoff sleep.
+const BACKOFF_STEP: Duration = Duration::from_millis(2);
+/// Upper bound on the backoff sleep between steal attempts.
+const BACKOFF_MAX: Duration = Duration::from_millis(250);
+
+/// Find the next command for `worker_id`: pop from its own deque first, then
+/// steal a batch from the global injector or a sibling. Returns `None` when no
+/// work is available anywhere. Siblings are visited starting from the worker's
+/// own index so workers don't all contend on the same victim.
+fn find_task(
+    local: &Worker<TaskCommand>,
+    injector: &Injector<TaskCommand>,
+    stealers: &[Stealer<TaskCommand>],
+    worker_id: usize,
+) -> Option<TaskCommand> {
+    local.pop().or_else(|| {
+        iter::repeat_with(|| {
+            injector.steal_batch_and_pop(local).or_else(|| {
+                (0..stealers.len())
+                    .map(|i| stealers[(worker_id + i) % stealers.len()].steal())
+                    .collect()
+            })
+        })
+        .find(|s| !s.is_retry())
+        .and_then(Steal::success)
+    })
 }
 
 impl TaskQueue {
-    /// Create a new task queue
+    /// Create a new task queue with no registered runners. Tasks whose kind has
+    /// no runner fail with a fatal "no runner" error; use [`TaskQueue::with_runners`]
+    /// to register handlers.
     pub fn new(db: Arc<Database>, worker_count: usize) -> Self {
-        let (tx, rx) = bounded::<TaskCommand>(1000);
+        let ctx = AppContext::new(db.clone());
+        Self::with_runners(db, worker_count, RunnerRegistry::new(), ctx)
+    }
+
+    /// Create a task queue backed by an explicit runner registry and context,
+    /// with a single `"*"` catch-all pool of `worker_count` workers serving
+    /// every agent. Use [`TaskQueue::with_routes`] to dedicate worker pools
+    /// to specific agents instead.
+    pub fn with_runners(
+        db: Arc<Database>,
+        worker_count: usize,
+        runners: RunnerRegistry,
+        ctx: AppContext,
+    ) -> Self {
+        Self::with_routes(db, vec![WorkerRoute::new("*", worker_count)], runners, ctx)
+    }
+
+    /// Create a task queue with a dedicated worker pool per [`WorkerRoute`],
+    /// e.g. two workers serving `agent1` and a `"*"` catch-all pool serving
+    /// everything else. Routes are matched by exact `agent_name`, falling
+    /// back to the `"*"` pool when a task's agent has no dedicated route.
+    pub fn with_routes(
+        db: Arc<Database>,
+        routes: Vec<WorkerRoute>,
+        runners: RunnerRegistry,
+        ctx: AppContext,
+    ) -> Self {
         let (result_tx, result_rx) = bounded::<TaskResult>(1000);
+        let pools = routes
+            .into_iter()
+            .map(|route| RoutePool {
+                route,
+                injector: Arc::new(Injector::new()),
+            })
+            .collect();
 
         Self {
             db,
-            tx,
-            rx: Arc::new(RwLock::new(rx)),
-            worker_count,
+            pools: Arc::new(pools),
+            wake: Arc::new(Notify::new()),
             result_tx,
             result_rx: Arc::new(RwLock::new(result_rx)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            handles: Arc::new(TokioMutex::new(Vec::new())),
+            runners: Arc::new(runners),
+            ctx,
+            retention: RetentionConfig::default(),
+            cancels: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Index of the pool whose `agent_filter` matches `agent_name` exactly,
+    /// falling back to the `"*"` catch-all pool, or pool 0 if none is
+    /// configured as a wildcard.
+    fn pool_index_for(&self, agent_name: &str) -> usize {
+        self.pools
+            .iter()
+            .position(|p| p.route.agent_filter == agent_name)
+            .or_else(|| self.pools.iter().position(|p| p.route.agent_filter == "*"))
+            .unwrap_or(0)
+    }
+
+    /// Resolve which pool a task belongs in by looking up its agent. Falls
+    /// back to the `"*"` pool if the task can't be found.
+    fn pool_index_for_task(&self, task_id: Uuid) -> Result<usize> {
+        match self.db.get_task(task_id)? {
+            Some(task) => Ok(self.pool_index_for(&task.agent_name)),
+            None => Ok(self.pool_index_for("*")),
+        }
+    }
+
+    /// Set the retention policy for terminal tasks. Builder-style; chain onto
+    /// [`TaskQueue::new`] or [`TaskQueue::with_runners`].
+    pub fn with_retention(mut self, retention: RetentionConfig) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Sweep terminal tasks older than the configured TTL. A no-op when no TTL
+    /// is set. Called from the scheduler tick; returns the number pruned.
+    pub fn sweep_expired_tasks(&self) -> Result<usize> {
+        let Some(ttl) = self.retention.ttl else {
+            return Ok(0);
+        };
+        let age = chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::zero());
+        let cutoff = Utc::now() - age;
+        self.db.prune_terminal_tasks_before(cutoff)
+    }
+
     /// Start the background workers
     pub async fn start(&self) -> Result<()> {
-        info!("Starting task queue with {} workers", self.worker_count);
-
-        // Start worker threads
-        for worker_id in 0..self.worker_count {
-            let db = Arc::clone(&self.db);
-            let rx = Arc::clone(&self.rx);
-            let result_tx = self.result_tx.clone();
-
-            tokio::spawn(async move {
-                info!("Worker {} started", worker_id);
-
-                loop {
-                    let rx_guard = rx.read().await;
-                    match rx_guard.recv_timeout(Duration::from_secs(1)) {
-                        Ok(TaskCommand::Execute(task_id)) => {
-                            drop(rx_guard); // Release lock before processing
-                            Self::execute_task(worker_id, &db, task_id, &result_tx).await;
-                        }
-                        Ok(TaskCommand::Cancel(task_id)) => {
-                            drop(rx_guard);
-                            Self::cancel_task(&db, task_id).await;
-                        }
-                        Ok(TaskCommand::Retry(task_id)) => {
-                            drop(rx_guard);
-                            Self::retry_task(worker_id, &db, task_id, &result_tx).await;
-                        }
-                        Ok(TaskCommand::Shutdown) => {
-                            drop(rx_guard);
-                            info!("Worker {} shutting down", worker_id);
+        let total_workers: usize = self.pools.iter().map(|p| p.route.worker_count).sum();
+        info!(
+            "Starting task queue with {} workers across {} route(s)",
+            total_workers,
+            self.pools.len()
+        );
+
+        let mut handles = self.handles.lock().await;
+
+        // Each pool's workers own a local deque apiece; siblings *within the
+        // same pool* expose a `Stealer` so an idle worker can pull work from
+        // a busy sibling, without reaching into another pool's deques. A
+        // global id numbers workers across pools for logging.
+        let mut next_worker_id = 0usize;
+
+        for pool in self.pools.iter() {
+            let workers: Vec<Worker<TaskCommand>> = (0..pool.route.worker_count)
+                .map(|_| Worker::new_fifo())
+                .collect();
+            let stealers: Arc<Vec<Stealer<TaskCommand>>> =
+                Arc::new(workers.iter().map(|w| w.stealer()).collect());
+
+            for local in workers.into_iter() {
+                let worker_id = next_worker_id;
+                next_worker_id += 1;
+
+                let db = Arc::clone(&self.db);
+                let injector = Arc::clone(&pool.injector);
+                let stealers = Arc::clone(&stealers);
+                let wake = Arc::clone(&self.wake);
+                let result_tx = self.result_tx.clone();
+                let shutdown = Arc::clone(&self.shutdown);
+                let runners = Arc::clone(&self.runners);
+                let ctx = self.ctx.clone();
+                let cancels = Arc::clone(&self.cancels);
+                let agent_filter = pool.route.agent_filter.clone();
+
+                handles.push(tokio::spawn(async move {
+                    info!("Worker {} started (route '{}')", worker_id, agent_filter);
+
+                    // Consecutive empty steals, used to scale the backoff sleep.
+                    let mut empty_streak: u32 = 0;
+
+                    loop {
+                        // Stop pulling new work once a drain has begun; the task
+                        // currently being run finishes first.
+                        if shutdown.load(Ordering::SeqCst) {
+                            info!("Worker {} draining stopped", worker_id);
                             break;
                         }
-                        Err(_) => {
-                            // Timeout, check for new tasks
-                            drop(rx_guard);
+
+                        match find_task(&local, &injector, &stealers, worker_id) {
+                            Some(TaskCommand::Execute(task_id)) => {
+                                empty_streak = 0;
+                                Self::execute_task(
+                                    worker_id, &db, task_id, &result_tx, &runners, &ctx, &cancels,
+                                )
+                                .await;
+                            }
+                            Some(TaskCommand::Cancel(task_id)) => {
+                                empty_streak = 0;
+                                Self::cancel_task(&db, task_id).await;
+                            }
+                            Some(TaskCommand::Retry(task_id)) => {
+                                empty_streak = 0;
+                                Self::retry_task(worker_id, &db, task_id, &result_tx).await;
+                            }
+                            Some(TaskCommand::Pause(task_id)) => {
+                                empty_streak = 0;
+                                Self::pause_running_or_park(&db, task_id, &cancels).await;
+                            }
+                            Some(TaskCommand::Resume(task_id)) => {
+                                empty_streak = 0;
+                                Self::resume_task(&db, task_id).await;
+                            }
+                            Some(TaskCommand::Abort(task_id)) => {
+                                empty_streak = 0;
+                                Self::abort_task(&db, task_id, &cancels).await;
+                            }
+                            None => {
+                                // Nothing to steal: back off linearly with the empty
+                                // streak, capped, then park until woken by a new
+                                // submission or the backoff deadline.
+                                empty_streak = empty_streak.saturating_add(1);
+                                let nap = (BACKOFF_STEP * empty_streak).min(BACKOFF_MAX);
+                                tokio::select! {
+                                    _ = wake.notified() => {}
+                                    _ = tokio::time::sleep(nap) => {}
+                                }
+                            }
                         }
                     }
-                }
-            });
+                }));
+            }
         }
 
         // Start task scheduler
         let db = Arc::clone(&self.db);
-        let tx = self.tx.clone();
+        let pools = Arc::clone(&self.pools);
+        let wake = Arc::clone(&self.wake);
+        let shutdown = Arc::clone(&self.shutdown);
 
-        tokio::spawn(async move {
+        handles.push(tokio::spawn(async move {
             info!("Task scheduler started");
 
             loop {
                 tokio::time::sleep(Duration::from_secs(5)).await;
 
-                match Self::schedule_pending_tasks(&db, &tx).await {
+                // Stop promoting new work once a drain has begun.
+                if shutdown.load(Ordering::SeqCst) {
+                    info!("Task scheduler stopped");
+                    break;
+                }
+
+                match Self::schedule_pending_tasks(&db, &pools, &wake).await {
                     Ok(count) => {
                         if count > 0 {
                             debug!("Scheduled {} pending tasks", count);
@@ -115,13 +393,15 @@ impl TaskQueue {
                     }
                 }
             }
-        });
+        }));
 
         // Start result processor
         let db = Arc::clone(&self.db);
         let result_rx = Arc::clone(&self.result_rx);
+        let shutdown = Arc::clone(&self.shutdown);
+        let retention = self.retention;
 
-        tokio::spawn(async move {
+        handles.push(tokio::spawn(async move {
             info!("Result processor started");
 
             loop {
@@ -129,78 +409,261 @@ impl TaskQueue {
                 match rx_guard.recv_timeout(Duration::from_secs(1)) {
                     Ok(result) => {
                         drop(rx_guard);
-                        if let Err(e) = Self::process_task_result(&db, result).await {
+                        if let Err(e) = Self::process_task_result(&db, result, &retention).await {
                             error!("Error processing result: {}", e);
                         }
                     }
                     Err(_) => {
                         drop(rx_guard);
+                        // Exit only once draining and the result channel is idle,
+                        // so in-flight results are still flushed.
+                        if shutdown.load(Ordering::SeqCst) {
+                            info!("Result processor stopped");
+                            break;
+                        }
                     }
                 }
             }
-        });
+        }));
 
+        drop(handles);
         Ok(())
     }
 
     /// Submit a task for execution
     pub fn submit_task(&self, task_id: Uuid) -> Result<()> {
-        self.tx
-            .send(TaskCommand::Execute(task_id))
-            .context("Failed to submit task")?;
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool]
+            .injector
+            .push(TaskCommand::Execute(task_id));
+        self.wake.notify_waiters();
         Ok(())
     }
 
     /// Cancel a task
     pub fn cancel_task(&self, task_id: Uuid) -> Result<()> {
-        self.tx
-            .send(TaskCommand::Cancel(task_id))
-            .context("Failed to cancel task")?;
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool].injector.push(TaskCommand::Cancel(task_id));
+        self.wake.notify_waiters();
         Ok(())
     }
 
     /// Retry a failed task
     pub fn retry_task(&self, task_id: Uuid) -> Result<()> {
-        self.tx
-            .send(TaskCommand::Retry(task_id))
-            .context("Failed to retry task")?;
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool].injector.push(TaskCommand::Retry(task_id));
+        self.wake.notify_waiters();
+        Ok(())
+    }
+
+    /// Pause a task: a running executor is asked to checkpoint and stop, a
+    /// not-yet-started task is parked until resumed.
+    pub fn pause_task(&self, task_id: Uuid) -> Result<()> {
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool].injector.push(TaskCommand::Pause(task_id));
+        self.wake.notify_waiters();
         Ok(())
     }
 
-    /// Shutdown the queue
+    /// Resume a paused task, returning it to the pending pool.
+    pub fn resume_task(&self, task_id: Uuid) -> Result<()> {
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool].injector.push(TaskCommand::Resume(task_id));
+        self.wake.notify_waiters();
+        Ok(())
+    }
+
+    /// Abort a task immediately, marking it cancelled (in contrast to a graceful
+    /// [`cancel_task`](Self::cancel_task)).
+    pub fn abort_task(&self, task_id: Uuid) -> Result<()> {
+        let pool = self.pool_index_for_task(task_id)?;
+        self.pools[pool].injector.push(TaskCommand::Abort(task_id));
+        self.wake.notify_waiters();
+        Ok(())
+    }
+
+    /// Register a recurring task. The task's `schedule` cron expression is used
+    /// to compute its first fire time; the scheduler then re-dispatches it on
+    /// every occurrence without marking it terminal. Returns the task id.
+    pub fn register_recurring_task(&self, mut task: AsyncTask) -> Result<Uuid> {
+        let cron = task
+            .schedule
+            .clone()
+            .context("register_recurring_task requires a task with a schedule")?;
+        let next_fire = crate::scheduler::compute_next_fire(Some(&cron), None, Utc::now())
+            .context("Invalid cron expression for recurring task")?;
+        task.status = TaskStatus::Pending;
+        task.next_retry_at = Some(next_fire);
+        self.db.insert_task(&task)?;
+        info!("Registered recurring task {} (first fire {})", task.id, next_fire);
+        Ok(task.id)
+    }
+
+    /// Unregister a recurring task so it no longer fires. The row is marked
+    /// `Cancelled` rather than deleted, preserving its run history.
+    pub fn unregister_recurring_task(&self, task_id: Uuid) -> Result<()> {
+        self.db.update_task_status(task_id, TaskStatus::Cancelled)?;
+        info!("Unregistered recurring task {}", task_id);
+        Ok(())
+    }
+
+    /// Shutdown the queue immediately by signalling every worker to stop. Does
+    /// not wait for in-flight work; prefer [`shutdown_graceful`](Self::shutdown_graceful)
+    /// for clean process restarts.
     pub fn shutdown(&self) -> Result<()> {
-        for _ in 0..self.worker_count {
-            self.tx
-                .send(TaskCommand::Shutdown)
-                .context("Failed to send shutdown signal")?;
-        }
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake every parked worker so it observes the flag and stops.
+        self.wake.notify_waiters();
         Ok(())
     }
 
+    /// Drain the queue cleanly: stop accepting new work, let workers finish the
+    /// task they are currently running, flush any pending results through
+    /// `process_task_result`, then join the spawned loops. Work still queued when
+    /// `drain_timeout` elapses is reported as abandoned.
+    pub async fn shutdown_graceful(&self, drain_timeout: Duration) -> Result<ShutdownSummary> {
+        info!("Beginning graceful shutdown (drain timeout {:?})", drain_timeout);
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        // Wake any parked worker so it re-checks the flag and exits.
+        self.wake.notify_waiters();
+
+        // Join the worker, scheduler, and result-processor loops within budget.
+        let mut handles = self.handles.lock().await;
+        let drained = tokio::time::timeout(drain_timeout, async {
+            for handle in handles.iter_mut() {
+                let _ = handle.await;
+            }
+        })
+        .await;
+
+        let mut reset_to_pending = 0;
+        if drained.is_err() {
+            warn!("Graceful shutdown timed out; abandoning remaining workers");
+            for handle in handles.iter() {
+                handle.abort();
+            }
+
+            // Anything still marked in-flight at this point was cut off
+            // mid-run rather than finishing normally; put it back to `pending`
+            // so it's re-picked on restart instead of stranded as `running`.
+            let stranded: Vec<Uuid> = match self.cancels.lock() {
+                Ok(guard) => guard.keys().copied().collect(),
+                Err(_) => Vec::new(),
+            };
+            for task_id in stranded {
+                match self.db.reset_running_to_pending(task_id) {
+                    Ok(true) => reset_to_pending += 1,
+                    Ok(false) => {}
+                    Err(e) => error!("Error resetting interrupted task {}: {}", task_id, e),
+                }
+            }
+            if let Ok(mut guard) = self.cancels.lock() {
+                guard.clear();
+            }
+        }
+        handles.clear();
+        drop(handles);
+
+        // Flush any results the processor did not get to before it stopped.
+        let mut completed = 0;
+        {
+            let rx_guard = self.result_rx.read().await;
+            while let Ok(result) = rx_guard.try_recv() {
+                if let Err(e) = Self::process_task_result(&self.db, result, &self.retention).await {
+                    error!("Error flushing result during shutdown: {}", e);
+                } else {
+                    completed += 1;
+                }
+            }
+        }
+
+        // Anything still queued for execution is abandoned.
+        let mut abandoned = 0;
+        for pool in self.pools.iter() {
+            loop {
+                match pool.injector.steal() {
+                    Steal::Success(TaskCommand::Execute(_)) => abandoned += 1,
+                    Steal::Success(_) => {}
+                    Steal::Retry => continue,
+                    Steal::Empty => break,
+                }
+            }
+        }
+
+        let summary = ShutdownSummary {
+            completed,
+            abandoned,
+            reset_to_pending,
+        };
+        info!(
+            "Graceful shutdown complete: {} flushed, {} abandoned, {} reset to pending",
+            summary.completed, summary.abandoned, summary.reset_to_pending
+        );
+        Ok(summary)
+    }
+
     // ===== Internal Methods =====
 
-    async fn schedule_pending_tasks(db: &Database, tx: &Sender<TaskCommand>) -> Result<usize> {
-        let pending_tasks = db.get_pending_tasks(100)?;
+    async fn schedule_pending_tasks(
+        db: &Database,
+        pools: &[RoutePool],
+        wake: &Notify,
+    ) -> Result<usize> {
+        // Agents with their own dedicated route are excluded from the `"*"`
+        // pool's candidates below, so a task is never dispatched twice.
+        let dedicated_filters: HashSet<&str> = pools
+            .iter()
+            .map(|p| p.route.agent_filter.as_str())
+            .filter(|f| *f != "*")
+            .collect();
+
         let mut scheduled = 0;
 
-        for task in pending_tasks {
-            // Check if dependencies are met
-            if db.are_dependencies_completed(&task)? {
-                // Check if phase dependencies are met (if task is part of a workflow)
-                if let (Some(workflow_id), Some(phase_id)) = (task.workflow_id, &task.phase_id) {
-                    let phases = db.get_workflow_phases(workflow_id)?;
-                    if let Some(phase) = phases.iter().find(|p| p.phase_id == *phase_id) {
-                        if !db.are_phase_dependencies_completed(workflow_id, phase)? {
-                            continue;
+        for pool in pools {
+            let pending_tasks = db.get_pending_tasks_for(&pool.route.agent_filter, 100)?;
+
+            for task in pending_tasks {
+                if pool.route.agent_filter == "*"
+                    && dedicated_filters.contains(task.agent_name.as_str())
+                {
+                    continue;
+                }
+
+                // Paused tasks stay parked until explicitly resumed.
+                if task.status == TaskStatus::Paused {
+                    continue;
+                }
+
+                // Hold the task back while its agent is busy or offline; an
+                // unregistered agent is treated as available.
+                if !db.agent_accepts_tasks(&task.agent_name)? {
+                    continue;
+                }
+
+                // Check if dependencies are met
+                if db.are_dependencies_completed(&task)? {
+                    // Check if phase dependencies are met (if task is part of a workflow)
+                    if let (Some(workflow_id), Some(phase_id)) = (task.workflow_id, &task.phase_id)
+                    {
+                        let phases = db.get_workflow_phases(workflow_id)?;
+                        if let Some(phase) = phases.iter().find(|p| p.phase_id == *phase_id) {
+                            if !db.are_phase_dependencies_completed(workflow_id, phase)? {
+                                continue;
+                            }
                         }
                     }
-                }
 
-                tx.send(TaskCommand::Execute(task.id))?;
-                scheduled += 1;
+                    pool.injector.push(TaskCommand::Execute(task.id));
+                    scheduled += 1;
+                }
             }
         }
 
+        if scheduled > 0 {
+            wake.notify_waiters();
+        }
+
         Ok(scheduled)
     }
 
@@ -209,9 +672,31 @@ impl TaskQueue {
         db: &Database,
         task_id: Uuid,
         result_tx: &Sender<TaskResult>,
+        runners: &RunnerRegistry,
+        ctx: &AppContext,
+        cancels: &Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
     ) {
         info!("Worker {} executing task {}", worker_id, task_id);
 
+        // Atomically claim the task before doing any work. If another worker
+        // already claimed it (or it's no longer pending), back off instead of
+        // running it a second time — this is the guard that makes sharing one
+        // `Database` across workers/executors safe.
+        match db.claim_task(task_id, &worker_id.to_string()) {
+            Ok(true) => {}
+            Ok(false) => {
+                info!(
+                    "Worker {} skipping task {}: already claimed",
+                    worker_id, task_id
+                );
+                return;
+            }
+            Err(e) => {
+                error!("Error claiming task {}: {}", task_id, e);
+                return;
+            }
+        }
+
         // Get task details
         let task = match db.get_task(task_id) {
             Ok(Some(task)) => task,
@@ -225,40 +710,60 @@ impl TaskQueue {
             }
         };
 
-        // Update status to running
-        if let Err(e) = db.update_task_status(task_id, TaskStatus::Running) {
-            error!("Error updating task status: {}", e);
-            return;
-        }
-
         if let Err(e) = db.add_task_log(task_id, "INFO", "Task started", None) {
             warn!("Error adding log: {}", e);
         }
 
-        // Execute the task (this is where we'd integrate with Claude Code Task tool)
-        // For now, simulate execution
-        let result = Self::simulate_task_execution(&task).await;
-
-        // Send result for processing
-        if let Err(e) = result_tx.send(result) {
-            error!("Error sending task result: {}", e);
+        // Publish a cancellation token so pause/abort can reach this runner.
+        let cancel = CancellationToken::new();
+        if let Ok(mut guard) = cancels.lock() {
+            guard.insert(task_id, cancel.clone());
         }
-    }
 
-    async fn simulate_task_execution(task: &AsyncTask) -> TaskResult {
-        // TODO: Replace with actual Claude Code Task tool integration
-        // This would involve:
-        // 1. Reading agent definition from file
-        // 2. Invoking Task tool with agent instructions
-        // 3. Collecting result or error
+        // Dispatch to the runner registered for this task's kind.
+        let started = Instant::now();
+        let result = match runners.get(&task.kind) {
+            Some(runner) => runner.run(&task, ctx, &cancel).await,
+            None => {
+                warn!(
+                    "Worker {} has no runner for kind '{}' (task {})",
+                    worker_id, task.kind, task_id
+                );
+                crate::executor::unknown_kind_result(&task)
+            }
+        };
+
+        // One invocation per dispatch; wall time stands in for CPU time since
+        // runners don't report their own CPU usage.
+        if let Err(e) = db.record_task_usage(task_id, started.elapsed().as_secs_f64(), 1) {
+            warn!("Error recording usage for task {}: {}", task_id, e);
+        }
 
-        tokio::time::sleep(Duration::from_secs(2)).await;
+        if let Ok(mut guard) = cancels.lock() {
+            guard.remove(&task_id);
+        }
 
-        TaskResult {
-            task_id: task.id,
-            success: true,
-            result: Some(format!("Simulated result for task: {}", task.name)),
-            error: None,
+        // A tripped token short-circuits the normal result path: a pause parks
+        // the task (resumable) while an abort marks it cancelled.
+        match cancel.reason() {
+            Some(CancelReason::Pause) => {
+                if let Err(e) = db.update_task_status(task_id, TaskStatus::Paused) {
+                    error!("Error pausing task {}: {}", task_id, e);
+                }
+                let _ = db.add_task_log(task_id, "INFO", "Task paused", None);
+            }
+            Some(CancelReason::Abort) => {
+                if let Err(e) = db.update_task_status(task_id, TaskStatus::Cancelled) {
+                    error!("Error aborting task {}: {}", task_id, e);
+                }
+                let _ = db.add_task_log(task_id, "WARN", "Task aborted", None);
+            }
+            None => {
+                // Send result for processing
+                if let Err(e) = result_tx.send(result) {
+                    error!("Error sending task result: {}", e);
+                }
+            }
         }
     }
 
@@ -275,6 +780,64 @@ impl TaskQueue {
         }
     }
 
+    /// Pause a task: signal its running executor to checkpoint and stop, or park
+    /// a not-yet-started task directly.
+    async fn pause_running_or_park(
+        db: &Database,
+        task_id: Uuid,
+        cancels: &Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
+    ) {
+        if let Ok(guard) = cancels.lock() {
+            if let Some(token) = guard.get(&task_id) {
+                token.pause();
+                info!("Signalled pause to running task {}", task_id);
+                return;
+            }
+        }
+        // Not running: park it so the scheduler skips it until resumed.
+        if let Err(e) = db.update_task_status(task_id, TaskStatus::Paused) {
+            error!("Error pausing task {}: {}", task_id, e);
+            return;
+        }
+        let _ = db.add_task_log(task_id, "INFO", "Task paused", None);
+    }
+
+    /// Resume a paused task by returning it to the pending pool.
+    async fn resume_task(db: &Database, task_id: Uuid) {
+        match db.get_task(task_id) {
+            Ok(Some(task)) if task.status == TaskStatus::Paused => {
+                if let Err(e) = db.update_task_status(task_id, TaskStatus::Pending) {
+                    error!("Error resuming task {}: {}", task_id, e);
+                    return;
+                }
+                let _ = db.add_task_log(task_id, "INFO", "Task resumed", None);
+            }
+            Ok(_) => warn!("Task {} is not paused; ignoring resume", task_id),
+            Err(e) => error!("Error fetching task {}: {}", task_id, e),
+        }
+    }
+
+    /// Abort a task: force-stop a running executor (marked cancelled on return)
+    /// or cancel a not-yet-started task directly.
+    async fn abort_task(
+        db: &Database,
+        task_id: Uuid,
+        cancels: &Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
+    ) {
+        if let Ok(guard) = cancels.lock() {
+            if let Some(token) = guard.get(&task_id) {
+                token.abort();
+                info!("Signalled abort to running task {}", task_id);
+                return;
+            }
+        }
+        if let Err(e) = db.update_task_status(task_id, TaskStatus::Cancelled) {
+            error!("Error aborting task {}: {}", task_id, e);
+            return;
+        }
+        let _ = db.add_task_log(task_id, "WARN", "Task aborted", None);
+    }
+
     async fn retry_task(
         worker_id: usize,
         db: &Database,
@@ -311,18 +874,83 @@ impl TaskQueue {
         }
     }
 
-    async fn process_task_result(db: &Database, result: TaskResult) -> Result<()> {
+    async fn process_task_result(
+        db: &Database,
+        result: TaskResult,
+        retention: &RetentionConfig,
+    ) -> Result<()> {
         info!("Processing result for task {}", result.task_id);
 
+        // Recurring tasks never reach a terminal state: record the run's outcome
+        // and advance the task to its next cron occurrence instead.
+        if let Some(task) = db.get_task(result.task_id)? {
+            if let Some(cron) = &task.schedule {
+                match crate::scheduler::compute_next_fire(Some(cron), None, Utc::now()) {
+                    Some(next_fire) => {
+                        db.reschedule_recurring_task(
+                            result.task_id,
+                            next_fire,
+                            result.result.clone(),
+                            result.error.clone(),
+                        )?;
+                        db.add_task_log(
+                            result.task_id,
+                            "INFO",
+                            &format!("Recurring run complete, next fire at {}", next_fire),
+                            None,
+                        )?;
+                    }
+                    None => {
+                        // Unparseable cron: stop recurring and fail loudly.
+                        db.update_task_error(
+                            result.task_id,
+                            format!("Invalid cron schedule '{}'", cron),
+                        )?;
+                    }
+                }
+                return Ok(());
+            }
+        }
+
         if result.success {
             if let Some(result_data) = result.result {
                 db.update_task_result(result.task_id, result_data)?;
                 db.add_task_log(result.task_id, "INFO", "Task completed successfully", None)?;
             }
-        } else {
-            if let Some(error) = result.error {
-                db.update_task_error(result.task_id, error)?;
-                db.add_task_log(result.task_id, "ERROR", "Task failed", None)?;
+        } else if let Some(error) = result.error {
+            // Fatal failures halt the phase and block dependents; retryable
+            // failures re-queue with backoff while retries remain.
+            match result.error_class.unwrap_or(ErrorClass::Retryable) {
+                ErrorClass::Fatal => {
+                    db.update_task_error_classified(result.task_id, error, ErrorClass::Fatal)?;
+                    db.add_task_log(result.task_id, "ERROR", "Task failed fatally", None)?;
+                    if let Some(task) = db.get_task(result.task_id)? {
+                        if let Some(workflow_id) = task.workflow_id {
+                            let blocked = db.block_dependents(workflow_id, &task)?;
+                            if blocked > 0 {
+                                db.add_task_log(
+                                    result.task_id,
+                                    "WARN",
+                                    &format!("{} dependent task(s) blocked", blocked),
+                                    None,
+                                )?;
+                            }
+                        }
+                    }
+                }
+                ErrorClass::Retryable => match db.schedule_task_retry(result.task_id, &error)? {
+                    Some(next_retry_at) => {
+                        db.add_task_log(
+                            result.task_id,
+                            "WARN",
+                            &format!("Task failed, retry scheduled for {}", next_retry_at),
+                            None,
+                        )?;
+                    }
+                    None => {
+                        db.add_task_log(result.task_id, "ERROR", "Task failed (retries exhausted)", None)?;
+                    }
+                },
             }
         }
 
@@ -337,6 +965,14 @@ impl TaskQueue {
             if let Some(workflow_id) = task.workflow_id {
                 Self::update_workflow_status(db, workflow_id).await?;
             }
+
+            // Apply the retention policy once the task has settled. The workflow
+            // status above was computed with the row still present; pruning now
+            // records a tally so later sibling results still compute correctly.
+            if task.status.is_terminal() && retention.mode.prunes(task.status) {
+                db.prune_task(result.task_id)?;
+                debug!("Pruned terminal task {} per retention policy", result.task_id);
+            }
         }
 
         Ok(())
@@ -344,9 +980,14 @@ impl TaskQueue {
 
     async fn update_workflow_status(db: &Database, workflow_id: Uuid) -> Result<()> {
         let tasks = db.get_workflow_tasks(workflow_id)?;
-
-        let all_completed = tasks.iter().all(|t| t.status == TaskStatus::Completed);
-        let any_failed = tasks.iter().any(|t| t.status == TaskStatus::Failed);
+        // Fold in tasks already pruned by the retention policy.
+        let pruned = db.get_pruned_task_counts(workflow_id)?;
+
+        let all_completed = tasks.iter().all(|t| t.status == TaskStatus::Completed)
+            && pruned.failed == 0
+            && pruned.cancelled == 0;
+        let any_failed = tasks.iter().any(|t| t.status == TaskStatus::Failed) || pruned.failed > 0;
+        // Pruned tasks were terminal by definition, so the live rows decide.
         let all_done = tasks.iter().all(|t| t.is_complete());
 
         if any_failed {
@@ -373,6 +1014,16 @@ impl WorkflowExecutor {
         Self { db, queue }
     }
 
+    /// Register a recurring task through the owned queue.
+    pub fn register_recurring_task(&self, task: AsyncTask) -> Result<Uuid> {
+        self.queue.register_recurring_task(task)
+    }
+
+    /// Unregister a recurring task through the owned queue.
+    pub fn unregister_recurring_task(&self, task_id: Uuid) -> Result<()> {
+        self.queue.unregister_recurring_task(task_id)
+    }
+
     /// Create a new workflow
     pub fn create_workflow(&self, name: String, description: Option<String>) -> Result<Uuid> {
         let workflow = Workflow {
@@ -452,6 +1103,215 @@ impl WorkflowExecutor {
         Ok(())
     }
 
+    /// Materialize a stored template into a concrete workflow in one call,
+    /// applying `{{key}}` parameter substitutions to task names and
+    /// instructions. Returns the new workflow id.
+    pub fn instantiate_template(
+        &self,
+        template: &WorkflowTemplate,
+        params: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Uuid> {
+        let name = WorkflowTemplate::substitute(&template.name, params);
+        let description = template
+            .description
+            .as_ref()
+            .map(|d| WorkflowTemplate::substitute(d, params));
+
+        let workflow_id = self.create_workflow(name, description)?;
+
+        for phase in &template.phases {
+            self.add_phase(
+                workflow_id,
+                phase.phase_id.clone(),
+                WorkflowTemplate::substitute(&phase.name, params),
+                phase.depends_on.clone(),
+            )?;
+        }
+
+        for task in &template.tasks {
+            let concrete = AsyncTask::new(
+                WorkflowTemplate::substitute(&task.name, params),
+                task.agent_name.clone(),
+                WorkflowTemplate::substitute(&task.agent_instructions, params),
+            );
+            self.add_phase_task(workflow_id, task.phase_id.clone(), concrete)?;
+        }
+
+        Ok(workflow_id)
+    }
+
+    /// Validate and construct an entire workflow graph from a single
+    /// submission. The submission is checked in full before anything is
+    /// persisted: duplicate phase IDs, unknown phase/task dependency references,
+    /// and cycles in either the phase graph or the task dependency graph are
+    /// rejected with a precise error naming the offending node. On success the
+    /// new workflow id and a map from each caller-supplied task name to its
+    /// generated UUID are returned.
+    pub fn submit_workflow(
+        &self,
+        submission: &WorkflowSubmission,
+    ) -> Result<(Uuid, HashMap<String, Uuid>)> {
+        // Phase graph validation.
+        let mut phase_ids = HashSet::new();
+        for phase in &submission.phases {
+            if !phase_ids.insert(phase.phase_id.as_str()) {
+                return Err(anyhow::anyhow!("Duplicate phase ID: {}", phase.phase_id));
+            }
+        }
+        for phase in &submission.phases {
+            for dep in &phase.depends_on {
+                if !phase_ids.contains(dep.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Phase '{}' depends on unknown phase '{}'",
+                        phase.phase_id,
+                        dep
+                    ));
+                }
+            }
+        }
+        let phase_edges: Vec<(&str, Vec<&str>)> = submission
+            .phases
+            .iter()
+            .map(|p| (p.phase_id.as_str(), p.depends_on.iter().map(|s| s.as_str()).collect()))
+            .collect();
+        if let Some(node) = detect_cycle(&phase_edges) {
+            return Err(anyhow::anyhow!("Cycle detected in phase graph at '{}'", node));
+        }
+
+        // Task graph validation.
+        let mut task_names = HashSet::new();
+        for task in &submission.tasks {
+            if !task_names.insert(task.name.as_str()) {
+                return Err(anyhow::anyhow!("Duplicate task name: {}", task.name));
+            }
+            if !phase_ids.contains(task.phase_id.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Task '{}' references unknown phase '{}'",
+                    task.name,
+                    task.phase_id
+                ));
+            }
+        }
+        for task in &submission.tasks {
+            for dep in &task.dependencies {
+                if !task_names.contains(dep.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Task '{}' depends on unknown task '{}'",
+                        task.name,
+                        dep
+                    ));
+                }
+            }
+        }
+        let task_edges: Vec<(&str, Vec<&str>)> = submission
+            .tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.dependencies.iter().map(|s| s.as_str()).collect()))
+            .collect();
+        if let Some(node) = detect_cycle(&task_edges) {
+            return Err(anyhow::anyhow!(
+                "Cycle detected in task dependency graph at '{}'",
+                node
+            ));
+        }
+
+        // Everything is valid; build the graph.
+        let workflow_id = self.create_workflow(submission.name.clone(), submission.description.clone())?;
+
+        for phase in &submission.phases {
+            self.add_phase(
+                workflow_id,
+                phase.phase_id.clone(),
+                phase.name.clone(),
+                phase.depends_on.clone(),
+            )?;
+        }
+
+        // Assign UUIDs up front so inter-task dependencies can be wired by name.
+        let mut name_to_id = HashMap::new();
+        let mut built: Vec<(AsyncTask, &SubmissionTask)> = Vec::new();
+        for spec in &submission.tasks {
+            let mut task = AsyncTask::new(
+                spec.name.clone(),
+                spec.agent_name.clone(),
+                spec.agent_instructions.clone(),
+            );
+            if let Some(priority) = &spec.priority {
+                task.priority = match priority.as_str() {
+                    "low" => TaskPriority::Low,
+                    "high" => TaskPriority::High,
+                    "critical" => TaskPriority::Critical,
+                    _ => TaskPriority::Normal,
+                };
+            }
+            name_to_id.insert(spec.name.clone(), task.id);
+            built.push((task, spec));
+        }
+
+        for (mut task, spec) in built {
+            task.dependencies = spec
+                .dependencies
+                .iter()
+                .filter_map(|name| name_to_id.get(name).copied())
+                .collect();
+            self.add_phase_task(workflow_id, spec.phase_id.clone(), task)?;
+        }
+
+        Ok((workflow_id, name_to_id))
+    }
+
+    /// Resume an interrupted workflow deterministically. Completed tasks are
+    /// never re-executed; only tasks that are `pending`, `running`, or failed
+    /// with a retryable error — and whose phase dependencies and task
+    /// dependencies are all `completed` — are re-dispatched. Resumption is
+    /// driven purely from persisted status and the dependency graph, so replay
+    /// is idempotent.
+    pub fn resume_workflow(&self, workflow_id: Uuid) -> Result<usize> {
+        self.db
+            .update_workflow_status(workflow_id, TaskStatus::Running)?;
+
+        let tasks = self.db.get_workflow_tasks(workflow_id)?;
+        let phases = self.db.get_workflow_phases(workflow_id)?;
+        let mut resumed = 0;
+
+        for task in &tasks {
+            let eligible = match task.status {
+                TaskStatus::Pending | TaskStatus::Running => true,
+                TaskStatus::Failed => task.error_class != Some(ErrorClass::Fatal),
+                _ => false,
+            };
+            if !eligible {
+                continue;
+            }
+
+            // Task-level dependencies must all be completed.
+            if !self.db.are_dependencies_completed(task)? {
+                continue;
+            }
+
+            // Phase-level dependencies must all be completed.
+            if let Some(phase_id) = &task.phase_id {
+                if let Some(phase) = phases.iter().find(|p| p.phase_id == *phase_id) {
+                    if !self
+                        .db
+                        .are_phase_dependencies_completed(workflow_id, phase)?
+                    {
+                        continue;
+                    }
+                }
+            }
+
+            // Reset a stale running/retryable-failed task back to pending.
+            if task.status != TaskStatus::Pending {
+                self.db.update_task_status(task.id, TaskStatus::Pending)?;
+            }
+            self.queue.submit_task(task.id)?;
+            resumed += 1;
+        }
+
+        Ok(resumed)
+    }
+
     /// Get workflow status
     pub fn get_workflow_status(&self, workflow_id: Uuid) -> Result<WorkflowStatus> {
         let workflow = self
@@ -474,6 +1334,21 @@ impl WorkflowExecutor {
             .iter()
             .filter(|t| t.status == TaskStatus::Running)
             .count();
+        let blocked_tasks = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Blocked)
+            .count();
+
+        let failures = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Failed)
+            .map(|t| TaskFailure {
+                task_id: t.id,
+                name: t.name.clone(),
+                error_class: t.error_class,
+                error: t.error.clone(),
+            })
+            .collect();
 
         Ok(WorkflowStatus {
             workflow,
@@ -482,10 +1357,130 @@ impl WorkflowExecutor {
             completed_tasks,
             failed_tasks,
             running_tasks,
+            blocked_tasks,
+            failures,
         })
     }
 }
 
+/// Reusable multi-phase workflow blueprint. Stored in the DB and materialized
+/// into a concrete workflow by [`WorkflowExecutor::instantiate_template`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WorkflowTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub phases: Vec<TemplatePhase>,
+    #[serde(default)]
+    pub tasks: Vec<TemplateTask>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TemplatePhase {
+    pub phase_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TemplateTask {
+    pub phase_id: String,
+    pub name: String,
+    pub agent_name: String,
+    pub agent_instructions: String,
+}
+
+/// A full workflow graph submitted in one call: the workflow metadata, every
+/// phase with its `depends_on`, and every task with its inter-task
+/// `dependencies` expressed by task name.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WorkflowSubmission {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub phases: Vec<SubmissionPhase>,
+    #[serde(default)]
+    pub tasks: Vec<SubmissionTask>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubmissionPhase {
+    pub phase_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubmissionTask {
+    pub name: String,
+    pub phase_id: String,
+    pub agent_name: String,
+    pub agent_instructions: String,
+    /// Names of other tasks in this submission that must complete first.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+}
+
+/// Detect a cycle in a dependency graph given as `(node, prerequisites)` edges.
+/// Returns the name of a node participating in a cycle, or `None` if the graph
+/// is a DAG (i.e. topologically sortable).
+fn detect_cycle<'a>(edges: &[(&'a str, Vec<&'a str>)]) -> Option<String> {
+    let graph: HashMap<&str, &Vec<&str>> = edges.iter().map(|(n, deps)| (*n, deps)).collect();
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut on_stack: HashSet<&str> = HashSet::new();
+
+    fn visit<'a>(
+        node: &'a str,
+        graph: &HashMap<&'a str, &Vec<&'a str>>,
+        visited: &mut HashSet<&'a str>,
+        on_stack: &mut HashSet<&'a str>,
+    ) -> Option<String> {
+        if on_stack.contains(node) {
+            return Some(node.to_string());
+        }
+        if visited.contains(node) {
+            return None;
+        }
+        visited.insert(node);
+        on_stack.insert(node);
+        if let Some(deps) = graph.get(node) {
+            for dep in deps.iter() {
+                if let Some(found) = visit(dep, graph, visited, on_stack) {
+                    return Some(found);
+                }
+            }
+        }
+        on_stack.remove(node);
+        None
+    }
+
+    for (node, _) in edges {
+        if let Some(found) = visit(node, &graph, &mut visited, &mut on_stack) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+impl WorkflowTemplate {
+    /// Apply `{{key}}` parameter substitutions to every templated string field.
+    fn substitute(text: &str, params: &serde_json::Map<String, serde_json::Value>) -> String {
+        let mut out = text.to_string();
+        for (key, value) in params {
+            let needle = format!("{{{{{}}}}}", key);
+            let replacement = value.as_str().map(|s| s.to_string()).unwrap_or_else(|| value.to_string());
+            out = out.replace(&needle, &replacement);
+        }
+        out
+    }
+}
+
 /// Workflow status summary
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WorkflowStatus {
@@ -495,6 +1490,17 @@ pub struct WorkflowStatus {
     pub completed_tasks: usize,
     pub failed_tasks: usize,
     pub running_tasks: usize,
+    pub blocked_tasks: usize,
+    pub failures: Vec<TaskFailure>,
+}
+
+/// A failed task and its error classification, surfaced in `WorkflowStatus`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TaskFailure {
+    pub task_id: Uuid,
+    pub name: String,
+    pub error_class: Option<ErrorClass>,
+    pub error: Option<String>,
 }
 
 #[cfg(test)]
@@ -519,4 +1525,204 @@ mod tests {
             .unwrap();
         assert!(!workflow_id.is_nil());
     }
+
+    #[tokio::test]
+    async fn test_register_recurring_task_sets_first_fire() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let queue = TaskQueue::new(db.clone(), 1);
+
+        let task = AsyncTask::new("poll".to_string(), "agent".to_string(), "check".to_string())
+            .with_schedule("0 * * * * *".to_string());
+        let id = queue.register_recurring_task(task).unwrap();
+
+        let stored = db.get_task(id).unwrap().unwrap();
+        assert_eq!(stored.status, TaskStatus::Pending);
+        assert!(stored.is_recurring());
+        assert!(stored.next_retry_at.is_some());
+
+        queue.unregister_recurring_task(id).unwrap();
+        assert_eq!(db.get_task(id).unwrap().unwrap().status, TaskStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_with_routes_dedicates_a_pool_per_agent() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let ctx = AppContext::new(db.clone());
+        let routes = vec![WorkerRoute::new("agent1", 2), WorkerRoute::new("*", 1)];
+        let queue = TaskQueue::with_routes(db.clone(), routes, RunnerRegistry::new(), ctx);
+
+        let for_agent1 = AsyncTask::new("t".to_string(), "agent1".to_string(), "do".to_string());
+        let for_agent1_id = for_agent1.id;
+        let for_other = AsyncTask::new("t".to_string(), "agent2".to_string(), "do".to_string());
+        let for_other_id = for_other.id;
+        db.insert_task(&for_agent1).unwrap();
+        db.insert_task(&for_other).unwrap();
+
+        let scheduled = TaskQueue::schedule_pending_tasks(&db, &queue.pools, &queue.wake)
+            .await
+            .unwrap();
+        // Each task is dispatched to exactly one pool, never both.
+        assert_eq!(scheduled, 2);
+
+        let agent1_pool_has = |id: Uuid| {
+            matches!(
+                queue.pools[0].injector.steal(),
+                Steal::Success(TaskCommand::Execute(t)) if t == id
+            )
+        };
+        let wildcard_pool_has = |id: Uuid| {
+            matches!(
+                queue.pools[1].injector.steal(),
+                Steal::Success(TaskCommand::Execute(t)) if t == id
+            )
+        };
+        assert!(agent1_pool_has(for_agent1_id));
+        assert!(wildcard_pool_has(for_other_id));
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown_joins_workers() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let queue = TaskQueue::new(db, 2);
+        queue.start().await.unwrap();
+
+        let summary = queue
+            .shutdown_graceful(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(summary.abandoned, 0);
+    }
+
+    #[test]
+    fn test_find_task_steals_from_sibling() {
+        let injector = Injector::new();
+        let w0 = Worker::new_fifo();
+        let w1 = Worker::new_fifo();
+        let stealers = vec![w0.stealer(), w1.stealer()];
+
+        // Work lives on worker 1's deque; worker 0's is empty.
+        let id = Uuid::new_v4();
+        w1.push(TaskCommand::Execute(id));
+
+        match find_task(&w0, &injector, &stealers, 0) {
+            Some(TaskCommand::Execute(got)) => assert_eq!(got, id),
+            other => panic!("expected stolen Execute, got {other:?}"),
+        }
+        // Nothing left anywhere.
+        assert!(find_task(&w0, &injector, &stealers, 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retention_prunes_successful_tasks() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string());
+        db.insert_task(&task).unwrap();
+
+        let result = TaskResult {
+            task_id: task.id,
+            success: true,
+            result: Some("ok".to_string()),
+            error: None,
+            error_class: None,
+        };
+        let retention = RetentionConfig {
+            mode: RetentionMode::RemoveDoneSuccessOnly,
+            ttl: None,
+        };
+        TaskQueue::process_task_result(&db, result, &retention)
+            .await
+            .unwrap();
+
+        // The successful task row is gone.
+        assert!(db.get_task(task.id).unwrap().is_none());
+
+        // A failed task under the same policy is kept for debugging.
+        let failing = AsyncTask::new("t2".to_string(), "a".to_string(), "do".to_string());
+        db.insert_task(&failing).unwrap();
+        let result = TaskResult {
+            task_id: failing.id,
+            success: false,
+            result: None,
+            error: Some("boom".to_string()),
+            error_class: Some(ErrorClass::Fatal),
+        };
+        TaskQueue::process_task_result(&db, result, &retention)
+            .await
+            .unwrap();
+        assert!(db.get_task(failing.id).unwrap().is_some());
+    }
+
+    struct EchoRunner;
+
+    #[async_trait::async_trait]
+    impl crate::executor::TaskRunner for EchoRunner {
+        async fn run(
+            &self,
+            task: &AsyncTask,
+            _ctx: &AppContext,
+            _cancel: &CancellationToken,
+        ) -> TaskResult {
+            TaskResult {
+                task_id: task.id,
+                success: true,
+                result: Some(format!("echo: {}", task.agent_instructions)),
+                error: None,
+                error_class: None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_runner_executes_task() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let mut runners = RunnerRegistry::new();
+        runners.insert("echo".to_string(), Arc::new(EchoRunner));
+        let ctx = AppContext::new(db.clone());
+        let cancels = Arc::new(Mutex::new(HashMap::new()));
+        // Exercise dispatch directly so the test does not race the worker loop.
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "hi".to_string())
+            .with_kind("echo");
+        db.insert_task(&task).unwrap();
+
+        let (tx, rx) = bounded::<TaskResult>(1);
+        TaskQueue::execute_task(0, &db, task.id, &tx, &runners, &ctx, &cancels).await;
+        let result = rx.recv().unwrap();
+        assert!(result.success);
+        assert_eq!(result.result.as_deref(), Some("echo: hi"));
+
+        // An unregistered kind fails fatally rather than silently succeeding.
+        let unknown = AsyncTask::new("t".to_string(), "a".to_string(), "hi".to_string())
+            .with_kind("missing");
+        db.insert_task(&unknown).unwrap();
+        TaskQueue::execute_task(0, &db, unknown.id, &tx, &runners, &ctx, &cancels).await;
+        let result = rx.recv().unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error_class, Some(ErrorClass::Fatal));
+    }
+
+    #[tokio::test]
+    async fn test_pause_parks_and_resume_requeues() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let cancels = Arc::new(Mutex::new(HashMap::new()));
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string());
+        db.insert_task(&task).unwrap();
+
+        // Not running: pause parks it, and the scheduler skips paused tasks.
+        TaskQueue::pause_running_or_park(&db, task.id, &cancels).await;
+        assert_eq!(db.get_task(task.id).unwrap().unwrap().status, TaskStatus::Paused);
+
+        // Resume returns it to the pending pool.
+        TaskQueue::resume_task(&db, task.id).await;
+        assert_eq!(db.get_task(task.id).unwrap().unwrap().status, TaskStatus::Pending);
+    }
+
+    #[test]
+    fn test_cancellation_token_abort_overrides_pause() {
+        let token = CancellationToken::new();
+        assert!(token.reason().is_none());
+        token.pause();
+        assert_eq!(token.reason(), Some(CancelReason::Pause));
+        token.abort();
+        assert_eq!(token.reason(), Some(CancelReason::Abort));
+    }
 }
@@ -0,0 +1,238 @@
+//! JSON-RPC 2.0 endpoint layered over the REST API.
+//!
+//! The REST surface issues one HTTP call per operation, which is wasteful for
+//! automation that creates many tasks or builds a workflow phase-by-phase. This
+//! module adds a single `/api/rpc` route that speaks JSON-RPC 2.0 so such
+//! clients can batch several operations into one round trip. Method strings map
+//! onto the same [`ApiState`] logic the REST handlers use (e.g. `task.create`,
+//! `workflow.start`), and either a single request object or an array of them is
+//! accepted. Notifications (requests without an `id`) are executed but produce
+//! no response.
+
+use crate::api::{
+    add_workflow_task_inner, create_task_inner, AddWorkflowTaskRequest, ApiError,
+    ApiState, CreateTaskRequest, CreateWorkflowRequest,
+};
+use axum::{extract::State, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A single JSON-RPC 2.0 request object.
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    #[allow(dead_code)]
+    jsonrpc: Option<String>,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+    /// Absent for notifications, which receive no response.
+    #[serde(default)]
+    id: Option<Value>,
+}
+
+/// A single JSON-RPC 2.0 response object.
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcErrorObject>,
+    id: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcErrorObject {
+    code: i32,
+    message: String,
+}
+
+/// A dispatch failure carrying the JSON-RPC code to surface.
+struct RpcFailure {
+    code: i32,
+    message: String,
+}
+
+impl RpcFailure {
+    fn method_not_found(method: &str) -> Self {
+        Self {
+            code: -32601,
+            message: format!("Method not found: {method}"),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: -32602,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ApiError> for RpcFailure {
+    fn from(err: ApiError) -> Self {
+        // Any error from the shared handler logic is an internal failure.
+        Self {
+            code: -32603,
+            message: err.message,
+        }
+    }
+}
+
+/// Axum handler for `POST /api/rpc`. Accepts a single request object or a batch
+/// array and returns matching responses (a single object, or an array).
+pub async fn rpc_endpoint(State(state): State<ApiState>, Json(body): Json<Value>) -> impl IntoResponse {
+    match body {
+        Value::Array(elements) => {
+            let mut responses = Vec::new();
+            for element in elements {
+                if let Some(resp) = dispatch_element(&state, element).await {
+                    responses.push(resp);
+                }
+            }
+            Json(Value::Array(
+                responses
+                    .into_iter()
+                    .map(|r| serde_json::to_value(r).unwrap_or(Value::Null))
+                    .collect(),
+            ))
+            .into_response()
+        }
+        other => match dispatch_element(&state, other).await {
+            Some(resp) => Json(serde_json::to_value(resp).unwrap_or(Value::Null)).into_response(),
+            // A lone notification warrants no body.
+            None => axum::http::StatusCode::NO_CONTENT.into_response(),
+        },
+    }
+}
+
+/// Dispatch one batch element. Returns `None` for notifications (no `id`).
+async fn dispatch_element(state: &ApiState, element: Value) -> Option<RpcResponse> {
+    let id = element.get("id").cloned();
+    let req: RpcRequest = match serde_json::from_value(element) {
+        Ok(req) => req,
+        Err(e) => {
+            return id.map(|id| error_response(id, RpcFailure::invalid_params(e.to_string())));
+        }
+    };
+
+    let response_id = req.id.clone();
+    let outcome = dispatch_method(state, &req.method, req.params).await;
+
+    // Only reply when the request carried an id.
+    response_id.map(|id| match outcome {
+        Ok(result) => RpcResponse {
+            jsonrpc: "2.0",
+            result: Some(result),
+            error: None,
+            id,
+        },
+        Err(failure) => error_response(id, failure),
+    })
+}
+
+fn error_response(id: Value, failure: RpcFailure) -> RpcResponse {
+    RpcResponse {
+        jsonrpc: "2.0",
+        result: None,
+        error: Some(RpcErrorObject {
+            code: failure.code,
+            message: failure.message,
+        }),
+        id,
+    }
+}
+
+/// Route a method string to the shared handler logic.
+async fn dispatch_method(
+    state: &ApiState,
+    method: &str,
+    params: Option<Value>,
+) -> Result<Value, RpcFailure> {
+    match method {
+        "task.create" => {
+            let req: CreateTaskRequest = parse_params(params)?;
+            let resp = create_task_inner(state, req).await?;
+            Ok(serde_json::to_value(resp).unwrap_or(Value::Null))
+        }
+        "task.cancel" => {
+            let id = parse_id(params)?;
+            state.queue.cancel_task(id).map_err(ApiError::from)?;
+            Ok(serde_json::json!({ "success": true }))
+        }
+        "task.retry" => {
+            let id = parse_id(params)?;
+            state.queue.retry_task(id).map_err(ApiError::from)?;
+            Ok(serde_json::json!({ "success": true }))
+        }
+        "workflow.create" => {
+            let req: CreateWorkflowRequest = parse_params(params)?;
+            let workflow_id = state
+                .executor
+                .create_workflow(req.name, req.description)
+                .map_err(ApiError::from)?;
+            Ok(serde_json::json!({ "workflow_id": workflow_id, "status": "pending" }))
+        }
+        "workflow.add_phase" => {
+            let req: AddPhaseParams = parse_params(params)?;
+            state
+                .executor
+                .add_phase(req.workflow_id, req.phase_id, req.name, req.depends_on)
+                .map_err(ApiError::from)?;
+            Ok(serde_json::json!({ "success": true }))
+        }
+        "workflow.add_task" => {
+            let req: AddTaskParams = parse_params(params)?;
+            let task_id = add_workflow_task_inner(state, req.workflow_id, req.task).await?;
+            Ok(serde_json::json!({ "success": true, "task_id": task_id }))
+        }
+        "workflow.start" => {
+            let req: WorkflowIdParams = parse_params(params)?;
+            state
+                .executor
+                .start_workflow(req.workflow_id)
+                .map_err(ApiError::from)?;
+            Ok(serde_json::json!({ "success": true }))
+        }
+        other => Err(RpcFailure::method_not_found(other)),
+    }
+}
+
+/// Deserialize the params object into the expected request type.
+fn parse_params<T: for<'de> Deserialize<'de>>(params: Option<Value>) -> Result<T, RpcFailure> {
+    serde_json::from_value(params.unwrap_or(Value::Null))
+        .map_err(|e| RpcFailure::invalid_params(e.to_string()))
+}
+
+/// Pull a single `id` (task or workflow uuid) out of the params object.
+fn parse_id(params: Option<Value>) -> Result<Uuid, RpcFailure> {
+    #[derive(Deserialize)]
+    struct IdParams {
+        id: Uuid,
+    }
+    let p: IdParams = parse_params(params)?;
+    Ok(p.id)
+}
+
+#[derive(Deserialize)]
+struct WorkflowIdParams {
+    workflow_id: Uuid,
+}
+
+#[derive(Deserialize)]
+struct AddPhaseParams {
+    workflow_id: Uuid,
+    phase_id: String,
+    name: String,
+    #[serde(default)]
+    depends_on: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct AddTaskParams {
+    workflow_id: Uuid,
+    #[serde(flatten)]
+    task: AddWorkflowTaskRequest,
+}
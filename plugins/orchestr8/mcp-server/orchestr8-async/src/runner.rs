@@ -0,0 +1,385 @@
+use crate::db::{AsyncTask, Database, TaskStatus};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A remote worker that claims and executes tasks on a separate machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Runner {
+    pub id: Uuid,
+    pub name: String,
+    pub status: RunnerStatus,
+    pub last_heartbeat: DateTime<Utc>,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Liveness of a registered runner, derived from its heartbeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunnerStatus {
+    Online,
+    Offline,
+}
+
+impl RunnerStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunnerStatus::Online => "online",
+            RunnerStatus::Offline => "offline",
+        }
+    }
+}
+
+/// Coordinator configuration.
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    /// How long a claimed task's lease is valid before the heartbeat must renew it.
+    pub lease_ttl_seconds: i64,
+    /// How long after its last heartbeat a runner is considered offline.
+    pub heartbeat_timeout_seconds: i64,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            lease_ttl_seconds: 60,
+            heartbeat_timeout_seconds: 90,
+        }
+    }
+}
+
+/// Coordinates a fleet of external runners: registration, task claiming with
+/// leases, heartbeat tracking, and reclamation of tasks whose runner has gone
+/// silent. This turns the in-process executor into a driver for remote workers.
+pub struct RunnerCoordinator {
+    db: Arc<Database>,
+    config: RunnerConfig,
+}
+
+impl RunnerCoordinator {
+    pub fn new(db: Arc<Database>, config: RunnerConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn with_defaults(db: Arc<Database>) -> Self {
+        Self::new(db, RunnerConfig::default())
+    }
+
+    /// Register a new runner and return its assigned identity.
+    pub fn register(&self, name: &str) -> Result<Runner> {
+        let runner = Runner {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            status: RunnerStatus::Online,
+            last_heartbeat: Utc::now(),
+            registered_at: Utc::now(),
+        };
+
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "INSERT INTO runners (id, name, status, last_heartbeat, registered_at)
+             VALUES (?, ?, ?, ?, ?)",
+            duckdb::params![
+                runner.id.to_string(),
+                runner.name,
+                runner.status.as_str(),
+                runner.last_heartbeat,
+                runner.registered_at,
+            ],
+        )?;
+
+        info!("Runner '{}' registered as {}", name, runner.id);
+        Ok(runner)
+    }
+
+    /// Record a heartbeat from a runner, renewing any leases it holds.
+    pub fn heartbeat(&self, runner_id: Uuid) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        let now = Utc::now();
+        conn.execute(
+            "UPDATE runners SET last_heartbeat = ?, status = 'online' WHERE id = ?",
+            duckdb::params![now, runner_id.to_string()],
+        )?;
+        conn.execute(
+            "UPDATE task_leases SET heartbeat_at = ?, expires_at = ? WHERE runner_id = ?",
+            duckdb::params![
+                now,
+                now + ChronoDuration::seconds(self.config.lease_ttl_seconds),
+                runner_id.to_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Claim the next ready pending task for a runner, leasing it and marking it
+    /// running. Returns `None` when nothing is ready, so the caller can long-poll.
+    pub fn claim_task(&self, runner_id: Uuid) -> Result<Option<AsyncTask>> {
+        let candidates = self.db.get_pending_tasks(50)?;
+
+        for task in candidates {
+            // Skip tasks whose dependencies aren't done or that are already leased.
+            if !self.db.are_dependencies_completed(&task)? {
+                continue;
+            }
+            if self.is_leased(task.id)? {
+                continue;
+            }
+
+            let now = Utc::now();
+            let conn = self.db.get_conn()?;
+            conn.execute(
+                "INSERT INTO task_leases (task_id, runner_id, leased_at, heartbeat_at, expires_at)
+                 VALUES (?, ?, ?, ?, ?)",
+                duckdb::params![
+                    task.id.to_string(),
+                    runner_id.to_string(),
+                    now,
+                    now,
+                    now + ChronoDuration::seconds(self.config.lease_ttl_seconds),
+                ],
+            )?;
+            self.db.update_task_status(task.id, TaskStatus::Running)?;
+            self.db.add_task_log(
+                task.id,
+                "INFO",
+                &format!("Task claimed by runner {}", runner_id),
+                None,
+            )?;
+
+            info!("Runner {} claimed task {}", runner_id, task.id);
+            return Ok(Some(task));
+        }
+
+        Ok(None)
+    }
+
+    fn is_leased(&self, task_id: Uuid) -> Result<bool> {
+        let conn = self.db.get_conn()?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM task_leases WHERE task_id = ?",
+            duckdb::params![task_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Append a streamed log chunk from the runner holding the task's lease.
+    pub fn append_log(&self, task_id: Uuid, runner_id: Uuid, chunk: &str) -> Result<()> {
+        self.verify_lease(task_id, runner_id)?;
+        self.db.add_task_log(task_id, "INFO", chunk, None)?;
+        Ok(())
+    }
+
+    /// Finalize a task from its runner, recording the result or error and
+    /// releasing the lease.
+    pub fn submit_result(
+        &self,
+        task_id: Uuid,
+        runner_id: Uuid,
+        success: bool,
+        result: Option<String>,
+        error: Option<String>,
+    ) -> Result<()> {
+        self.verify_lease(task_id, runner_id)?;
+
+        if success {
+            self.db
+                .update_task_result(task_id, result.unwrap_or_default())?;
+        } else {
+            self.db
+                .update_task_error(task_id, error.unwrap_or_else(|| "runner reported failure".to_string()))?;
+        }
+
+        self.release_lease(task_id)?;
+        info!("Runner {} submitted result for task {}", runner_id, task_id);
+        Ok(())
+    }
+
+    fn verify_lease(&self, task_id: Uuid, runner_id: Uuid) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        let holder: Option<String> = conn
+            .query_row(
+                "SELECT runner_id FROM task_leases WHERE task_id = ?",
+                duckdb::params![task_id.to_string()],
+                |row| row.get(0),
+            )
+            .ok();
+
+        match holder {
+            Some(holder) if holder == runner_id.to_string() => Ok(()),
+            Some(_) => Err(anyhow::anyhow!(
+                "Task {} is leased to another runner",
+                task_id
+            )),
+            None => Err(anyhow::anyhow!("Task {} has no active lease", task_id)),
+        }
+    }
+
+    fn release_lease(&self, task_id: Uuid) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "DELETE FROM task_leases WHERE task_id = ?",
+            duckdb::params![task_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// List runners with their derived liveness.
+    pub fn list_runners(&self) -> Result<Vec<Runner>> {
+        let conn = self.db.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, status, last_heartbeat, registered_at FROM runners ORDER BY registered_at ASC",
+        )?;
+
+        let cutoff = Utc::now() - ChronoDuration::seconds(self.config.heartbeat_timeout_seconds);
+        let rows = stmt.query_map([], |row| {
+            let id: String = row.get(0)?;
+            let name: String = row.get(1)?;
+            let last_heartbeat: DateTime<Utc> = row.get(3)?;
+            let registered_at: DateTime<Utc> = row.get(4)?;
+            Ok((id, name, last_heartbeat, registered_at))
+        })?;
+
+        let mut runners = Vec::new();
+        for row in rows {
+            let (id, name, last_heartbeat, registered_at) = row?;
+            let status = if last_heartbeat >= cutoff {
+                RunnerStatus::Online
+            } else {
+                RunnerStatus::Offline
+            };
+            runners.push(Runner {
+                id: Uuid::parse_str(&id)?,
+                name,
+                status,
+                last_heartbeat,
+                registered_at,
+            });
+        }
+        Ok(runners)
+    }
+
+    /// Reclaim tasks whose lease has expired (the runner stopped heartbeating),
+    /// returning them to the queue for another runner to claim.
+    pub fn reclaim_expired(&self) -> Result<usize> {
+        let expired = {
+            let conn = self.db.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT task_id, runner_id FROM task_leases WHERE expires_at < ?",
+            )?;
+            let rows = stmt.query_map(duckdb::params![Utc::now()], |row| {
+                let task_id: String = row.get(0)?;
+                let runner_id: String = row.get(1)?;
+                Ok((task_id, runner_id))
+            })?;
+            let mut out = Vec::new();
+            for row in rows {
+                out.push(row?);
+            }
+            out
+        };
+
+        let count = expired.len();
+        for (task_id_str, runner_id) in expired {
+            let task_id = Uuid::parse_str(&task_id_str)?;
+            warn!(
+                "Lease for task {} (runner {}) expired; returning to queue",
+                task_id, runner_id
+            );
+            self.db.update_task_status(task_id, TaskStatus::Pending)?;
+            self.db.add_task_log(
+                task_id,
+                "WARN",
+                &format!("Lease reclaimed from runner {} after heartbeat timeout", runner_id),
+                None,
+            )?;
+            self.release_lease(task_id)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Start the background reaper that reclaims expired leases and marks silent
+    /// runners offline.
+    pub async fn start_reaper(&self) -> Result<()> {
+        info!("Starting runner lease reaper");
+        let coordinator = Arc::new(Self::new(Arc::clone(&self.db), self.config.clone()));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(15)).await;
+                if let Err(e) = coordinator.reclaim_expired() {
+                    warn!("Error reclaiming expired leases: {}", e);
+                }
+                if let Err(e) = coordinator.mark_silent_runners_offline() {
+                    warn!("Error marking silent runners offline: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn mark_silent_runners_offline(&self) -> Result<()> {
+        let conn = self.db.get_conn()?;
+        let cutoff = Utc::now() - ChronoDuration::seconds(self.config.heartbeat_timeout_seconds);
+        conn.execute(
+            "UPDATE runners SET status = 'offline' WHERE last_heartbeat < ?",
+            duckdb::params![cutoff],
+        )?;
+        Ok(())
+    }
+}
+
+impl Runner {
+    /// Convenience used by tests and callers that just registered a runner.
+    pub fn is_online(&self) -> bool {
+        self.status == RunnerStatus::Online
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinator() -> RunnerCoordinator {
+        let db = Arc::new(Database::in_memory().unwrap());
+        RunnerCoordinator::with_defaults(db)
+    }
+
+    #[test]
+    fn test_register_and_list() {
+        let coord = coordinator();
+        let runner = coord.register("worker-1").unwrap();
+        assert!(runner.is_online());
+
+        let runners = coord.list_runners().unwrap();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].name, "worker-1");
+    }
+
+    #[test]
+    fn test_claim_leases_and_result_releases() {
+        let coord = coordinator();
+        let runner = coord.register("worker-1").unwrap();
+
+        let task = AsyncTask::new("t".to_string(), "a".to_string(), "do".to_string());
+        coord.db.insert_task(&task).unwrap();
+
+        let claimed = coord.claim_task(runner.id).unwrap().unwrap();
+        assert_eq!(claimed.id, task.id);
+        // A second runner cannot claim the same leased task.
+        let other = coord.register("worker-2").unwrap();
+        assert!(coord.claim_task(other.id).unwrap().is_none());
+
+        coord
+            .submit_result(task.id, runner.id, true, Some("done".to_string()), None)
+            .unwrap();
+        let stored = coord.db.get_task(task.id).unwrap().unwrap();
+        assert_eq!(stored.status, TaskStatus::Completed);
+    }
+}
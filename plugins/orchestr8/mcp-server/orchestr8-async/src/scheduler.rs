@@ -0,0 +1,308 @@
+use crate::db::{AsyncTask, Database, ScheduleEntry, TaskPriority};
+use crate::queue::TaskQueue;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Fallback for how long a `running` task may go without completing before
+/// the scheduler tick reclaims it, for tasks that didn't set their own
+/// `timeout_seconds`. See [`Database::reclaim_stale_running`].
+const DEFAULT_STALE_RUNNING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Serializable task template stored on a schedule and cloned into a fresh
+/// [`AsyncTask`] each time the schedule fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    pub name: String,
+    pub agent_name: String,
+    pub agent_instructions: String,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub timeout_seconds: Option<i32>,
+    #[serde(default)]
+    pub metadata: Option<String>,
+}
+
+impl TaskTemplate {
+    /// Materialize a new task instance from this template.
+    pub fn instantiate(&self) -> AsyncTask {
+        let mut task = AsyncTask::new(
+            self.name.clone(),
+            self.agent_name.clone(),
+            self.agent_instructions.clone(),
+        );
+        if let Some(priority) = self.priority {
+            task.priority = priority;
+        }
+        task.webhook_url = self.webhook_url.clone();
+        task.timeout_seconds = self.timeout_seconds;
+        task.metadata = self.metadata.clone();
+        task
+    }
+}
+
+/// Scheduler for recurring and interval-based tasks. Owned by `AsyncSystem`
+/// alongside the queue and executor.
+pub struct Scheduler {
+    db: Arc<Database>,
+    queue: Arc<TaskQueue>,
+}
+
+impl Scheduler {
+    pub fn new(db: Arc<Database>, queue: Arc<TaskQueue>) -> Self {
+        Self { db, queue }
+    }
+
+    /// Register a new schedule. Exactly one of `cron` or `interval_seconds`
+    /// determines the cadence; `cron` takes precedence when both are supplied.
+    pub fn create_schedule(
+        &self,
+        template: TaskTemplate,
+        cron: Option<String>,
+        interval_seconds: Option<i64>,
+        end_at: Option<DateTime<Utc>>,
+    ) -> Result<Uuid> {
+        if cron.is_none() && interval_seconds.is_none() {
+            anyhow::bail!("A schedule requires either a cron expression or interval_seconds");
+        }
+
+        let now = Utc::now();
+        let next_fire_at = compute_next_fire(cron.as_deref(), interval_seconds, now)
+            .context("Could not compute an initial fire time for the schedule")?;
+
+        let entry = ScheduleEntry {
+            id: Uuid::new_v4(),
+            name: template.name.clone(),
+            cron,
+            interval_seconds,
+            end_at,
+            next_fire_at,
+            template: serde_json::to_string(&template)?,
+            active: true,
+            created_at: now,
+        };
+
+        self.db.insert_schedule(&entry)?;
+        Ok(entry.id)
+    }
+
+    /// List all schedules (active and inactive).
+    pub fn list(&self) -> Result<Vec<ScheduleEntry>> {
+        self.db.list_schedules(false)
+    }
+
+    /// Cancel (deactivate) a schedule.
+    pub fn cancel(&self, id: Uuid) -> Result<()> {
+        self.db.deactivate_schedule(id)
+    }
+
+    /// Start the background tick loop.
+    pub async fn start(&self) -> Result<()> {
+        info!("Starting scheduler tick loop");
+
+        let db = Arc::clone(&self.db);
+        let queue = Arc::clone(&self.queue);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+                if let Err(e) = Self::tick(&db, &queue).await {
+                    error!("Scheduler tick error: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn tick(db: &Database, queue: &TaskQueue) -> Result<()> {
+        let now = Utc::now();
+        let due = db.get_due_schedules(now, 100)?;
+
+        for entry in due {
+            // Respect the optional end boundary.
+            if let Some(end_at) = entry.end_at {
+                if now >= end_at {
+                    db.deactivate_schedule(entry.id)?;
+                    debug!("Schedule {} reached end_at, deactivated", entry.id);
+                    continue;
+                }
+            }
+
+            // Clone the template into a concrete task and enqueue it.
+            match serde_json::from_str::<TaskTemplate>(&entry.template) {
+                Ok(template) => {
+                    let task = template.instantiate();
+                    db.insert_task(&task)?;
+                    queue.submit_task(task.id)?;
+                    debug!("Schedule {} fired task {}", entry.id, task.id);
+                }
+                Err(e) => {
+                    warn!("Schedule {} has an invalid template: {}", entry.id, e);
+                }
+            }
+
+            // Advance to the next fire time. Always step from the *scheduled*
+            // fire time, never from `now` (dispatch time) — otherwise a tick
+            // that runs late permanently shifts the cadence forward and drift
+            // accumulates run over run. If a tick was missed by more than one
+            // period, fast-forward through the missed occurrences without
+            // dispatching them, to avoid a thundering-herd catch-up burst.
+            let mut next = entry.next_fire_at;
+            let mut skipped = 0u32;
+            loop {
+                match compute_next_fire(entry.cron.as_deref(), entry.interval_seconds, next) {
+                    Some(candidate) => {
+                        next = candidate;
+                        if next > now {
+                            break;
+                        }
+                        skipped += 1;
+                    }
+                    None => break,
+                }
+            }
+            if skipped > 0 {
+                debug!(
+                    "Schedule {} fast-forwarded past {} missed occurrence(s)",
+                    entry.id, skipped
+                );
+            }
+
+            if entry.end_at.map(|e| next < e).unwrap_or(true) {
+                db.update_schedule_next_fire(entry.id, next)?;
+            } else {
+                db.deactivate_schedule(entry.id)?;
+            }
+        }
+
+        // Age-based retention sweep, if the queue is configured with a TTL.
+        match queue.sweep_expired_tasks() {
+            Ok(n) if n > 0 => debug!("Retention swept {} expired task(s)", n),
+            Ok(_) => {}
+            Err(e) => warn!("Retention sweep error: {}", e),
+        }
+
+        // Reclaim tasks abandoned by a crashed or hung in-process worker.
+        match db.reclaim_stale_running(DEFAULT_STALE_RUNNING_TIMEOUT) {
+            Ok(ids) if !ids.is_empty() => {
+                warn!("Reclaimed {} stale running task(s): {:?}", ids.len(), ids)
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Stale-running reclaim error: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+/// Compute the next fire time after `from`, preferring the cron spec when set.
+pub(crate) fn compute_next_fire(
+    cron: Option<&str>,
+    interval_seconds: Option<i64>,
+    from: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if let Some(cron) = cron {
+        if let Ok(schedule) = cron::Schedule::from_str(cron) {
+            return schedule.after(&from).next();
+        }
+    }
+    interval_seconds.map(|secs| from + Duration::seconds(secs.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_next_fire() {
+        let now = Utc::now();
+        let next = compute_next_fire(None, Some(60), now).unwrap();
+        assert_eq!((next - now).num_seconds(), 60);
+    }
+
+    #[tokio::test]
+    async fn test_create_and_list_schedule() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let queue = Arc::new(TaskQueue::new(db.clone(), 1));
+        let scheduler = Scheduler::new(db, queue);
+
+        let template = TaskTemplate {
+            name: "poll".to_string(),
+            agent_name: "agent".to_string(),
+            agent_instructions: "check".to_string(),
+            priority: None,
+            webhook_url: None,
+            timeout_seconds: None,
+            metadata: None,
+        };
+
+        let id = scheduler
+            .create_schedule(template, None, Some(30), None)
+            .unwrap();
+
+        let schedules = scheduler.list().unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].id, id);
+
+        scheduler.cancel(id).unwrap();
+        assert!(!scheduler.list().unwrap()[0].active);
+    }
+
+    #[tokio::test]
+    async fn test_tick_advances_from_scheduled_time_not_dispatch_time() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let queue = Arc::new(TaskQueue::new(db.clone(), 1));
+        let scheduler = Scheduler::new(db.clone(), queue.clone());
+
+        let template = TaskTemplate {
+            name: "poll".to_string(),
+            agent_name: "agent".to_string(),
+            agent_instructions: "check".to_string(),
+            priority: None,
+            webhook_url: None,
+            timeout_seconds: None,
+            metadata: None,
+        };
+        let id = scheduler
+            .create_schedule(template, None, Some(30), None)
+            .unwrap();
+
+        // Simulate a scheduler that has fallen behind by multiple periods.
+        let now = Utc::now();
+        let overdue_since = now - Duration::seconds(95);
+        db.update_schedule_next_fire(id, overdue_since).unwrap();
+
+        Scheduler::tick(&db, &queue).await.unwrap();
+
+        let schedule = scheduler
+            .list()
+            .unwrap()
+            .into_iter()
+            .find(|s| s.id == id)
+            .unwrap();
+        // Advanced from the missed `next_fire_at`, in fixed 30s steps, to the
+        // first occurrence still in the future - not from `now` directly.
+        let elapsed_steps = (schedule.next_fire_at - overdue_since).num_seconds() / 30;
+        assert_eq!(
+            overdue_since + Duration::seconds(elapsed_steps * 30),
+            schedule.next_fire_at
+        );
+        assert!(schedule.next_fire_at > now);
+
+        // Only one task was dispatched for the whole missed window, not one
+        // per skipped occurrence.
+        let page = db
+            .query_tasks(&crate::db::TaskQueryFilter::default())
+            .unwrap();
+        assert_eq!(page.total, 1);
+    }
+}
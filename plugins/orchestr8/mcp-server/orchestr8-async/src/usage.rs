@@ -0,0 +1,378 @@
+use crate::db::Database;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Billing rates used to turn raw usage counters into an estimated cost. Both
+/// default to zero so metering is observational until an operator sets a price.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct UsageRates {
+    /// Cost charged per CPU/wall second of execution.
+    pub cost_per_cpu_second: f64,
+    /// Cost charged per 1000 agent/tool invocations.
+    pub cost_per_1k_invocations: f64,
+}
+
+impl Default for UsageRates {
+    fn default() -> Self {
+        Self {
+            cost_per_cpu_second: 0.0,
+            cost_per_1k_invocations: 0.0,
+        }
+    }
+}
+
+impl UsageRates {
+    /// Read rates from `ORCHESTR8_COST_PER_CPU_SECOND` and
+    /// `ORCHESTR8_COST_PER_1K_INVOCATIONS`, falling back to zero.
+    pub fn from_env() -> Self {
+        let parse = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        Self {
+            cost_per_cpu_second: parse("ORCHESTR8_COST_PER_CPU_SECOND"),
+            cost_per_1k_invocations: parse("ORCHESTR8_COST_PER_1K_INVOCATIONS"),
+        }
+    }
+
+    /// Estimated cost for the given raw counters.
+    pub fn estimate(&self, cpu_seconds: f64, invocations: i64) -> f64 {
+        cpu_seconds * self.cost_per_cpu_second
+            + (invocations as f64 / 1000.0) * self.cost_per_1k_invocations
+    }
+}
+
+/// Scope a usage query is aggregated over.
+#[derive(Debug, Clone, Default)]
+pub struct UsageQuery {
+    pub workflow_id: Option<Uuid>,
+    pub agent_name: Option<String>,
+    /// Only count tasks that completed at or after this instant. When set, the
+    /// totals are computed live from the `tasks` table rather than the rollups.
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Accumulated execution totals for a scope, plus the derived cost.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct UsageTotals {
+    pub cpu_seconds: f64,
+    pub wall_seconds: f64,
+    pub invocations: i64,
+    pub task_count: i64,
+    pub estimated_cost: f64,
+}
+
+/// Usage report for a single task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskUsageReport {
+    pub task_id: Uuid,
+    pub workflow_id: Option<Uuid>,
+    pub agent_name: String,
+    pub cpu_seconds: f64,
+    pub wall_seconds: f64,
+    pub invocations: i64,
+    pub estimated_cost: f64,
+}
+
+/// Per-task resource accounting: records execution counters against tasks and
+/// periodically rolls them into per-workflow and per-agent totals. The rollup
+/// is idempotent and checkpoints the last-processed `completed_at`, so a restart
+/// resumes without double-counting.
+pub struct UsageMeter {
+    db: Arc<Database>,
+    rates: UsageRates,
+}
+
+impl UsageMeter {
+    pub fn new(db: Arc<Database>, rates: UsageRates) -> Self {
+        Self { db, rates }
+    }
+
+    pub fn with_defaults(db: Arc<Database>) -> Self {
+        Self::new(db, UsageRates::from_env())
+    }
+
+    pub fn rates(&self) -> UsageRates {
+        self.rates
+    }
+
+    /// Add execution counters to a task as it runs.
+    pub fn record(&self, task_id: Uuid, cpu_seconds: f64, invocations: i64) -> Result<()> {
+        self.db.record_task_usage(task_id, cpu_seconds, invocations)
+    }
+
+    /// Usage for a single task, with wall time derived from its timestamps.
+    pub fn task_usage(&self, task_id: Uuid) -> Result<Option<TaskUsageReport>> {
+        let Some(task) = self.db.get_task(task_id)? else {
+            return Ok(None);
+        };
+        let wall_seconds = wall_seconds(task.started_at, task.completed_at);
+        Ok(Some(TaskUsageReport {
+            task_id: task.id,
+            workflow_id: task.workflow_id,
+            agent_name: task.agent_name,
+            cpu_seconds: task.cpu_seconds,
+            wall_seconds,
+            invocations: task.invocations,
+            estimated_cost: self.rates.estimate(task.cpu_seconds, task.invocations),
+        }))
+    }
+
+    /// Totals for a scope. With a `since` filter the totals are computed live
+    /// from the `tasks` table; otherwise the pre-aggregated rollups are used.
+    pub fn query(&self, query: &UsageQuery) -> Result<UsageTotals> {
+        let mut totals = if query.since.is_some() {
+            self.query_live(query)?
+        } else if let Some(workflow_id) = query.workflow_id {
+            self.rollup_for("workflow", &workflow_id.to_string())?
+        } else if let Some(agent) = &query.agent_name {
+            self.rollup_for("agent", agent)?
+        } else {
+            self.query_live(query)?
+        };
+        totals.estimated_cost = self.rates.estimate(totals.cpu_seconds, totals.invocations);
+        Ok(totals)
+    }
+
+    fn query_live(&self, query: &UsageQuery) -> Result<UsageTotals> {
+        let conn = self.db.get_conn()?;
+        let mut sql = String::from(
+            "SELECT COALESCE(SUM(cpu_seconds), 0.0),
+                    COALESCE(SUM(CASE WHEN started_at IS NOT NULL AND completed_at IS NOT NULL
+                                      THEN epoch(completed_at) - epoch(started_at) ELSE 0 END), 0.0),
+                    COALESCE(SUM(invocations), 0),
+                    COUNT(*)
+             FROM tasks WHERE 1 = 1",
+        );
+        let mut params: Vec<Box<dyn duckdb::ToSql>> = Vec::new();
+        if let Some(workflow_id) = query.workflow_id {
+            sql.push_str(" AND workflow_id = ?");
+            params.push(Box::new(workflow_id.to_string()));
+        }
+        if let Some(agent) = &query.agent_name {
+            sql.push_str(" AND agent_name = ?");
+            params.push(Box::new(agent.clone()));
+        }
+        if let Some(since) = query.since {
+            sql.push_str(" AND completed_at >= ?");
+            params.push(Box::new(since));
+        }
+
+        let param_refs: Vec<&dyn duckdb::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let row = conn.query_row(&sql, param_refs.as_slice(), |row| {
+            Ok(UsageTotals {
+                cpu_seconds: row.get(0)?,
+                wall_seconds: row.get(1)?,
+                invocations: row.get(2)?,
+                task_count: row.get(3)?,
+                estimated_cost: 0.0,
+            })
+        })?;
+        Ok(row)
+    }
+
+    fn rollup_for(&self, scope_kind: &str, scope_key: &str) -> Result<UsageTotals> {
+        let conn = self.db.get_conn()?;
+        let totals = conn
+            .query_row(
+                "SELECT cpu_seconds, wall_seconds, invocations, task_count
+                 FROM usage_rollup WHERE scope_kind = ? AND scope_key = ?",
+                duckdb::params![scope_kind, scope_key],
+                |row| {
+                    Ok(UsageTotals {
+                        cpu_seconds: row.get(0)?,
+                        wall_seconds: row.get(1)?,
+                        invocations: row.get(2)?,
+                        task_count: row.get(3)?,
+                        estimated_cost: 0.0,
+                    })
+                },
+            )
+            .unwrap_or_default();
+        Ok(totals)
+    }
+
+    /// Fold every task that completed since the checkpoint into the per-workflow
+    /// and per-agent rollups, then advance the checkpoint. Returns the number of
+    /// tasks processed. Safe to call repeatedly: only tasks past the checkpoint
+    /// are folded in.
+    pub fn aggregate_once(&self) -> Result<usize> {
+        let conn = self.db.get_conn()?;
+        let checkpoint: DateTime<Utc> = conn
+            .query_row(
+                "SELECT last_processed FROM usage_checkpoint WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or_else(|_| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
+
+        let mut stmt = conn.prepare(
+            "SELECT workflow_id, agent_name, cpu_seconds, invocations,
+                    CASE WHEN started_at IS NOT NULL AND completed_at IS NOT NULL
+                         THEN epoch(completed_at) - epoch(started_at) ELSE 0 END,
+                    completed_at
+             FROM tasks
+             WHERE completed_at IS NOT NULL AND completed_at > ?
+             ORDER BY completed_at ASC",
+        )?;
+        let rows = stmt.query_map(duckdb::params![checkpoint], |row| {
+            let workflow_id: Option<String> = row.get(0)?;
+            let agent_name: String = row.get(1)?;
+            let cpu_seconds: f64 = row.get(2)?;
+            let invocations: i64 = row.get(3)?;
+            let wall_seconds: f64 = row.get(4)?;
+            let completed_at: DateTime<Utc> = row.get(5)?;
+            Ok((
+                workflow_id,
+                agent_name,
+                cpu_seconds,
+                invocations,
+                wall_seconds,
+                completed_at,
+            ))
+        })?;
+
+        let mut high_water = checkpoint;
+        let mut processed = 0usize;
+        for row in rows {
+            let (workflow_id, agent_name, cpu_seconds, invocations, wall_seconds, completed_at) =
+                row?;
+            if let Some(workflow_id) = workflow_id {
+                self.add_rollup(&conn, "workflow", &workflow_id, cpu_seconds, wall_seconds, invocations)?;
+            }
+            self.add_rollup(&conn, "agent", &agent_name, cpu_seconds, wall_seconds, invocations)?;
+            high_water = high_water.max(completed_at);
+            processed += 1;
+        }
+
+        if processed > 0 {
+            conn.execute(
+                "INSERT INTO usage_checkpoint (id, last_processed) VALUES (1, ?)
+                 ON CONFLICT (id) DO UPDATE SET last_processed = excluded.last_processed",
+                duckdb::params![high_water],
+            )?;
+            debug!("Aggregated usage for {} task(s)", processed);
+        }
+        Ok(processed)
+    }
+
+    fn add_rollup(
+        &self,
+        conn: &duckdb::Connection,
+        scope_kind: &str,
+        scope_key: &str,
+        cpu_seconds: f64,
+        wall_seconds: f64,
+        invocations: i64,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO usage_rollup
+                (scope_kind, scope_key, cpu_seconds, wall_seconds, invocations, task_count, updated_at)
+             VALUES (?, ?, ?, ?, ?, 1, ?)
+             ON CONFLICT (scope_kind, scope_key) DO UPDATE SET
+                cpu_seconds = usage_rollup.cpu_seconds + excluded.cpu_seconds,
+                wall_seconds = usage_rollup.wall_seconds + excluded.wall_seconds,
+                invocations = usage_rollup.invocations + excluded.invocations,
+                task_count = usage_rollup.task_count + 1,
+                updated_at = excluded.updated_at",
+            duckdb::params![
+                scope_kind,
+                scope_key,
+                cpu_seconds,
+                wall_seconds,
+                invocations,
+                Utc::now()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Spawn the background aggregation loop, rolling usage up on a fixed cadence.
+    pub async fn start_aggregator(&self) -> Result<()> {
+        info!("Starting usage aggregator");
+        let meter = Arc::new(Self::new(Arc::clone(&self.db), self.rates));
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                if let Err(e) = meter.aggregate_once() {
+                    warn!("Error aggregating usage: {}", e);
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Wall-clock seconds between two optional timestamps, clamped at zero.
+fn wall_seconds(started: Option<DateTime<Utc>>, completed: Option<DateTime<Utc>>) -> f64 {
+    match (started, completed) {
+        (Some(start), Some(end)) => (end - start).num_milliseconds().max(0) as f64 / 1000.0,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{AsyncTask, TaskStatus};
+
+    fn meter() -> UsageMeter {
+        let db = Arc::new(Database::in_memory().unwrap());
+        UsageMeter::new(
+            db,
+            UsageRates {
+                cost_per_cpu_second: 0.01,
+                cost_per_1k_invocations: 2.0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_and_task_usage() {
+        let meter = meter();
+        let task = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do".to_string());
+        meter.db.insert_task(&task).unwrap();
+
+        meter.record(task.id, 4.0, 500).unwrap();
+        meter.record(task.id, 1.0, 500).unwrap();
+
+        let report = meter.task_usage(task.id).unwrap().unwrap();
+        assert_eq!(report.cpu_seconds, 5.0);
+        assert_eq!(report.invocations, 1000);
+        // 5 cpu-sec * 0.01 + 1k invocations * 2.0 = 0.05 + 2.0.
+        assert!((report.estimated_cost - 2.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregate_is_idempotent() {
+        let meter = meter();
+        let workflow_id = Uuid::new_v4();
+        let mut task = AsyncTask::new("t".to_string(), "agent-a".to_string(), "do".to_string())
+            .with_workflow(workflow_id);
+        task.cpu_seconds = 3.0;
+        task.invocations = 100;
+        meter.db.insert_task(&task).unwrap();
+        meter.db.update_task_status(task.id, TaskStatus::Completed).unwrap();
+
+        assert_eq!(meter.aggregate_once().unwrap(), 1);
+        // A second pass finds nothing new: totals don't double.
+        assert_eq!(meter.aggregate_once().unwrap(), 0);
+
+        let totals = meter
+            .query(&UsageQuery {
+                workflow_id: Some(workflow_id),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(totals.cpu_seconds, 3.0);
+        assert_eq!(totals.invocations, 100);
+        assert_eq!(totals.task_count, 1);
+    }
+}
@@ -1,11 +1,14 @@
+use crate::artifacts::{ArtifactReference, ArtifactStore};
 use crate::db::{AsyncTask, Database};
 use anyhow::{Context, Result};
 use chrono::Utc;
+use hmac::{Hmac, Mac};
 use reqwest::Client;
+use sha2::Sha256;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -19,6 +22,10 @@ pub struct WebhookPayload {
     pub error: Option<String>,
     pub completed_at: Option<chrono::DateTime<Utc>>,
     pub metadata: Option<serde_json::Value>,
+    /// References to out-of-band artifacts produced by the task, in place of
+    /// inlining large or binary outputs in `result`.
+    #[serde(default)]
+    pub artifacts: Vec<ArtifactReference>,
 }
 
 impl WebhookPayload {
@@ -34,8 +41,15 @@ impl WebhookPayload {
                 .metadata
                 .as_ref()
                 .and_then(|m| serde_json::from_str(m).ok()),
+            artifacts: Vec::new(),
         }
     }
+
+    /// Attach artifact references to the payload, replacing any already set.
+    pub fn with_artifacts(mut self, artifacts: Vec<ArtifactReference>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
 }
 
 /// Webhook delivery configuration
@@ -44,6 +58,14 @@ pub struct WebhookConfig {
     pub max_retries: u32,
     pub retry_delay_seconds: u64,
     pub timeout_seconds: u64,
+    /// Maximum deliveries attempted concurrently by the worker pool.
+    pub max_concurrency: usize,
+    /// Optional shared secret used to HMAC-sign outgoing payloads. When unset,
+    /// deliveries are sent unsigned.
+    pub signing_secret: Option<String>,
+    /// Identifier of the active signing secret, recorded on each attempt so
+    /// secret rotation is auditable.
+    pub secret_version: String,
 }
 
 impl Default for WebhookConfig {
@@ -52,8 +74,72 @@ impl Default for WebhookConfig {
             max_retries: 3,
             retry_delay_seconds: 5,
             timeout_seconds: 30,
+            max_concurrency: 16,
+            signing_secret: None,
+            secret_version: "v1".to_string(),
+        }
+    }
+}
+
+/// Classification of a single delivery attempt. Mirrors how a real webhook
+/// worker reasons about outcomes: a clean success, a server that replied but
+/// rejected the request, or a transport failure before any reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryKind {
+    /// 2xx response.
+    Success,
+    /// 4xx response: the request is malformed/unauthorized and will never
+    /// succeed, so it is a permanent failure (dead-letter, no retries).
+    ClientError,
+    /// 5xx (or any other non-2xx, non-4xx) response: the server is
+    /// transiently unhealthy; worth retrying.
+    ServerError,
+    /// No HTTP reply at all (timeout, DNS, connection refused); worth retrying.
+    TransportError,
+}
+
+impl DeliveryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryKind::Success => "success",
+            DeliveryKind::ClientError => "client_error",
+            DeliveryKind::ServerError => "server_error",
+            DeliveryKind::TransportError => "transport_error",
         }
     }
+
+    /// Whether an attempt of this kind should be retried.
+    fn is_retryable(&self) -> bool {
+        matches!(self, DeliveryKind::ServerError | DeliveryKind::TransportError)
+    }
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Compute the hex-encoded HMAC-SHA256 over `{timestamp}.{body}`. The timestamp
+/// is part of the signed material so a captured request cannot be replayed
+/// outside the receiver's tolerance window.
+fn sign_body(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    let bytes = mac.finalize().into_bytes();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Header carrying the hex-encoded HMAC-SHA256 signature of an outgoing body.
+const SIGNATURE_HEADER: &str = "X-Orchestr8-Signature";
+/// Header carrying the Unix timestamp that is also mixed into the signature.
+const TIMESTAMP_HEADER: &str = "X-Orchestr8-Timestamp";
+
+/// Outcome of a single delivery attempt.
+#[derive(Debug)]
+struct DeliveryOutcome {
+    kind: DeliveryKind,
+    status_code: Option<u16>,
+    body: String,
+    /// Which signing secret version signed this attempt, if any.
+    secret_version: Option<String>,
 }
 
 /// Webhook delivery manager
@@ -61,6 +147,8 @@ pub struct WebhookManager {
     db: Arc<Database>,
     client: Client,
     config: WebhookConfig,
+    /// When set, each payload is enriched with the task's artifact references.
+    artifacts: Option<Arc<ArtifactStore>>,
 }
 
 impl WebhookManager {
@@ -70,148 +158,244 @@ impl WebhookManager {
             .build()
             .context("Failed to create HTTP client")?;
 
-        Ok(Self { db, client, config })
+        Ok(Self {
+            db,
+            client,
+            config,
+            artifacts: None,
+        })
     }
 
     pub fn with_defaults(db: Arc<Database>) -> Result<Self> {
         Self::new(db, WebhookConfig::default())
     }
 
-    /// Deliver webhook for a completed task
-    pub async fn deliver_webhook(&self, task_id: Uuid) -> Result<()> {
-        let task = self
-            .db
-            .get_task(task_id)?
-            .context("Task not found")?;
-
-        if let Some(webhook_url) = &task.webhook_url {
-            if task.is_complete() {
-                info!("Delivering webhook for task {} to {}", task_id, webhook_url);
-
-                let payload = WebhookPayload::from_task(&task);
-
-                match self.send_webhook(webhook_url, &payload).await {
-                    Ok(response) => {
-                        info!(
-                            "Webhook delivered successfully for task {}: status {}",
-                            task_id, response.status
-                        );
-                        self.log_delivery(task_id, webhook_url, &payload, Some(response.status), Some(&response.body))
-                            .await?;
-                    }
-                    Err(e) => {
-                        error!("Failed to deliver webhook for task {}: {}", task_id, e);
-                        self.log_delivery(task_id, webhook_url, &payload, None, Some(&e.to_string()))
-                            .await?;
-
-                        // Retry with exponential backoff
-                        self.retry_webhook(task_id, webhook_url, &payload).await?;
-                    }
-                }
-            }
-        }
-
-        Ok(())
+    /// Attach an artifact store so delivered payloads reference a task's
+    /// out-of-band outputs instead of relying solely on the inline result.
+    pub fn with_artifact_store(mut self, store: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(store);
+        self
     }
 
-    /// Send webhook with retries
-    async fn send_webhook(&self, url: &str, payload: &WebhookPayload) -> Result<WebhookResponse> {
-        debug!("Sending webhook to {}", url);
+    /// Enqueue a delivery for a completed task if one does not already exist.
+    /// The row carries its own backoff schedule, so enqueueing is all that is
+    /// needed to hand the work to the worker pool.
+    pub async fn enqueue_delivery(&self, task_id: Uuid) -> Result<()> {
+        let task = self.db.get_task(task_id)?.context("Task not found")?;
+
+        let Some(webhook_url) = &task.webhook_url else {
+            return Ok(());
+        };
+        if !task.is_complete() {
+            return Ok(());
+        }
 
-        let response = self
-            .client
-            .post(url)
-            .json(payload)
-            .send()
-            .await
-            .context("Failed to send webhook")?;
-
-        let status = response.status().as_u16();
-        let body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read response".to_string());
-
-        if status >= 200 && status < 300 {
-            Ok(WebhookResponse { status, body })
-        } else {
-            Err(anyhow::anyhow!(
-                "Webhook delivery failed with status {}: {}",
-                status,
-                body
-            ))
+        let conn = self.db.get_conn()?;
+        let existing: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM webhook_deliveries WHERE task_id = ?",
+            duckdb::params![task_id.to_string()],
+            |row| row.get(0),
+        )?;
+        if existing > 0 {
+            return Ok(());
+        }
+
+        let mut payload = WebhookPayload::from_task(&task);
+        if let Some(store) = &self.artifacts {
+            payload = payload.with_artifacts(store.list_for_task(task_id)?);
         }
+        conn.execute(
+            "INSERT INTO webhook_deliveries
+                (id, task_id, webhook_url, payload, attempt_count, status, next_attempt_at)
+             VALUES (nextval('webhook_deliveries_seq'), ?, ?, ?, 0, 'pending', ?)",
+            duckdb::params![
+                task_id.to_string(),
+                webhook_url,
+                serde_json::to_string(&payload)?,
+                Utc::now(),
+            ],
+        )?;
+
+        Ok(())
     }
 
-    /// Retry webhook delivery with exponential backoff
-    async fn retry_webhook(
-        &self,
-        task_id: Uuid,
-        url: &str,
-        payload: &WebhookPayload,
-    ) -> Result<()> {
-        for retry in 1..=self.config.max_retries {
-            let delay = Duration::from_secs(self.config.retry_delay_seconds * retry as u64);
-            warn!(
-                "Retrying webhook for task {} (attempt {}/{}) after {:?}",
-                task_id, retry, self.config.max_retries, delay
-            );
-
-            sleep(delay).await;
-
-            match self.send_webhook(url, payload).await {
-                Ok(response) => {
-                    info!(
-                        "Webhook retry succeeded for task {} on attempt {}",
-                        task_id, retry
-                    );
-                    self.log_delivery(task_id, url, payload, Some(response.status), Some(&response.body))
-                        .await?;
-                    return Ok(());
-                }
-                Err(e) => {
-                    error!(
-                        "Webhook retry {} failed for task {}: {}",
-                        retry, task_id, e
+    /// Attempt a single queued delivery and reschedule or finalize it based on
+    /// the classified outcome. Never sleeps: a retryable failure persists a
+    /// future `next_attempt_at` and returns.
+    async fn attempt_delivery(&self, row: &PendingDelivery) -> Result<()> {
+        let payload: WebhookPayload = serde_json::from_str(&row.payload)?;
+        let outcome = self.send_webhook(&row.webhook_url, &payload).await;
+        let attempt_count = row.attempt_count + 1;
+        let conn = self.db.get_conn()?;
+
+        match outcome.kind {
+            DeliveryKind::Success => {
+                info!("Webhook {} delivered for task {}", row.id, row.task_id);
+                conn.execute(
+                    "UPDATE webhook_deliveries
+                     SET status = 'delivered', kind = ?, status_code = ?, response = ?,
+                         attempt_count = ?, attempted_at = ?, delivered_at = ?, secret_version = ?
+                     WHERE id = ?",
+                    duckdb::params![
+                        outcome.kind.as_str(),
+                        outcome.status_code.map(|s| s as i32),
+                        outcome.body,
+                        attempt_count,
+                        Utc::now(),
+                        Utc::now(),
+                        outcome.secret_version,
+                        row.id,
+                    ],
+                )?;
+            }
+            DeliveryKind::ClientError => {
+                error!("Webhook {} dead-lettered (4xx) for task {}", row.id, row.task_id);
+                conn.execute(
+                    "UPDATE webhook_deliveries
+                     SET status = 'dead_letter', kind = ?, status_code = ?, response = ?,
+                         attempt_count = ?, attempted_at = ?, secret_version = ?
+                     WHERE id = ?",
+                    duckdb::params![
+                        outcome.kind.as_str(),
+                        outcome.status_code.map(|s| s as i32),
+                        outcome.body,
+                        attempt_count,
+                        Utc::now(),
+                        outcome.secret_version,
+                        row.id,
+                    ],
+                )?;
+            }
+            DeliveryKind::ServerError | DeliveryKind::TransportError => {
+                if attempt_count as u32 > self.config.max_retries {
+                    error!("Webhook {} exhausted retries for task {}", row.id, row.task_id);
+                    conn.execute(
+                        "UPDATE webhook_deliveries
+                         SET status = 'exhausted', kind = ?, status_code = ?, response = ?,
+                             attempt_count = ?, attempted_at = ?, last_error = ?, secret_version = ?
+                         WHERE id = ?",
+                        duckdb::params![
+                            outcome.kind.as_str(),
+                            outcome.status_code.map(|s| s as i32),
+                            outcome.body,
+                            attempt_count,
+                            Utc::now(),
+                            outcome.body,
+                            outcome.secret_version,
+                            row.id,
+                        ],
+                    )?;
+                } else {
+                    let next = self.next_attempt_at(row.id, attempt_count);
+                    warn!(
+                        "Webhook {} for task {} failed ({}); next attempt at {}",
+                        row.id,
+                        row.task_id,
+                        outcome.kind.as_str(),
+                        next
                     );
-                    self.log_delivery(task_id, url, payload, None, Some(&e.to_string()))
-                        .await?;
+                    conn.execute(
+                        "UPDATE webhook_deliveries
+                         SET status = 'pending', kind = ?, status_code = ?, response = ?,
+                             attempt_count = ?, attempted_at = ?, next_attempt_at = ?, last_error = ?,
+                             secret_version = ?
+                         WHERE id = ?",
+                        duckdb::params![
+                            outcome.kind.as_str(),
+                            outcome.status_code.map(|s| s as i32),
+                            outcome.body,
+                            attempt_count,
+                            Utc::now(),
+                            next,
+                            outcome.body,
+                            outcome.secret_version,
+                            row.id,
+                        ],
+                    )?;
                 }
             }
         }
 
-        Err(anyhow::anyhow!(
-            "Webhook delivery failed after {} retries",
-            self.config.max_retries
-        ))
+        Ok(())
     }
 
-    /// Log webhook delivery attempt
-    async fn log_delivery(
-        &self,
-        task_id: Uuid,
-        url: &str,
-        payload: &WebhookPayload,
-        status_code: Option<u16>,
-        response: Option<&str>,
-    ) -> Result<()> {
-        let conn = self.db.get_conn()?;
+    /// Exponential backoff with deterministic per-delivery jitter (±10%),
+    /// derived from the delivery id so the schedule is stable across restarts.
+    fn next_attempt_at(&self, delivery_id: i64, attempt_count: i32) -> chrono::DateTime<Utc> {
+        let exp = attempt_count.saturating_sub(1).min(16) as u32;
+        let base = self.config.retry_delay_seconds.saturating_mul(2u64.pow(exp));
+        let jitter_span = (base / 10).max(1);
+        let jitter = (delivery_id.unsigned_abs() % (2 * jitter_span + 1)) as i64 - jitter_span as i64;
+        let delay = (base as i64 + jitter).max(1);
+        Utc::now() + chrono::Duration::seconds(delay)
+    }
 
-        conn.execute(
-            "INSERT INTO webhook_deliveries (task_id, webhook_url, payload, status_code, response, attempted_at, delivered_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-            duckdb::params![
-                task_id.to_string(),
-                url,
-                serde_json::to_string(payload)?,
-                status_code.map(|s| s as i32),
-                response,
-                Utc::now(),
-                status_code.map(|_| Utc::now()),
-            ],
-        )?;
+    /// Send a single webhook attempt and classify its outcome. Unlike a plain
+    /// error, a transport failure and a server rejection are distinguished so
+    /// the retry policy can treat them differently.
+    async fn send_webhook(&self, url: &str, payload: &WebhookPayload) -> DeliveryOutcome {
+        debug!("Sending webhook to {}", url);
 
-        Ok(())
+        let body = match serde_json::to_string(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                return DeliveryOutcome {
+                    kind: DeliveryKind::ClientError,
+                    status_code: None,
+                    body: format!("Failed to serialize payload: {}", e),
+                    secret_version: None,
+                }
+            }
+        };
+
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        // Sign the body with a timestamp to let receivers verify authenticity
+        // and reject replays outside their tolerance window.
+        let secret_version = if let Some(secret) = &self.config.signing_secret {
+            let timestamp = Utc::now().timestamp();
+            let signature = sign_body(secret, timestamp, &body);
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, signature);
+            Some(self.config.secret_version.clone())
+        } else {
+            None
+        };
+
+        match request.body(body).send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Failed to read response".to_string());
+
+                let kind = if (200..300).contains(&status) {
+                    DeliveryKind::Success
+                } else if (400..500).contains(&status) {
+                    DeliveryKind::ClientError
+                } else {
+                    DeliveryKind::ServerError
+                };
+
+                DeliveryOutcome {
+                    kind,
+                    status_code: Some(status),
+                    body,
+                    secret_version,
+                }
+            }
+            Err(e) => DeliveryOutcome {
+                kind: DeliveryKind::TransportError,
+                status_code: None,
+                body: e.to_string(),
+                secret_version,
+            },
+        }
     }
 
     /// Get webhook delivery history for a task
@@ -222,7 +406,7 @@ impl WebhookManager {
             "SELECT id, task_id, webhook_url, payload, status_code, response, attempted_at, delivered_at
              FROM webhook_deliveries
              WHERE task_id = ?
-             ORDER BY attempted_at DESC",
+             ORDER BY id DESC",
         )?;
 
         let rows = stmt.query_map(duckdb::params![task_id.to_string()], |row| {
@@ -249,29 +433,25 @@ impl WebhookManager {
         Ok(deliveries)
     }
 
-    /// Start webhook delivery worker
+    /// Start the webhook delivery worker. Each tick it enqueues deliveries for
+    /// newly completed tasks, then drains every due delivery across a bounded
+    /// concurrency pool so a single slow endpoint cannot stall the others.
     pub async fn start_worker(&self) -> Result<()> {
         info!("Starting webhook delivery worker");
 
-        let db = Arc::clone(&self.db);
-        let manager = Arc::new(Self::with_defaults(db)?);
+        let mut manager = Self::new(Arc::clone(&self.db), self.config.clone())?;
+        manager.artifacts = self.artifacts.clone();
+        let manager = Arc::new(manager);
 
         tokio::spawn(async move {
             loop {
-                tokio::time::sleep(Duration::from_secs(10)).await;
-
-                // Find completed tasks with webhooks that haven't been delivered
-                match manager.find_pending_webhooks().await {
-                    Ok(task_ids) => {
-                        for task_id in task_ids {
-                            if let Err(e) = manager.deliver_webhook(task_id).await {
-                                error!("Error delivering webhook for task {}: {}", task_id, e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error finding pending webhooks: {}", e);
-                    }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+
+                if let Err(e) = manager.enqueue_new_deliveries().await {
+                    error!("Error enqueueing webhook deliveries: {}", e);
+                }
+                if let Err(e) = manager.drain_due().await {
+                    error!("Error draining webhook queue: {}", e);
                 }
             }
         });
@@ -279,45 +459,103 @@ impl WebhookManager {
         Ok(())
     }
 
-    /// Find tasks with pending webhook deliveries
-    async fn find_pending_webhooks(&self) -> Result<Vec<Uuid>> {
-        let conn = self.db.get_conn()?;
+    /// Enqueue deliveries for completed tasks that don't have one yet.
+    async fn enqueue_new_deliveries(&self) -> Result<()> {
+        let task_ids = {
+            let conn = self.db.get_conn()?;
+            let mut stmt = conn.prepare(
+                "SELECT t.id FROM tasks t
+                 WHERE t.webhook_url IS NOT NULL
+                   AND t.status IN ('completed', 'failed')
+                   AND NOT EXISTS (SELECT 1 FROM webhook_deliveries wd WHERE wd.task_id = t.id)
+                 ORDER BY t.completed_at ASC
+                 LIMIT 100",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let id: String = row.get(0)?;
+                Ok(id)
+            })?;
+            let mut ids = Vec::new();
+            for row in rows {
+                ids.push(row?);
+            }
+            ids
+        };
 
+        for id in task_ids {
+            if let Ok(task_id) = Uuid::parse_str(&id) {
+                self.enqueue_delivery(task_id).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fan every due delivery out across a bounded concurrency pool.
+    async fn drain_due(self: &Arc<Self>) -> Result<()> {
+        let due = self.select_due_deliveries()?;
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        let semaphore = Arc::new(Semaphore::new(self.config.max_concurrency));
+        let mut handles = Vec::new();
+
+        for row in due {
+            let manager = Arc::clone(self);
+            let semaphore = Arc::clone(&semaphore);
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire().await;
+                if let Err(e) = manager.attempt_delivery(&row).await {
+                    error!("Error attempting webhook delivery {}: {}", row.id, e);
+                }
+            }));
+        }
+
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        Ok(())
+    }
+
+    /// Select deliveries that are pending and due for another attempt.
+    fn select_due_deliveries(&self) -> Result<Vec<PendingDelivery>> {
+        let conn = self.db.get_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT DISTINCT t.id
-             FROM tasks t
-             WHERE t.webhook_url IS NOT NULL
-               AND t.status IN ('completed', 'failed')
-               AND NOT EXISTS (
-                   SELECT 1 FROM webhook_deliveries wd
-                   WHERE wd.task_id = t.id
-                     AND wd.status_code IS NOT NULL
-                     AND wd.status_code >= 200
-                     AND wd.status_code < 300
-               )
-             ORDER BY t.completed_at ASC
-             LIMIT 100",
+            "SELECT id, task_id, webhook_url, payload, attempt_count
+             FROM webhook_deliveries
+             WHERE status = 'pending' AND next_attempt_at <= ?
+             ORDER BY next_attempt_at ASC
+             LIMIT 500",
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            let id_str: String = row.get(0)?;
-            Ok(Uuid::parse_str(&id_str).unwrap())
+        let rows = stmt.query_map(duckdb::params![Utc::now()], |row| {
+            Ok(PendingDelivery {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                webhook_url: row.get(2)?,
+                payload: row.get(3)?,
+                attempt_count: row.get(4)?,
+            })
         })?;
 
-        let mut task_ids = Vec::new();
-        for id in rows {
-            task_ids.push(id?);
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
         }
-
-        Ok(task_ids)
+        Ok(out)
     }
 }
 
-/// Webhook delivery response
-#[derive(Debug)]
-struct WebhookResponse {
-    status: u16,
-    body: String,
+/// A queued delivery awaiting its next attempt.
+#[derive(Debug, Clone)]
+struct PendingDelivery {
+    id: i64,
+    task_id: String,
+    webhook_url: String,
+    payload: String,
+    attempt_count: i32,
 }
 
 /// Webhook delivery record
@@ -329,7 +567,7 @@ pub struct WebhookDelivery {
     pub payload: String,
     pub status_code: Option<u16>,
     pub response: Option<String>,
-    pub attempted_at: chrono::DateTime<Utc>,
+    pub attempted_at: Option<chrono::DateTime<Utc>>,
     pub delivered_at: Option<chrono::DateTime<Utc>>,
 }
 
@@ -357,4 +595,32 @@ mod tests {
         let manager = WebhookManager::with_defaults(db);
         assert!(manager.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_backoff_grows_with_attempts() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let manager = WebhookManager::with_defaults(db).unwrap();
+
+        let first = manager.next_attempt_at(1, 1);
+        let later = manager.next_attempt_at(1, 4);
+        assert!(later > first, "later attempts should be scheduled further out");
+    }
+
+    #[test]
+    fn test_sign_body_is_deterministic_and_timestamp_bound() {
+        let a = sign_body("secret", 1000, "{\"x\":1}");
+        let b = sign_body("secret", 1000, "{\"x\":1}");
+        let c = sign_body("secret", 1001, "{\"x\":1}");
+        assert_eq!(a, b, "same inputs must produce the same signature");
+        assert_ne!(a, c, "a different timestamp must change the signature");
+        assert_eq!(a.len(), 64, "hex-encoded SHA-256 is 64 chars");
+    }
+
+    #[test]
+    fn test_delivery_kind_retry_policy() {
+        assert!(!DeliveryKind::Success.is_retryable());
+        assert!(!DeliveryKind::ClientError.is_retryable());
+        assert!(DeliveryKind::ServerError.is_retryable());
+        assert!(DeliveryKind::TransportError.is_retryable());
+    }
 }
@@ -354,6 +354,43 @@ async fn test_task_retry() {
     assert_eq!(retry_task.status, TaskStatus::Pending);
 }
 
+#[tokio::test]
+async fn test_workflow_resume_skips_completed() {
+    let db = Arc::new(Database::in_memory().unwrap());
+    let queue = Arc::new(TaskQueue::new(db.clone(), 2));
+    let executor = WorkflowExecutor::new(db.clone(), queue);
+
+    let workflow_id = executor
+        .create_workflow("Resumable".to_string(), None)
+        .unwrap();
+    executor
+        .add_phase(workflow_id, "phase1".to_string(), "Phase 1".to_string(), vec![])
+        .unwrap();
+
+    let task1 = AsyncTask::new("Task 1".to_string(), "a".to_string(), "one".to_string());
+    let task1_id = executor
+        .add_phase_task(workflow_id, "phase1".to_string(), task1)
+        .unwrap();
+
+    let task2 = AsyncTask::new("Task 2".to_string(), "a".to_string(), "two".to_string())
+        .with_dependencies(vec![task1_id]);
+    let task2_id = executor
+        .add_phase_task(workflow_id, "phase1".to_string(), task2)
+        .unwrap();
+
+    // Task 1 completed; task 2 left pending with its dependency satisfied.
+    db.update_task_result(task1_id, "done".to_string()).unwrap();
+
+    let resumed = executor.resume_workflow(workflow_id).unwrap();
+    assert_eq!(resumed, 1, "only the pending dependent task should be re-dispatched");
+
+    // Completed task must not be touched.
+    let task1_after = db.get_task(task1_id).unwrap().unwrap();
+    assert_eq!(task1_after.status, TaskStatus::Completed);
+    let task2_after = db.get_task(task2_id).unwrap().unwrap();
+    assert_eq!(task2_after.status, TaskStatus::Pending);
+}
+
 #[tokio::test]
 async fn test_phase_tasks_by_phase_id() {
     let db = Arc::new(Database::in_memory().unwrap());
@@ -412,3 +449,152 @@ async fn test_phase_tasks_by_phase_id() {
     assert_eq!(phase1_tasks.len(), 3);
     assert_eq!(phase2_tasks.len(), 2);
 }
+
+#[tokio::test]
+async fn test_instantiate_template_materializes_workflow() {
+    use orchestr8_async::{TemplatePhase, TemplateTask, WorkflowTemplate};
+
+    let db = Arc::new(Database::in_memory().unwrap());
+    let queue = Arc::new(TaskQueue::new(db.clone(), 2));
+    let executor = WorkflowExecutor::new(db.clone(), queue);
+
+    let template = WorkflowTemplate {
+        name: "deploy-{{service}}".to_string(),
+        description: Some("Deploy {{service}}".to_string()),
+        phases: vec![TemplatePhase {
+            phase_id: "build".to_string(),
+            name: "Build {{service}}".to_string(),
+            depends_on: vec![],
+        }],
+        tasks: vec![TemplateTask {
+            phase_id: "build".to_string(),
+            name: "Compile {{service}}".to_string(),
+            agent_name: "builder".to_string(),
+            agent_instructions: "Build the {{service}} service".to_string(),
+        }],
+    };
+
+    let mut params = serde_json::Map::new();
+    params.insert("service".to_string(), serde_json::json!("api"));
+
+    let workflow_id = executor.instantiate_template(&template, &params).unwrap();
+
+    let workflow = db.get_workflow(workflow_id).unwrap().unwrap();
+    assert_eq!(workflow.name, "deploy-api");
+
+    let tasks = db.get_phase_tasks(workflow_id, "build").unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].name, "Compile api");
+    assert_eq!(tasks[0].agent_instructions, "Build the api service");
+}
+
+#[tokio::test]
+async fn test_submit_workflow_builds_graph_and_maps_names() {
+    use orchestr8_async::{SubmissionPhase, SubmissionTask, WorkflowSubmission};
+
+    let db = Arc::new(Database::in_memory().unwrap());
+    let queue = Arc::new(TaskQueue::new(db.clone(), 2));
+    let executor = WorkflowExecutor::new(db.clone(), queue);
+
+    let submission = WorkflowSubmission {
+        name: "Build and Deploy".to_string(),
+        description: None,
+        phases: vec![
+            SubmissionPhase {
+                phase_id: "build".to_string(),
+                name: "Build".to_string(),
+                depends_on: vec![],
+            },
+            SubmissionPhase {
+                phase_id: "deploy".to_string(),
+                name: "Deploy".to_string(),
+                depends_on: vec!["build".to_string()],
+            },
+        ],
+        tasks: vec![
+            SubmissionTask {
+                name: "compile".to_string(),
+                phase_id: "build".to_string(),
+                agent_name: "builder".to_string(),
+                agent_instructions: "compile".to_string(),
+                dependencies: vec![],
+                priority: None,
+            },
+            SubmissionTask {
+                name: "ship".to_string(),
+                phase_id: "deploy".to_string(),
+                agent_name: "deployer".to_string(),
+                agent_instructions: "ship".to_string(),
+                dependencies: vec!["compile".to_string()],
+                priority: None,
+            },
+        ],
+    };
+
+    let (workflow_id, name_to_id) = executor.submit_workflow(&submission).unwrap();
+    assert_eq!(name_to_id.len(), 2);
+
+    let tasks = db.get_workflow_tasks(workflow_id).unwrap();
+    let ship = tasks.iter().find(|t| t.name == "ship").unwrap();
+    assert_eq!(ship.dependencies, vec![name_to_id["compile"]]);
+}
+
+#[tokio::test]
+async fn test_submit_workflow_rejects_phase_cycle() {
+    use orchestr8_async::{SubmissionPhase, WorkflowSubmission};
+
+    let db = Arc::new(Database::in_memory().unwrap());
+    let queue = Arc::new(TaskQueue::new(db.clone(), 2));
+    let executor = WorkflowExecutor::new(db.clone(), queue);
+
+    let submission = WorkflowSubmission {
+        name: "Cyclic".to_string(),
+        description: None,
+        phases: vec![
+            SubmissionPhase {
+                phase_id: "a".to_string(),
+                name: "A".to_string(),
+                depends_on: vec!["b".to_string()],
+            },
+            SubmissionPhase {
+                phase_id: "b".to_string(),
+                name: "B".to_string(),
+                depends_on: vec!["a".to_string()],
+            },
+        ],
+        tasks: vec![],
+    };
+
+    let err = executor.submit_workflow(&submission).unwrap_err();
+    assert!(err.to_string().contains("Cycle detected in phase graph"));
+}
+
+#[tokio::test]
+async fn test_submit_workflow_rejects_unknown_task_dependency() {
+    use orchestr8_async::{SubmissionPhase, SubmissionTask, WorkflowSubmission};
+
+    let db = Arc::new(Database::in_memory().unwrap());
+    let queue = Arc::new(TaskQueue::new(db.clone(), 2));
+    let executor = WorkflowExecutor::new(db.clone(), queue);
+
+    let submission = WorkflowSubmission {
+        name: "Dangling".to_string(),
+        description: None,
+        phases: vec![SubmissionPhase {
+            phase_id: "p".to_string(),
+            name: "P".to_string(),
+            depends_on: vec![],
+        }],
+        tasks: vec![SubmissionTask {
+            name: "only".to_string(),
+            phase_id: "p".to_string(),
+            agent_name: "a".to_string(),
+            agent_instructions: "x".to_string(),
+            dependencies: vec!["missing".to_string()],
+            priority: None,
+        }],
+    };
+
+    let err = executor.submit_workflow(&submission).unwrap_err();
+    assert!(err.to_string().contains("unknown task 'missing'"));
+}
@@ -0,0 +1,50 @@
+/*!
+ * Storage migration tool.
+ *
+ * Copies all agent metadata from one [`Storage`](orchestr8_bin::storage::Storage)
+ * backend to another without hand-rolled scripts. A `memory` spec opens a
+ * fresh, empty store that lives only for this run, so copying from it copies
+ * nothing and copying into it keeps nothing after the tool exits; only
+ * `sqlite:<path>` specs carry data between runs:
+ *
+ * ```text
+ * storage_migrate --from sqlite:agents.db --to sqlite:new.db
+ * ```
+ *
+ * Both endpoints are opened through the same trait, so the copy loop is
+ * backend-agnostic: `list_agents` on the source, `import_agents` on the
+ * destination.
+ */
+
+use anyhow::{bail, Context, Result};
+use orchestr8_bin::storage::{open_storage, Storage};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let (mut from, mut to) = (None, None);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => from = args.next(),
+            "--to" => to = args.next(),
+            other => bail!("unknown argument: {other}"),
+        }
+    }
+
+    let from = from.context("missing --from <spec>")?;
+    let to = to.context("missing --to <spec>")?;
+
+    let source = open_storage(&from).context("opening source store")?;
+    let dest = open_storage(&to).context("opening destination store")?;
+
+    let mut entries = Vec::new();
+    for agent in source.list_agents().await.context("reading source agents")? {
+        let path = source.get_agent_file_path(&agent.name).await?;
+        entries.push((agent, path));
+    }
+    let total = entries.len();
+    dest.import_agents(entries).await.context("writing destination")?;
+
+    println!("Migrated {total} agent(s) from {from} to {to}");
+    Ok(())
+}
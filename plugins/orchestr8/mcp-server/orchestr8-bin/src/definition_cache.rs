@@ -0,0 +1,132 @@
+/*!
+ * Persistent on-disk cache for JIT-loaded agent definitions.
+ *
+ * The in-memory [`LruCache`](lru::LruCache) of parsed definitions is lost on
+ * restart, so every cold process start re-parses every agent file. This module
+ * adds a disk-backed tier keyed by agent name: a parsed [`AgentDefinition`] is
+ * serialized to `<cache_dir>/<name>.json` alongside the source file's mtime, so
+ * a warm process can deserialize it and skip the parse entirely whenever the
+ * source file is unchanged.
+ *
+ * Writes use the crash-safe tmp-write-then-rename pattern: serialize to
+ * `<name>.json.tmp`, `write_all` + `sync_data` to flush, then atomically
+ * `rename` onto the final path. A partially written cache file is therefore
+ * never observed; on any failure the temp file is removed.
+ */
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::{debug, warn};
+
+use crate::loader::AgentDefinition;
+
+/// A cached definition tagged with the mtime of the source file it was parsed
+/// from, so a stale entry is detected by a simple mtime comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDefinition {
+    /// Source file mtime at parse time, as (seconds, nanoseconds) since the
+    /// Unix epoch — an exact, serialization-stable key.
+    source_mtime: (u64, u32),
+    definition: AgentDefinition,
+}
+
+/// Disk-backed cache of parsed agent definitions.
+pub struct DefinitionDiskCache {
+    cache_dir: PathBuf,
+}
+
+impl DefinitionDiskCache {
+    /// Open (creating if needed) a cache rooted at `cache_dir`.
+    pub fn open(cache_dir: impl Into<PathBuf>) -> Result<Self> {
+        let cache_dir = cache_dir.into();
+        fs::create_dir_all(&cache_dir)
+            .with_context(|| format!("creating definition cache dir {}", cache_dir.display()))?;
+        Ok(Self { cache_dir })
+    }
+
+    /// Final cache path for an agent. The name is sanitized so it can't escape
+    /// the cache directory.
+    fn path_for(&self, name: &str) -> PathBuf {
+        let safe: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.cache_dir.join(format!("{safe}.json"))
+    }
+
+    /// Return the cached definition for `name` iff a cache file exists and its
+    /// stored source mtime matches the current mtime of `source_path`. A miss,
+    /// a stale entry, or any read/parse error yields `None` so the caller falls
+    /// back to a fresh parse.
+    pub fn get(&self, name: &str, source_path: &Path) -> Option<AgentDefinition> {
+        let current = mtime_parts(source_path)?;
+        let path = self.path_for(name);
+        let bytes = fs::read(&path).ok()?;
+        let cached: CachedDefinition = serde_json::from_slice(&bytes).ok()?;
+        if cached.source_mtime == current {
+            debug!("Definition disk cache hit for: {}", name);
+            Some(cached.definition)
+        } else {
+            debug!("Definition disk cache stale for: {}", name);
+            None
+        }
+    }
+
+    /// Persist `definition` for `name`, tagged with the current mtime of
+    /// `source_path`, using an atomic tmp-write-then-rename. Errors are logged
+    /// and swallowed: the cache is an optimization, never a hard dependency.
+    pub fn put(&self, name: &str, source_path: &Path, definition: &AgentDefinition) {
+        if let Err(e) = self.try_put(name, source_path, definition) {
+            warn!("Failed to persist definition cache for {}: {}", name, e);
+        }
+    }
+
+    fn try_put(&self, name: &str, source_path: &Path, definition: &AgentDefinition) -> Result<()> {
+        let source_mtime =
+            mtime_parts(source_path).context("reading source mtime for cache entry")?;
+        let entry = CachedDefinition {
+            source_mtime,
+            definition: definition.clone(),
+        };
+        let serialized = serde_json::to_vec(&entry)?;
+
+        let final_path = self.path_for(name);
+        let tmp_path = final_path.with_extension("json.tmp");
+
+        // Write to the temp file, flush to disk, then rename over the target.
+        // Clean up the temp file on any failure so a crash never leaves litter.
+        let write = || -> Result<()> {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(&serialized)?;
+            file.sync_data()?;
+            fs::rename(&tmp_path, &final_path)?;
+            Ok(())
+        };
+        if let Err(e) = write() {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+/// The mtime of `path` as (seconds, nanoseconds) since the Unix epoch, or
+/// `None` if it can't be read. Exposed so the background refresher can compare
+/// a source file's mtime against what it last saw.
+pub fn mtime_parts(path: &Path) -> Option<(u64, u32)> {
+    let mtime = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let dur = mtime.duration_since(UNIX_EPOCH).ok()?;
+    Some((dur.as_secs(), dur.subsec_nanos()))
+}
+
+impl std::fmt::Debug for DefinitionDiskCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefinitionDiskCache")
+            .field("cache_dir", &self.cache_dir)
+            .finish()
+    }
+}
@@ -0,0 +1,214 @@
+/*!
+ * Optional gRPC transport for the agent-discovery service, generated from
+ * `proto/agent_discovery.proto`. Feature-gated behind `grpc` so a build that
+ * only wants JSON-RPC doesn't pull in `tonic`/`prost`.
+ *
+ * Each method delegates into [`McpHandler::handle_request`] — the same
+ * per-method dispatch the JSON-RPC transport uses — so the two surfaces can
+ * never drift in behavior. That keeps this file a thin typed<->untyped
+ * adapter rather than a second copy of the handler logic; hoisting each
+ * `handle_*` body into a typed core both transports call directly (as
+ * opposed to going through `handle_request`'s `Value` params) is worthwhile
+ * follow-up once more than one RPC needs it.
+ */
+
+use crate::mcp::{JsonRpcRequest, McpHandler};
+use crate::storage::Storage;
+use anyhow::Result;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+tonic::include_proto!("orchestr8.agent_discovery.v1");
+
+use agent_discovery_server::{AgentDiscovery, AgentDiscoveryServer};
+
+/// Adapts an [`McpHandler`] to the `AgentDiscovery` gRPC service.
+pub struct AgentDiscoveryService<S: Storage = crate::db::Database> {
+    handler: Arc<McpHandler<S>>,
+}
+
+impl<S: Storage + 'static> AgentDiscoveryService<S> {
+    pub fn new(handler: Arc<McpHandler<S>>) -> Self {
+        Self { handler }
+    }
+
+    pub fn into_server(self) -> AgentDiscoveryServer<Self> {
+        AgentDiscoveryServer::new(self)
+    }
+
+    /// Run `method` through the shared JSON-RPC dispatch and surface any
+    /// handler error as a gRPC `Status`, matching the JSON-RPC error message
+    /// so clients see the same diagnostic on either transport.
+    async fn call(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, Status> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: Some(params),
+            id: serde_json::Value::Null,
+        };
+        // gRPC has no bearer-token concept of its own yet; callers that need
+        // auth-gated methods over gRPC should front this service with a
+        // tonic interceptor that extracts `authorization` metadata.
+        let response = self.handler.handle_request(request, None).await;
+        match response.error {
+            Some(err) => Err(Status::unknown(err.message)),
+            None => Ok(response.result.unwrap_or(serde_json::Value::Null)),
+        }
+    }
+
+    fn from_value<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Result<T, Status> {
+        serde_json::from_value(value)
+            .map_err(|e| Status::internal(format!("malformed handler response: {e}")))
+    }
+}
+
+fn agent_metadata_to_proto(a: &crate::loader::AgentMetadata) -> AgentMetadata {
+    AgentMetadata {
+        name: a.name.clone(),
+        plugin: a.plugin.clone(),
+        role: a.role.clone(),
+        description: a.description.clone(),
+        capabilities: a.capabilities.clone(),
+    }
+}
+
+fn query_result_to_proto(result: crate::mcp::AgentQueryResult) -> QueryResponse {
+    QueryResponse {
+        agents: result.agents.iter().map(agent_metadata_to_proto).collect(),
+        reasoning: result.reasoning,
+        confidence: result.confidence,
+        cache_hit: result.cache_hit,
+        query_time_ms: result.query_time_ms,
+        next_cursor: result.next_cursor,
+    }
+}
+
+#[tonic::async_trait]
+impl<S: Storage + 'static> AgentDiscovery for AgentDiscoveryService<S> {
+    async fn query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        let req = request.into_inner();
+        let params = serde_json::json!({
+            "context": req.context,
+            "role": req.role,
+            "capability": req.capability,
+            "limit": req.limit,
+            "cursor": req.cursor,
+            "page_size": req.page_size,
+        });
+        let value = self.call("agents/query", params).await?;
+        let result: crate::mcp::AgentQueryResult = Self::from_value(value)?;
+        Ok(Response::new(query_result_to_proto(result)))
+    }
+
+    async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
+        let req = request.into_inner();
+        let value = self
+            .call("agents/list", serde_json::json!({ "plugin": req.plugin }))
+            .await?;
+        let agents: Vec<crate::loader::AgentMetadata> = Self::from_value(
+            value
+                .get("agents")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null),
+        )?;
+        Ok(Response::new(ListResponse {
+            total: agents.len() as u64,
+            agents: agents.iter().map(agent_metadata_to_proto).collect(),
+        }))
+    }
+
+    async fn get(&self, request: Request<GetRequest>) -> Result<Response<AgentMetadata>, Status> {
+        let req = request.into_inner();
+        let value = self
+            .call("agents/get", serde_json::json!({ "name": req.name }))
+            .await?;
+        let agent: crate::loader::AgentMetadata = Self::from_value(value)?;
+        Ok(Response::new(agent_metadata_to_proto(&agent)))
+    }
+
+    async fn get_definition(
+        &self,
+        request: Request<GetDefinitionRequest>,
+    ) -> Result<Response<GetDefinitionResponse>, Status> {
+        let req = request.into_inner();
+        let value = self
+            .call(
+                "agents/get_definition",
+                serde_json::json!({ "name": req.name }),
+            )
+            .await?;
+        Ok(Response::new(GetDefinitionResponse {
+            definition_json: value.to_string(),
+        }))
+    }
+
+    async fn discover_by_capability(
+        &self,
+        request: Request<DiscoverByCapabilityRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        let req = request.into_inner();
+        let value = self
+            .call(
+                "agents/discover_by_capability",
+                serde_json::json!({ "capability": req.capability, "limit": req.limit }),
+            )
+            .await?;
+        let result: crate::mcp::AgentQueryResult = Self::from_value(value)?;
+        Ok(Response::new(query_result_to_proto(result)))
+    }
+
+    async fn discover_by_role(
+        &self,
+        request: Request<DiscoverByRoleRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        let req = request.into_inner();
+        let value = self
+            .call(
+                "agents/discover_by_role",
+                serde_json::json!({ "role": req.role, "limit": req.limit }),
+            )
+            .await?;
+        let result: crate::mcp::AgentQueryResult = Self::from_value(value)?;
+        Ok(Response::new(query_result_to_proto(result)))
+    }
+
+    async fn health(
+        &self,
+        _request: Request<HealthRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        let value = self.call("health", serde_json::Value::Null).await?;
+        Ok(Response::new(HealthResponse {
+            status: value["status"].as_str().unwrap_or("unknown").to_string(),
+            uptime_ms: value["uptime_ms"].as_u64().unwrap_or(0),
+            memory_mb: value["memory_mb"].as_f64().unwrap_or(0.0),
+        }))
+    }
+
+    async fn cache_stats(
+        &self,
+        _request: Request<CacheStatsRequest>,
+    ) -> Result<Response<CacheStatsResponse>, Status> {
+        let value = self.call("cache/stats", serde_json::Value::Null).await?;
+        Ok(Response::new(CacheStatsResponse {
+            hits: value["hits"].as_u64().unwrap_or(0),
+            misses: value["misses"].as_u64().unwrap_or(0),
+        }))
+    }
+
+    async fn cache_clear(
+        &self,
+        _request: Request<CacheClearRequest>,
+    ) -> Result<Response<CacheClearResponse>, Status> {
+        let value = self.call("cache/clear", serde_json::Value::Null).await?;
+        Ok(Response::new(CacheClearResponse {
+            cleared: value["cleared"].as_bool().unwrap_or(false),
+        }))
+    }
+}
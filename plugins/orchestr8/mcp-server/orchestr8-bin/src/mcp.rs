@@ -3,16 +3,24 @@
  */
 
 use anyhow::Result;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::cache::QueryCache;
 use crate::db::Database;
+use crate::definition_cache::DefinitionDiskCache;
 use crate::loader::{AgentDefinition, AgentLoader, AgentMetadata};
+use crate::metrics::MetricsRegistry;
+use crate::persistent_cache::{CacheTier, PersistentCache};
+use crate::storage::Storage;
 use std::num::NonZeroUsize;
 use lru::LruCache;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::task::JoinSet;
 
 /// JSON-RPC 2.0 Request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +75,130 @@ impl JsonRpcResponse {
     }
 }
 
+/// An error carrying an explicit JSON-RPC error code. Handlers return this
+/// (via `anyhow`) when they need to surface a protocol-level failure such as
+/// invalid params rather than the generic internal error.
+#[derive(Debug)]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Structured taxonomy of failures handlers can raise, each mapping to a
+/// specific JSON-RPC error code with a machine-readable `data` payload. This
+/// lets clients branch on the error programmatically instead of string-matching
+/// a generic `-32603` blob.
+#[derive(Debug)]
+pub enum McpError {
+    /// Malformed JSON (-32700).
+    ParseError(String),
+    /// Well-formed but invalid request (-32600).
+    InvalidRequest(String),
+    /// Unknown method, carries the offending method name (-32601).
+    MethodNotFound(String),
+    /// Invalid parameters (-32602).
+    InvalidParams(String),
+    /// Domain error: the requested resource URI does not exist. Uses the
+    /// server-reserved range (-32001).
+    ResourceNotFound(String),
+    /// Missing or invalid bearer token for a method gated by [`McpAuth`]
+    /// (-32003; -32002 is already used for "Server not initialized").
+    Unauthorized(String),
+    /// Catch-all internal failure (-32603).
+    Internal(String),
+}
+
+impl McpError {
+    /// JSON-RPC numeric error code for this failure.
+    pub fn code(&self) -> i32 {
+        match self {
+            McpError::ParseError(_) => -32700,
+            McpError::InvalidRequest(_) => -32600,
+            McpError::MethodNotFound(_) => -32601,
+            McpError::InvalidParams(_) => -32602,
+            // -32000..=-32099 is reserved for implementation-defined codes.
+            McpError::ResourceNotFound(_) => -32001,
+            McpError::Unauthorized(_) => -32003,
+            McpError::Internal(_) => -32603,
+        }
+    }
+
+    /// Machine-readable payload attached to the error, naming the offending
+    /// input (the unknown method, the missing URI) so clients need not parse
+    /// the human message.
+    pub fn data(&self) -> Option<Value> {
+        match self {
+            McpError::MethodNotFound(method) => Some(serde_json::json!({ "method": method })),
+            McpError::ResourceNotFound(uri) => Some(serde_json::json!({ "uri": uri })),
+            McpError::InvalidParams(detail)
+            | McpError::InvalidRequest(detail)
+            | McpError::ParseError(detail)
+            | McpError::Unauthorized(detail) => Some(serde_json::json!({ "detail": detail })),
+            McpError::Internal(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for McpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpError::ParseError(m) => write!(f, "Parse error: {m}"),
+            McpError::InvalidRequest(m) => write!(f, "Invalid request: {m}"),
+            McpError::MethodNotFound(m) => write!(f, "Method not found: {m}"),
+            McpError::InvalidParams(m) => write!(f, "Invalid params: {m}"),
+            McpError::ResourceNotFound(uri) => write!(f, "Resource not found: {uri}"),
+            McpError::Unauthorized(m) => write!(f, "Unauthorized: {m}"),
+            McpError::Internal(m) => write!(f, "{m}"),
+        }
+    }
+}
+
+impl std::error::Error for McpError {}
+
+/// Connection lifecycle state. A client must drive the handshake
+/// `initialize` → `initialized` before issuing feature methods, and after
+/// `shutdown` only `exit` is accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Uninitialized,
+    Initializing,
+    Ready,
+    ShuttingDown,
+}
+
+impl ConnectionState {
+    const UNINITIALIZED: u8 = 0;
+    const INITIALIZING: u8 = 1;
+    const READY: u8 = 2;
+    const SHUTTING_DOWN: u8 = 3;
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            Self::INITIALIZING => ConnectionState::Initializing,
+            Self::READY => ConnectionState::Ready,
+            Self::SHUTTING_DOWN => ConnectionState::ShuttingDown,
+            _ => ConnectionState::Uninitialized,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            ConnectionState::Uninitialized => Self::UNINITIALIZED,
+            ConnectionState::Initializing => Self::INITIALIZING,
+            ConnectionState::Ready => Self::READY,
+            ConnectionState::ShuttingDown => Self::SHUTTING_DOWN,
+        }
+    }
+}
+
 /// Agent query parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentQueryParams {
@@ -78,6 +210,16 @@ pub struct AgentQueryParams {
     pub capability: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    /// Opaque pagination token from a previous page's `next_cursor`. When
+    /// present, overrides `context`/`role`/`capability` with the filter the
+    /// cursor was minted against, so a client can't accidentally page over a
+    /// different query by repeating the wrong params.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Agents to return in this page. Defaults to [`DEFAULT_PAGE_SIZE`] and
+    /// is clamped to 1..=[`MAX_PAGE_SIZE`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
 }
 
 /// Agent query result
@@ -88,6 +230,56 @@ pub struct AgentQueryResult {
     pub confidence: f64,
     pub cache_hit: bool,
     pub query_time_ms: f64,
+    /// Pass back as `cursor` to fetch the next page; `None` once the result
+    /// set is exhausted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Page size used when a query supplies no `page_size` (with or without a
+/// `cursor`; the cursor does not record a page size).
+const DEFAULT_PAGE_SIZE: usize = 10;
+/// Upper bound a caller's `page_size` is clamped to, regardless of request.
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Contents of an opaque `agents/query`/`agents/discover` pagination cursor:
+/// the last-seen stable sort key (an agent name, unique within one ranked
+/// result set) plus the filter it was minted against and the index
+/// generation at the time, so a stale cursor from before an index rebuild is
+/// rejected rather than silently returning a different page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PageCursor {
+    after: String,
+    context: Option<String>,
+    role: Option<String>,
+    capability: Option<String>,
+    epoch: u64,
+}
+
+impl PageCursor {
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("PageCursor always serializes");
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode and validate a cursor against the current index generation.
+    /// Any malformed token or one minted before `current_epoch` is rejected
+    /// with [`McpError::InvalidParams`] so clients never silently page over
+    /// stale data.
+    fn decode(raw: &str, current_epoch: u64) -> Result<Self, McpError> {
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(raw)
+            .map_err(|e| McpError::InvalidParams(format!("invalid cursor: {e}")))?;
+        let cursor: PageCursor = serde_json::from_slice(&bytes)
+            .map_err(|e| McpError::InvalidParams(format!("invalid cursor: {e}")))?;
+        if cursor.epoch < current_epoch {
+            return Err(McpError::InvalidParams(
+                "cursor is stale: the agent index has been rebuilt since it was issued"
+                    .to_string(),
+            ));
+        }
+        Ok(cursor)
+    }
 }
 
 /// Health check response
@@ -158,22 +350,260 @@ pub struct ResourceContent {
     pub text: String,
 }
 
-/// MCP Handler - processes JSON-RPC requests with JIT agent loading support
-pub struct McpHandler {
-    db: Database,
+/// Default number of resource deltas emitted per subscription batch.
+const RESOURCE_BATCH_SIZE: usize = 50;
+
+/// A resource together with the index generation at which it first appeared,
+/// so subscribers can request only the deltas newer than their last cursor.
+#[derive(Debug, Clone)]
+struct GenerationedResource {
+    generation: u64,
+    resource: ResourceMetadata,
+}
+
+/// A bounded, cursor-driven iterator over resource deltas. Yields
+/// [`ResourceMetadata`] in batches no larger than `batch_size`, advancing over
+/// the resources newer than the caller's last-seen generation.
+pub struct BatchIterator {
+    items: Vec<ResourceMetadata>,
+    pos: usize,
+    batch_size: usize,
+    /// Generation the caller should record after draining this iterator.
+    high_water: u64,
+}
+
+impl BatchIterator {
+    fn new(items: Vec<ResourceMetadata>, batch_size: usize, high_water: u64) -> Self {
+        Self {
+            items,
+            pos: 0,
+            batch_size: batch_size.max(1),
+            high_water,
+        }
+    }
+
+    /// Next batch of deltas, or `None` once fully drained.
+    pub fn next_batch(&mut self) -> Option<Vec<ResourceMetadata>> {
+        if self.pos >= self.items.len() {
+            return None;
+        }
+        let end = (self.pos + self.batch_size).min(self.items.len());
+        let batch = self.items[self.pos..end].to_vec();
+        self.pos = end;
+        Some(batch)
+    }
+
+    /// Whether every delta has been emitted.
+    pub fn is_drained(&self) -> bool {
+        self.pos >= self.items.len()
+    }
+
+    /// Generation a client should send as its cursor to resume without
+    /// duplicates.
+    pub fn high_water(&self) -> u64 {
+        self.high_water
+    }
+}
+
+/// Capacity of the `agents/watch` broadcast channel: the number of
+/// undelivered deltas a slow subscriber may lag behind before it starts
+/// missing them (surfaced to that subscriber as a `RecvError::Lagged`).
+const AGENT_WATCH_CHANNEL_CAPACITY: usize = 256;
+
+/// A compact change to the agent index, broadcast to `agents/watch`
+/// subscribers whenever the filesystem watcher observes a create, modify, or
+/// delete under `agent_dir`. An agent that changed in place (same name, new
+/// content) appears in `updated`, never in both `added` and `removed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentDelta {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub added: Vec<AgentMetadata>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub updated: Vec<AgentMetadata>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub removed: Vec<String>,
+}
+
+impl AgentDelta {
+    /// Whether this delta touches no agent at all, in which case it's not
+    /// worth delivering to a subscriber.
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+
+    /// Narrow a delta to the agents matching `filter`. Removals are always
+    /// kept regardless of filter: a subscriber that previously saw an agent
+    /// should still learn it disappeared, even though the bare name in
+    /// `removed` carries nothing a filter could match against.
+    fn filtered(&self, filter: &AgentWatchFilter) -> AgentDelta {
+        AgentDelta {
+            added: self
+                .added
+                .iter()
+                .filter(|a| filter.matches(a))
+                .cloned()
+                .collect(),
+            updated: self
+                .updated
+                .iter()
+                .filter(|a| filter.matches(a))
+                .cloned()
+                .collect(),
+            removed: self.removed.clone(),
+        }
+    }
+}
+
+/// Per-subscriber interest for `agents/watch`: a delta is delivered when an
+/// added or updated agent matches both criteria (each, when set), or when it
+/// removes any agent, since removals pass every filter. Matching is a
+/// case-insensitive substring check, mirroring [`crate::storage::InMemoryStorage`]'s
+/// query filtering.
+#[derive(Debug, Clone, Default)]
+struct AgentWatchFilter {
+    capability: Option<String>,
+    role: Option<String>,
+}
+
+impl AgentWatchFilter {
+    fn matches(&self, agent: &AgentMetadata) -> bool {
+        let role_ok = self
+            .role
+            .as_deref()
+            .map_or(true, |r| agent.role.to_lowercase().contains(&r.to_lowercase()));
+        let cap_ok = self.capability.as_deref().map_or(true, |c| {
+            let c = c.to_lowercase();
+            agent.capabilities.iter().any(|x| x.to_lowercase().contains(&c))
+        });
+        role_ok && cap_ok
+    }
+}
+
+/// Filesystem change kind observed for one agent definition file, as reported
+/// by the `notify` watcher to [`McpHandler::apply_agent_file_change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentFileChange {
+    CreatedOrModified,
+    Removed,
+}
+
+/// Optional bearer-token gate for the methods [`McpHandler::requires_auth`]
+/// flags as sensitive. Mirrors `orchestr8_async::api::ApiSecurity`'s
+/// shared-secret model: reads one or more comma-separated tokens from
+/// `ORCHESTR8_API_TOKEN` and, when unset, leaves every method open (current
+/// stdio behavior), so existing deployments keep working unconfigured.
+#[derive(Debug, Clone, Default)]
+pub struct McpAuth {
+    tokens: Vec<String>,
+}
+
+impl McpAuth {
+    /// An open policy: no token required.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Read the policy from `ORCHESTR8_API_TOKEN`: a single token or a
+    /// comma-separated list of acceptable tokens.
+    pub fn from_env() -> Self {
+        let tokens = std::env::var("ORCHESTR8_API_TOKEN")
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { tokens }
+    }
+
+    /// Whether any gated method actually enforces a token.
+    pub fn is_enabled(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Check a presented token against the configured list in constant time,
+    /// so a timing side-channel can't be used to guess a valid token byte by
+    /// byte.
+    pub fn verify(&self, presented: Option<&str>) -> bool {
+        let Some(presented) = presented else {
+            return false;
+        };
+        self.tokens
+            .iter()
+            .any(|expected| constant_time_eq(expected, presented))
+    }
+}
+
+/// Compare two strings in constant time with respect to their shared length.
+/// Unequal lengths short-circuit (length isn't the secret), but once lengths
+/// match, every byte is compared regardless of earlier mismatches.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// MCP Handler - processes JSON-RPC requests with JIT agent loading support.
+///
+/// Generic over the [`Storage`] backend so the protocol layer is decoupled from
+/// any one database; see [`crate::storage`] for the in-memory and SQLite
+/// implementations.
+pub struct McpHandler<S: Storage = Database> {
+    db: S,
     cache: QueryCache,
-    agents: Vec<AgentMetadata>,
+    /// Live agent index. Behind a lock (rather than rebuilt at startup only)
+    /// so [`Self::apply_agent_file_change`] can add, replace, or drop entries
+    /// as the filesystem watcher observes them.
+    agents: Arc<Mutex<Vec<AgentMetadata>>>,
     agent_dir: std::path::PathBuf,
     loader: AgentLoader,
     /// LRU cache for full agent definitions (loaded on-demand)
     definition_cache: std::sync::Arc<Mutex<LruCache<String, AgentDefinition>>>,
+    /// Precomputed semantic embedding per agent, keyed by name. Refreshed
+    /// in place as agents are added, changed, or removed.
+    agent_embeddings: Arc<Mutex<std::collections::HashMap<String, Vec<f32>>>>,
+    /// LRU cache of query-text embeddings for the semantic search path.
+    query_embedding_cache: std::sync::Arc<Mutex<LruCache<String, Vec<f32>>>>,
+    /// Monotonic generation counter, bumped whenever the agent index changes.
+    index_generation: Arc<AtomicU64>,
+    /// Resource catalog with per-entry first-seen generation for delta streaming.
+    resource_catalog: Arc<Mutex<Vec<GenerationedResource>>>,
+    /// Active subscriptions: id -> last-delivered generation.
+    subscriptions: Arc<Mutex<std::collections::HashMap<String, u64>>>,
+    /// Source of unique subscription ids.
+    subscription_counter: Arc<AtomicU64>,
+    /// Maps an agent's definition file path back to its name, so filesystem
+    /// events (which carry paths, not names) can resolve removals.
+    agent_paths: Arc<Mutex<std::collections::HashMap<std::path::PathBuf, String>>>,
+    /// Broadcasts agent-index deltas to `agents/watch` subscribers.
+    agent_events: broadcast::Sender<AgentDelta>,
+    /// Active `agents/watch` subscriptions: id -> filter.
+    watch_subscriptions: Arc<Mutex<std::collections::HashMap<String, AgentWatchFilter>>>,
+    /// Optional persistent (SQLite) cache tier behind the in-memory caches.
+    persistent: Option<Arc<PersistentCache>>,
+    /// Optional disk-backed cache of parsed agent definitions, surviving
+    /// restarts so warm caches skip the parse entirely.
+    definition_disk: Option<Arc<DefinitionDiskCache>>,
+    /// Operational metrics accumulated over the life of the process.
+    metrics: Arc<MetricsRegistry>,
+    /// Connection lifecycle state machine.
+    state: Arc<AtomicU8>,
     start_time: std::time::Instant,
+    /// Bearer-token policy for methods [`Self::requires_auth`] flags as
+    /// sensitive. Read from `ORCHESTR8_API_TOKEN` at construction.
+    auth: McpAuth,
 }
 
-impl McpHandler {
+impl<S: Storage> McpHandler<S> {
     /// Create new handler with JIT agent loading support
     pub fn new(
-        db: Database,
+        db: S,
         cache: QueryCache,
         agents: Vec<AgentMetadata>,
         agent_dir: std::path::PathBuf,
@@ -185,24 +615,249 @@ impl McpHandler {
         let root_dir = agent_dir.parent().unwrap_or_else(|| std::path::Path::new("/")).to_path_buf();
         let loader = AgentLoader::new(&root_dir, &agent_dir);
 
+        // Build the semantic index once, alongside the agents themselves.
+        let agent_embeddings = agents
+            .iter()
+            .map(|a| (a.name.clone(), embed(&agent_embed_text(a))))
+            .collect();
+
+        // Seed the resource catalog at generation 0 from the initial index.
+        let resource_catalog: Vec<GenerationedResource> = agents
+            .iter()
+            .map(|a| GenerationedResource {
+                generation: 0,
+                resource: ResourceMetadata {
+                    uri: format!("agent://{}", a.name),
+                    name: a.name.clone(),
+                    description: Some(a.description.clone()),
+                    mimeType: Some("application/vnd.orchestr8.agent".to_string()),
+                },
+            })
+            .collect();
+
+        let (agent_events, _) = broadcast::channel(AGENT_WATCH_CHANNEL_CAPACITY);
+
         Self {
             db,
             cache,
-            agents,
+            agents: Arc::new(Mutex::new(agents)),
             agent_dir,
             loader,
             definition_cache: std::sync::Arc::new(Mutex::new(LruCache::new(cache_size))),
+            agent_embeddings: Arc::new(Mutex::new(agent_embeddings)),
+            query_embedding_cache: std::sync::Arc::new(Mutex::new(LruCache::new(cache_size))),
+            index_generation: Arc::new(AtomicU64::new(0)),
+            resource_catalog: Arc::new(Mutex::new(resource_catalog)),
+            subscriptions: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            subscription_counter: Arc::new(AtomicU64::new(0)),
+            agent_paths: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            agent_events,
+            watch_subscriptions: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            persistent: None,
+            definition_disk: None,
+            metrics: Arc::new(MetricsRegistry::new()),
+            state: Arc::new(AtomicU8::new(ConnectionState::Uninitialized.as_u8())),
             start_time: std::time::Instant::now(),
+            auth: McpAuth::from_env(),
         }
     }
 
-    pub async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+    /// Current connection lifecycle state.
+    pub fn state(&self) -> ConnectionState {
+        ConnectionState::from_u8(self.state.load(Ordering::SeqCst))
+    }
+
+    fn set_state(&self, state: ConnectionState) {
+        self.state.store(state.as_u8(), Ordering::SeqCst);
+    }
+
+    /// Enforce the lifecycle: feature methods require a `Ready` connection, and
+    /// once shutting down only `exit` is honored. Returns the rejection when a
+    /// method is not allowed in the current state.
+    fn lifecycle_gate(&self, method: &str) -> Option<(i32, &'static str)> {
+        const LIFECYCLE: &[&str] = &["initialize", "initialized", "shutdown", "exit"];
+
+        match self.state() {
+            ConnectionState::ShuttingDown if method != "exit" => {
+                Some((-32600, "Server is shutting down"))
+            }
+            ConnectionState::Uninitialized | ConnectionState::Initializing
+                if !LIFECYCLE.contains(&method) =>
+            {
+                Some((-32002, "Server not initialized"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Methods whose data justifies requiring the bearer token configured in
+    /// [`McpAuth`]. Everything else, including `health`, stays open even when
+    /// the server has a token configured, so liveness checks never need one.
+    fn requires_auth(method: &str) -> bool {
+        matches!(method, "cache/clear" | "agents/get_definition")
+    }
+
+    /// Attach a persistent cache tier behind the in-memory caches. Hits that
+    /// miss memory but are found on disk are promoted back into memory.
+    pub fn with_persistent_cache(mut self, cache: PersistentCache) -> Self {
+        self.persistent = Some(Arc::new(cache));
+        self
+    }
+
+    /// Attach a disk-backed definition cache. On a warm cache, `resources/read`
+    /// and `agents/get_definition` deserialize the parsed definition from disk
+    /// instead of re-parsing the agent file.
+    pub fn with_definition_disk_cache(mut self, cache: DefinitionDiskCache) -> Self {
+        self.definition_disk = Some(Arc::new(cache));
+        self
+    }
+
+    /// Override the bearer-token policy instead of reading it from
+    /// `ORCHESTR8_API_TOKEN`. Mainly useful for tests.
+    pub fn with_auth(mut self, auth: McpAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Spawn a background task that keeps the definition caches consistent with
+    /// on-disk edits. Every `refresh` interval it re-stats each cached agent's
+    /// file and, when the mtime has advanced, reloads and re-caches the
+    /// definition, evicting the stale in-memory entry.
+    ///
+    /// A reload that fails (file temporarily missing or malformed) sets a
+    /// per-entry backoff so the broken file isn't hammered every tick: the skip
+    /// window doubles on each consecutive failure up to `backoff_cap`, and
+    /// resets once the reload succeeds.
+    pub fn spawn_refresher(
+        self: &Arc<Self>,
+        refresh: std::time::Duration,
+        backoff_cap: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: 'static,
+    {
+        use std::time::{Duration, Instant};
+
+        /// Per-entry refresh bookkeeping: the last mtime we reloaded at and the
+        /// current backoff deadline/delay after repeated failures.
+        struct RefreshEntry {
+            last_mtime: Option<(u64, u32)>,
+            backoff: Option<(Instant, Duration)>,
+        }
+
+        let handler = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut state: std::collections::HashMap<String, RefreshEntry> =
+                std::collections::HashMap::new();
+
+            loop {
+                tokio::time::sleep(refresh).await;
+
+                // Snapshot the currently cached agent names so we don't hold the
+                // cache lock across the reload work.
+                let names: Vec<String> = {
+                    let cache = handler.definition_cache.lock().unwrap();
+                    cache.iter().map(|(name, _)| name.clone()).collect()
+                };
+
+                let now = Instant::now();
+                for name in names {
+                    let entry = state.entry(name.clone()).or_insert(RefreshEntry {
+                        last_mtime: None,
+                        backoff: None,
+                    });
+
+                    // Respect an active backoff window.
+                    if let Some((deadline, _)) = entry.backoff {
+                        if now < deadline {
+                            continue;
+                        }
+                    }
+
+                    let file_path = match handler.db.get_agent_file_path(&name).await {
+                        Ok(p) => p,
+                        Err(_) => {
+                            bump_backoff(&mut entry.backoff, refresh, backoff_cap, now);
+                            continue;
+                        }
+                    };
+
+                    let current = crate::definition_cache::mtime_parts(&file_path);
+                    match current {
+                        // File unreadable: treat as a transient failure.
+                        None => bump_backoff(&mut entry.backoff, refresh, backoff_cap, now),
+                        // Unchanged since last reload: nothing to do.
+                        Some(m) if entry.last_mtime == Some(m) => {
+                            entry.backoff = None;
+                        }
+                        Some(m) => match handler.loader.get_agent_definition_jit(&file_path) {
+                            Ok(def) => {
+                                if let Some(disk) = &handler.definition_disk {
+                                    disk.put(&name, file_path.as_path(), &def);
+                                }
+                                handler.definition_cache.lock().unwrap().put(name.clone(), def);
+                                entry.last_mtime = Some(m);
+                                entry.backoff = None;
+                                debug!("Refreshed agent definition from disk: {}", name);
+                            }
+                            Err(e) => {
+                                warn!("Failed to refresh definition {}: {}", name, e);
+                                bump_backoff(&mut entry.backoff, refresh, backoff_cap, now);
+                            }
+                        },
+                    }
+                }
+            }
+        })
+    }
+
+    /// Handle one JSON-RPC request. `auth_context` is the bearer token
+    /// presented alongside the request, if any; it's only consulted for
+    /// methods [`Self::requires_auth`] flags, and only when [`McpAuth`] has a
+    /// token configured, so existing callers passing `None` see no change in
+    /// behavior unless `ORCHESTR8_API_TOKEN` is set.
+    pub async fn handle_request(
+        &self,
+        request: JsonRpcRequest,
+        auth_context: Option<&str>,
+    ) -> JsonRpcResponse {
         let start = std::time::Instant::now();
 
         debug!("Handling method: {}", request.method);
 
+        // Enforce the lifecycle handshake before any feature method runs.
+        if let Some((code, message)) = self.lifecycle_gate(&request.method) {
+            error!("Rejected {} in state {:?}", request.method, self.state());
+            self.metrics
+                .record_request(&request.method, start.elapsed(), Some(code));
+            return JsonRpcResponse::error(request.id, code, message, None);
+        }
+
+        if self.auth.is_enabled()
+            && Self::requires_auth(&request.method)
+            && !self.auth.verify(auth_context)
+        {
+            warn!("Rejected unauthenticated {}", request.method);
+            let err =
+                McpError::Unauthorized(format!("{} requires a valid bearer token", request.method));
+            self.metrics
+                .record_request(&request.method, start.elapsed(), Some(err.code()));
+            return JsonRpcResponse::error(request.id, err.code(), &err.to_string(), err.data());
+        }
+
         let result = match request.method.as_str() {
             "initialize" => self.handle_initialize(request.params).await,
+            "initialized" | "notifications/initialized" => {
+                self.set_state(ConnectionState::Ready);
+                info!("MCP connection ready");
+                Ok(serde_json::json!({}))
+            }
+            "shutdown" => {
+                self.set_state(ConnectionState::ShuttingDown);
+                info!("MCP connection shutting down");
+                Ok(serde_json::json!({}))
+            }
+            "exit" => Ok(serde_json::json!({})),
             "agents/query" => self.handle_agent_query(request.params).await,
             "agents/list" => self.handle_agent_list(request.params).await,
             "agents/get" => self.handle_agent_get(request.params).await,
@@ -210,6 +865,7 @@ impl McpHandler {
             "agents/discover_by_capability" => self.handle_discover_by_capability(request.params).await,
             "agents/discover_by_role" => self.handle_discover_by_role(request.params).await,
             "agents/discover" => self.handle_discover_agents(request.params).await,
+            "agents/watch" => self.handle_agents_watch(request.params).await,
             "health" => self.handle_health(request.params).await,
             "cache/stats" => self.handle_cache_stats(request.params).await,
             "cache/clear" => self.handle_cache_clear(request.params).await,
@@ -217,8 +873,12 @@ impl McpHandler {
             "prompts/get" => self.handle_prompts_get(request.params).await,
             "resources/list" => self.handle_resources_list(request.params).await,
             "resources/read" => self.handle_resources_read(request.params).await,
+            "resources/subscribe" => self.handle_resources_subscribe(request.params).await,
+            "metrics" => self.handle_metrics(request.params).await,
             method => {
                 error!("Unknown method: {}", method);
+                self.metrics
+                    .record_request(method, start.elapsed(), Some(-32601));
                 return JsonRpcResponse::error(
                     request.id,
                     -32601,
@@ -231,25 +891,198 @@ impl McpHandler {
         let duration = start.elapsed();
         debug!("Method {} completed in {:?}", request.method, duration);
 
+        let error_code = result.as_ref().err().map(classify_error_code);
+        self.metrics
+            .record_request(&request.method, duration, error_code);
+
         match result {
             Ok(value) => JsonRpcResponse::success(request.id, value),
             Err(e) => {
                 error!("Error handling {}: {}", request.method, e);
-                JsonRpcResponse::error(
-                    request.id,
-                    -32603,
-                    "Internal error",
-                    Some(serde_json::json!({ "error": e.to_string() })),
-                )
+                // Prefer the structured taxonomy, then a bare explicit code,
+                // then fall back to a generic internal error.
+                if let Some(mcp) = e.downcast_ref::<McpError>() {
+                    JsonRpcResponse::error(request.id, mcp.code(), &mcp.to_string(), mcp.data())
+                } else if let Some(rpc) = e.downcast_ref::<RpcError>() {
+                    JsonRpcResponse::error(request.id, rpc.code, &rpc.message, None)
+                } else {
+                    JsonRpcResponse::error(
+                        request.id,
+                        -32603,
+                        "Internal error",
+                        Some(serde_json::json!({ "error": e.to_string() })),
+                    )
+                }
             }
         }
     }
 
-    async fn handle_initialize(&self, _params: Option<Value>) -> Result<Value> {
-        info!("MCP server initialized");
+    async fn handle_metrics(&self, _params: Option<Value>) -> Result<Value> {
+        let uptime_secs = self.start_time.elapsed().as_secs_f64();
+        let occupancy = self.definition_cache.lock().unwrap().len();
+        let text = self.metrics.render(uptime_secs, occupancy);
 
         Ok(serde_json::json!({
-            "protocolVersion": "2024-11-05",
+            "contentType": "text/plain; version=0.0.4",
+            "metrics": text,
+        }))
+    }
+
+    /// Entry point for a raw JSON-RPC payload that may be either a single
+    /// request object or a batch array. `auth_context` is the bearer token
+    /// presented alongside the payload (e.g. extracted from a transport-level
+    /// header), forwarded to every element's [`Self::handle_request`]. Returns
+    /// the serialized response value, or `None` when the payload was a lone
+    /// notification that warrants no reply. A payload that is neither an
+    /// object nor an array never parses into a request at all, so it always
+    /// gets a Parse error rather than being silently dropped.
+    pub async fn handle(
+        self: &Arc<Self>,
+        input: Value,
+        auth_context: Option<&str>,
+    ) -> Option<Value> {
+        match input {
+            Value::Array(elements) => Some(
+                self.handle_batch(elements, auth_context.map(String::from))
+                    .await,
+            ),
+            Value::Object(_) => self
+                .dispatch_element(input, auth_context)
+                .await
+                .map(|resp| serde_json::to_value(resp).unwrap_or(Value::Null)),
+            _ => {
+                let err = JsonRpcResponse::error(
+                    Value::Null,
+                    -32700,
+                    "Parse error",
+                    Some(serde_json::json!({
+                        "reason": "payload is not a JSON-RPC request object or batch array"
+                    })),
+                );
+                Some(serde_json::to_value(err).unwrap_or(Value::Null))
+            }
+        }
+    }
+
+    /// Process a batch of JSON-RPC requests, dispatching each element
+    /// concurrently and collecting the responses. Responses carry their own
+    /// id, so correlation survives out-of-order completion. Notifications
+    /// contribute no element, and an empty batch is itself an invalid request.
+    async fn handle_batch(
+        self: &Arc<Self>,
+        elements: Vec<Value>,
+        auth_context: Option<String>,
+    ) -> Value {
+        if elements.is_empty() {
+            let err = JsonRpcResponse::error(
+                Value::Null,
+                -32600,
+                "Invalid Request",
+                Some(serde_json::json!({ "reason": "empty batch" })),
+            );
+            return serde_json::to_value(err).unwrap_or(Value::Null);
+        }
+
+        let mut set = JoinSet::new();
+        for element in elements {
+            let handler = Arc::clone(self);
+            let auth_context = auth_context.clone();
+            set.spawn(async move {
+                handler
+                    .dispatch_element(element, auth_context.as_deref())
+                    .await
+            });
+        }
+
+        let mut responses = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok(Some(resp)) => responses.push(serde_json::to_value(resp).unwrap_or(Value::Null)),
+                Ok(None) => {}
+                Err(e) => error!("Batch element task failed: {}", e),
+            }
+        }
+
+        Value::Array(responses)
+    }
+
+    /// Dispatch a single batch element. Returns `None` for notifications
+    /// (elements with no `id`), which are executed for their side effects but
+    /// receive no response per the spec.
+    async fn dispatch_element(
+        &self,
+        element: Value,
+        auth_context: Option<&str>,
+    ) -> Option<JsonRpcResponse> {
+        #[derive(Deserialize)]
+        struct RawMessage {
+            #[serde(default)]
+            jsonrpc: Option<String>,
+            method: String,
+            #[serde(default)]
+            params: Option<Value>,
+            #[serde(default)]
+            id: Option<Value>,
+        }
+
+        let id = element.get("id").cloned();
+        let msg: RawMessage = match serde_json::from_value(element) {
+            Ok(msg) => msg,
+            Err(e) => {
+                // A malformed request with an id still gets an error reply; a
+                // malformed notification stays silent.
+                return id.map(|id| {
+                    JsonRpcResponse::error(
+                        id,
+                        -32600,
+                        "Invalid Request",
+                        Some(serde_json::json!({ "error": e.to_string() })),
+                    )
+                });
+            }
+        };
+
+        let request = JsonRpcRequest {
+            jsonrpc: msg.jsonrpc.unwrap_or_else(|| "2.0".to_string()),
+            method: msg.method,
+            params: msg.params,
+            id: msg.id.clone().unwrap_or(Value::Null),
+        };
+
+        let response = self.handle_request(request, auth_context).await;
+        msg.id.map(|_| response)
+    }
+
+    async fn handle_initialize(&self, params: Option<Value>) -> Result<Value> {
+        // A client may only initialize from a fresh or initializing connection.
+        if matches!(
+            self.state(),
+            ConnectionState::Ready | ConnectionState::ShuttingDown
+        ) {
+            return Err(RpcError {
+                code: -32600,
+                message: "Connection already initialized".to_string(),
+            }
+            .into());
+        }
+
+        // Echo the client's requested protocol version when supplied, else the
+        // version we implement.
+        let client_version = params
+            .as_ref()
+            .and_then(|p| p.get("protocolVersion"))
+            .and_then(|v| v.as_str());
+        if let Some(params) = &params {
+            if let Some(client_info) = params.get("clientInfo") {
+                info!("MCP client connected: {}", client_info);
+            }
+        }
+
+        self.set_state(ConnectionState::Initializing);
+        info!("MCP server initializing");
+
+        Ok(serde_json::json!({
+            "protocolVersion": client_version.unwrap_or("2024-11-05"),
             "serverInfo": {
                 "name": "orchestr8-mcp-server",
                 "version": env!("CARGO_PKG_VERSION"),
@@ -259,6 +1092,7 @@ impl McpHandler {
                     "query": true,
                     "list": true,
                     "get": true,
+                    "watch": true,
                 },
                 "cache": {
                     "stats": true,
@@ -271,54 +1105,113 @@ impl McpHandler {
                 "resources": {
                     "list": true,
                     "read": true,
+                    "subscribe": true,
+                    "listChanged": true,
+                    "pagination": { "cursor": true },
+                    "mimeTypes": ["application/vnd.orchestr8.agent+json"],
                 },
                 "health": true,
+                "metrics": true,
             }
         }))
     }
 
     async fn handle_agent_query(&self, params: Option<Value>) -> Result<Value> {
-        let query_params: AgentQueryParams = match params {
+        let mut query_params: AgentQueryParams = match params {
             Some(p) => serde_json::from_value(p)?,
             None => AgentQueryParams {
                 context: None,
                 role: None,
                 capability: None,
                 limit: Some(10),
+                cursor: None,
+                page_size: None,
             },
         };
 
+        let page_size = query_params
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        // A cursor carries its own filter, overriding anything the caller
+        // repeated, so a mismatched query can't silently page over the wrong
+        // result set.
+        let cursor = match &query_params.cursor {
+            Some(raw) => {
+                let decoded = PageCursor::decode(raw, self.generation())?;
+                query_params.context = decoded.context.clone();
+                query_params.role = decoded.role.clone();
+                query_params.capability = decoded.capability.clone();
+                Some(decoded)
+            }
+            None => None,
+        };
+
         let start = std::time::Instant::now();
 
-        // Check cache
+        // Only the first page is cacheable: later pages are cheap, one-off,
+        // and keyed by a cursor that's unique per call anyway.
         let cache_key = format!(
-            "query:{}:{}:{}",
+            "query:{}:{}:{}:{}",
             query_params.context.as_deref().unwrap_or(""),
             query_params.role.as_deref().unwrap_or(""),
-            query_params.capability.as_deref().unwrap_or("")
+            query_params.capability.as_deref().unwrap_or(""),
+            page_size,
         );
 
-        if let Some(cached) = self.cache.get(&cache_key) {
-            debug!("Cache hit for query: {}", cache_key);
-            return Ok(cached);
+        if cursor.is_none() {
+            if let Some(cached) = self.cache.get(&cache_key) {
+                debug!("Memory cache hit for query: {}", cache_key);
+                self.metrics.record_cache(true);
+                return Ok(cached);
+            }
+
+            // Fall back to the persistent tier, promoting hits into memory.
+            if let Some(persistent) = &self.persistent {
+                if let Some(cached) = persistent.get(CacheTier::Query, &cache_key) {
+                    debug!("Disk cache hit for query: {}", cache_key);
+                    self.metrics.record_cache(true);
+                    self.cache.put(cache_key, cached.clone());
+                    return Ok(cached);
+                }
+            }
+            self.metrics.record_cache(false);
         }
 
-        // Execute query
-        let agents = self.db.query_agents(&query_params)?;
+        // Execute query, then rank the candidates by BM25 relevance.
+        let agents = self.db.query_agents(&query_params).await?;
+        let (agents, reasoning, confidence) =
+            self.rank_and_explain(&query_text(&query_params), agents);
+
+        let (page, next_cursor) = paginate(
+            agents,
+            cursor.as_ref(),
+            page_size,
+            &query_params,
+            self.generation(),
+        );
         let query_time_ms = start.elapsed().as_secs_f64() * 1000.0;
 
         let result = AgentQueryResult {
-            agents,
-            reasoning: self.generate_reasoning(&query_params),
-            confidence: 0.95, // TODO: implement confidence scoring
+            agents: page,
+            reasoning,
+            confidence,
             cache_hit: false,
             query_time_ms,
+            next_cursor,
         };
 
         let result_json = serde_json::to_value(&result)?;
 
-        // Cache result
-        self.cache.put(cache_key, result_json.clone());
+        // Only write through the first page; later pages aren't cached (see
+        // above).
+        if cursor.is_none() {
+            if let Some(persistent) = &self.persistent {
+                persistent.put(CacheTier::Query, &cache_key, &result_json, None);
+            }
+            self.cache.put(cache_key, result_json.clone());
+        }
 
         Ok(result_json)
     }
@@ -335,13 +1228,11 @@ impl McpHandler {
             None => ListParams { plugin: None },
         };
 
+        let all_agents = self.agents.lock().unwrap();
         let agents: Vec<&AgentMetadata> = if let Some(plugin) = &list_params.plugin {
-            self.agents
-                .iter()
-                .filter(|a| &a.plugin == plugin)
-                .collect()
+            all_agents.iter().filter(|a| &a.plugin == plugin).collect()
         } else {
-            self.agents.iter().collect()
+            all_agents.iter().collect()
         };
 
         Ok(serde_json::json!({
@@ -360,8 +1251,11 @@ impl McpHandler {
 
         let agent = self
             .agents
+            .lock()
+            .unwrap()
             .iter()
             .find(|a| a.name == get_params.name)
+            .cloned()
             .ok_or_else(|| anyhow::anyhow!("Agent not found: {}", get_params.name))?;
 
         Ok(serde_json::to_value(agent)?)
@@ -372,7 +1266,8 @@ impl McpHandler {
         let memory_mb = get_memory_usage_mb();
         let cache_stats = self.cache.stats();
 
-        let db_size_mb = self.db.get_size_mb()?;
+        let db_size_mb = self.db.get_size_mb().await?;
+        let agents = self.agents.lock().unwrap();
 
         let health = HealthStatus {
             status: "healthy".to_string(),
@@ -388,9 +1283,8 @@ impl McpHandler {
                 size_mb: db_size_mb,
             },
             indexes: IndexStats {
-                agents: self.agents.len(),
-                plugins: self
-                    .agents
+                agents: agents.len(),
+                plugins: agents
                     .iter()
                     .map(|a| a.plugin.as_str())
                     .collect::<std::collections::HashSet<_>>()
@@ -403,17 +1297,38 @@ impl McpHandler {
 
     async fn handle_cache_stats(&self, _params: Option<Value>) -> Result<Value> {
         let stats = self.cache.stats();
-        Ok(serde_json::json!({
+        let mut response = serde_json::json!({
+            "memory": {
+                "hits": stats.hits,
+                "misses": stats.misses,
+                "hit_rate": stats.hit_rate(),
+                "size": stats.size,
+            },
+            // Flattened memory stats retained for backward compatibility.
             "hits": stats.hits,
             "misses": stats.misses,
             "hit_rate": stats.hit_rate(),
             "size": stats.size,
-        }))
+        });
+
+        if let Some(persistent) = &self.persistent {
+            let disk = persistent.stats();
+            response["disk"] = serde_json::json!({
+                "hits": disk.hits,
+                "misses": disk.misses,
+                "size": disk.size,
+            });
+        }
+
+        Ok(response)
     }
 
     async fn handle_cache_clear(&self, _params: Option<Value>) -> Result<Value> {
         self.cache.clear();
-        info!("Cache cleared");
+        if let Some(persistent) = &self.persistent {
+            persistent.clear();
+        }
+        info!("Cache cleared (memory and disk)");
         Ok(serde_json::json!({ "cleared": true }))
     }
 
@@ -426,7 +1341,7 @@ impl McpHandler {
 
         let def_params: DefParams = serde_json::from_value(params.unwrap_or(Value::Null))?;
 
-        // Check definition cache first
+        // Check in-memory definition cache first
         {
             let mut cache = self.definition_cache.lock().unwrap();
             if let Some(def) = cache.get(&def_params.name) {
@@ -435,23 +1350,61 @@ impl McpHandler {
             }
         }
 
+        // Fall back to the persistent tier, promoting hits into memory.
+        if let Some(persistent) = &self.persistent {
+            if let Some(cached) = persistent.get(CacheTier::Definition, &def_params.name) {
+                if let Ok(def) = serde_json::from_value::<AgentDefinition>(cached.clone()) {
+                    debug!("Disk definition cache hit for: {}", def_params.name);
+                    self.definition_cache
+                        .lock()
+                        .unwrap()
+                        .put(def_params.name.clone(), def);
+                }
+                return Ok(cached);
+            }
+        }
+
         // Get file path from database
-        let file_path = self.db.get_agent_file_path(&def_params.name)?;
+        let file_path = self.db.get_agent_file_path(&def_params.name).await?;
+
+        // Try the disk cache before parsing: a warm entry whose source mtime
+        // still matches skips the parse entirely.
+        if let Some(disk) = &self.definition_disk {
+            if let Some(def) = disk.get(&def_params.name, file_path.as_path()) {
+                let definition_json = serde_json::to_value(&def)?;
+                self.definition_cache
+                    .lock()
+                    .unwrap()
+                    .put(def_params.name.clone(), def);
+                return Ok(definition_json);
+            }
+        }
 
         // Load full definition via JIT
         let start = std::time::Instant::now();
         let definition = self.loader.get_agent_definition_jit(&file_path)?;
-        let load_time = start.elapsed().as_secs_f64() * 1000.0;
+        let elapsed = start.elapsed();
+        self.metrics.record_jit_load(elapsed);
+        let load_time = elapsed.as_secs_f64() * 1000.0;
 
         debug!("Loaded agent definition in {:.2}ms", load_time);
 
-        // Cache the definition
+        // Persist the freshly parsed definition to the disk cache.
+        if let Some(disk) = &self.definition_disk {
+            disk.put(&def_params.name, file_path.as_path(), &definition);
+        }
+
+        // Write through to both cache tiers.
+        let definition_json = serde_json::to_value(&definition)?;
+        if let Some(persistent) = &self.persistent {
+            persistent.put(CacheTier::Definition, &def_params.name, &definition_json, None);
+        }
         {
             let mut cache = self.definition_cache.lock().unwrap();
-            cache.put(def_params.name.clone(), definition.clone());
+            cache.put(def_params.name.clone(), definition);
         }
 
-        Ok(serde_json::to_value(definition)?)
+        Ok(definition_json)
     }
 
     /// Discover agents by capability
@@ -470,14 +1423,20 @@ impl McpHandler {
             role: None,
             capability: Some(cap_params.capability),
             limit: cap_params.limit.or(Some(10)),
+            cursor: None,
+            page_size: None,
         };
 
-        let agents = self.db.query_agents(&query_params)?;
+        let agents = self.db.query_agents(&query_params).await?;
+        let (agents, reasoning, confidence) =
+            self.rank_and_explain(&query_text(&query_params), agents);
 
         Ok(serde_json::json!({
             "agents": agents,
             "total": agents.len(),
             "discovery_method": "capability",
+            "reasoning": reasoning,
+            "confidence": confidence,
         }))
     }
 
@@ -497,14 +1456,20 @@ impl McpHandler {
             role: Some(role_params.role),
             capability: None,
             limit: role_params.limit.or(Some(10)),
+            cursor: None,
+            page_size: None,
         };
 
-        let agents = self.db.query_agents(&query_params)?;
+        let agents = self.db.query_agents(&query_params).await?;
+        let (agents, reasoning, confidence) =
+            self.rank_and_explain(&query_text(&query_params), agents);
 
         Ok(serde_json::json!({
             "agents": agents,
             "total": agents.len(),
             "discovery_method": "role",
+            "reasoning": reasoning,
+            "confidence": confidence,
         }))
     }
 
@@ -520,23 +1485,142 @@ impl McpHandler {
             role: Option<String>,
             #[serde(default)]
             limit: Option<usize>,
+            /// Ranking strategy: "lexical" (default), "semantic", or "hybrid".
+            #[serde(default)]
+            mode: Option<String>,
+            #[serde(default)]
+            cursor: Option<String>,
+            #[serde(default)]
+            page_size: Option<usize>,
         }
 
         let discover_params: DiscoverParams = serde_json::from_value(params.unwrap_or(Value::Null))?;
+        let mode = discover_params.mode.clone().unwrap_or_else(|| "lexical".to_string());
 
-        let query_params = AgentQueryParams {
+        let page_size = discover_params
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let cursor = match &discover_params.cursor {
+            Some(raw) => Some(PageCursor::decode(raw, self.generation())?),
+            None => None,
+        };
+
+        let mut query_params = AgentQueryParams {
             context: discover_params.query,
             role: discover_params.role,
             capability: discover_params.capability,
             limit: discover_params.limit.or(Some(10)),
+            cursor: discover_params.cursor,
+            page_size: discover_params.page_size,
         };
+        // A cursor carries its own filter, overriding anything the caller
+        // repeated, so a mismatched query can't silently page over the wrong
+        // result set.
+        if let Some(c) = &cursor {
+            query_params.context = c.context.clone();
+            query_params.role = c.role.clone();
+            query_params.capability = c.capability.clone();
+        }
+
+        let agents = self.db.query_agents(&query_params).await?;
+        let limit = query_params.limit.unwrap_or(10);
+        let context = query_params.context.clone().unwrap_or_default();
 
-        let agents = self.db.query_agents(&query_params)?;
+        let (agents, reasoning, confidence) = match mode.as_str() {
+            "semantic" => self.rank_semantic(&context, agents, limit),
+            "hybrid" => self.rank_hybrid(&context, agents, limit),
+            _ => self.rank_and_explain(&query_text(&query_params), agents),
+        };
+
+        let (page, next_cursor) = paginate(
+            agents,
+            cursor.as_ref(),
+            page_size,
+            &query_params,
+            self.generation(),
+        );
+        let total = page.len();
 
         Ok(serde_json::json!({
-            "agents": agents,
-            "total": agents.len(),
+            "agents": page,
+            "total": total,
             "discovery_method": "multi-criteria",
+            "mode": mode,
+            "reasoning": reasoning,
+            "confidence": confidence,
+            "next_cursor": next_cursor,
+        }))
+    }
+
+    /// Push-based alternative to polling `agents/list`: returns the current
+    /// index (optionally narrowed by `capability`/`role`) and, in `"subscribe"`
+    /// mode, registers the caller's filter so later index changes are pushed
+    /// as `notifications/agents/updated` over whatever transport is driving
+    /// [`Self::watch_agent_events`] (e.g. an SSE route). `"snapshot"` mode (the
+    /// default) returns the current state with no ongoing subscription.
+    async fn handle_agents_watch(&self, params: Option<Value>) -> Result<Value> {
+        #[derive(Deserialize)]
+        struct WatchParams {
+            #[serde(default)]
+            mode: Option<String>,
+            #[serde(default)]
+            capability: Option<String>,
+            #[serde(default)]
+            role: Option<String>,
+        }
+
+        let watch_params: WatchParams = match params {
+            Some(p) => serde_json::from_value(p).map_err(|e| McpError::InvalidParams(e.to_string()))?,
+            None => WatchParams {
+                mode: None,
+                capability: None,
+                role: None,
+            },
+        };
+
+        let mode = watch_params.mode.unwrap_or_else(|| "snapshot".to_string());
+        let filter = AgentWatchFilter {
+            capability: watch_params.capability,
+            role: watch_params.role,
+        };
+
+        let snapshot: Vec<AgentMetadata> = self
+            .agents
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| filter.matches(a))
+            .cloned()
+            .collect();
+
+        let subscription_id = if mode == "subscribe" {
+            let id = format!(
+                "watch-{}",
+                self.subscription_counter.fetch_add(1, Ordering::SeqCst)
+            );
+            self.watch_subscriptions
+                .lock()
+                .unwrap()
+                .insert(id.clone(), filter);
+            Some(id)
+        } else {
+            None
+        };
+
+        info!(
+            "agents/watch ({}): {} agent(s) in initial snapshot",
+            mode,
+            snapshot.len()
+        );
+
+        Ok(serde_json::json!({
+            "agents": snapshot,
+            "total": snapshot.len(),
+            "mode": mode,
+            "subscriptionId": subscription_id,
+            "generation": self.generation(),
         }))
     }
 
@@ -559,36 +1643,15 @@ impl McpHandler {
                             // Convert filename to prompt name (kebab-case stays as-is)
                             let prompt_name = filename.to_string();
 
-                            // Try to read and parse file for description
-                            let description = match std::fs::read_to_string(&path) {
-                                Ok(content) => {
-                                    // Try to extract YAML frontmatter
-                                    match crate::loader::extract_frontmatter(&content) {
-                                        Ok(frontmatter) => {
-                                            // Parse YAML to get description
-                                            match serde_yaml::from_str::<serde_json::Value>(&frontmatter) {
-                                                Ok(yaml) => {
-                                                    yaml.get("description")
-                                                        .and_then(|v| v.as_str())
-                                                        .unwrap_or(&prompt_name)
-                                                        .to_string()
-                                                }
-                                                Err(_) => prompt_name.replace("-", " ").to_uppercase(),
-                                            }
-                                        }
-                                        Err(_) => {
-                                            // Fallback to filename
-                                            prompt_name.replace("-", " ").to_uppercase()
-                                        }
-                                    }
-                                }
-                                Err(_) => prompt_name.replace("-", " ").to_uppercase(),
-                            };
+                            // Read and parse the file for its description and arguments.
+                            let content = std::fs::read_to_string(&path).unwrap_or_default();
+                            let (description, arguments) =
+                                parse_prompt_metadata(&content, &prompt_name);
 
                             prompts.push(PromptInfo {
                                 name: prompt_name,
                                 description,
-                                arguments: Vec::new(), // Will implement argument parsing later
+                                arguments,
                             });
                         }
                     }
@@ -613,6 +1676,8 @@ impl McpHandler {
         #[derive(Deserialize)]
         struct PromptsGetParams {
             name: String,
+            #[serde(default)]
+            arguments: std::collections::HashMap<String, Value>,
         }
 
         let get_params: PromptsGetParams = serde_json::from_value(params.unwrap_or(Value::Null))?;
@@ -629,21 +1694,28 @@ impl McpHandler {
         let content = std::fs::read_to_string(&prompt_path)
             .map_err(|e| anyhow::anyhow!("Prompt not found: {} (error: {})", get_params.name, e))?;
 
-        // Extract frontmatter for description
-        let description = match crate::loader::extract_frontmatter(&content) {
-            Ok(frontmatter) => {
-                match serde_yaml::from_str::<serde_json::Value>(&frontmatter) {
-                    Ok(yaml) => {
-                        yaml.get("description")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or(&get_params.name)
-                            .to_string()
-                    }
-                    Err(_) => get_params.name.replace("-", " ").to_uppercase(),
-                }
+        let (description, arguments) = parse_prompt_metadata(&content, &get_params.name);
+
+        // Every declared required argument must be supplied.
+        let missing: Vec<String> = arguments
+            .iter()
+            .filter(|a| a.required && !get_params.arguments.contains_key(&a.name))
+            .map(|a| a.name.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(RpcError {
+                code: -32602,
+                message: format!("Missing required arguments: {}", missing.join(", ")),
             }
-            Err(_) => get_params.name.replace("-", " ").to_uppercase(),
-        };
+            .into());
+        }
+
+        // Substitute `{{name}}` placeholders with the caller-supplied values.
+        let mut rendered = content;
+        for (name, value) in &get_params.arguments {
+            let replacement = value.as_str().map(|s| s.to_string()).unwrap_or_else(|| value.to_string());
+            rendered = rendered.replace(&format!("{{{{{name}}}}}"), &replacement);
+        }
 
         // Return as MCP prompt format with messages array
         Ok(serde_json::json!({
@@ -651,7 +1723,7 @@ impl McpHandler {
             "messages": [
                 {
                     "role": "user",
-                    "content": content,
+                    "content": rendered,
                 }
             ]
         }))
@@ -667,7 +1739,8 @@ impl McpHandler {
         }
 
         let list_params: ResourcesListParams = match params {
-            Some(p) => serde_json::from_value(p)?,
+            Some(p) => serde_json::from_value(p)
+                .map_err(|e| McpError::InvalidParams(e.to_string()))?,
             None => ResourcesListParams { cursor: None, limit: None },
         };
 
@@ -685,9 +1758,11 @@ impl McpHandler {
             role: None,
             capability: None,
             limit: Some(10000),
+            cursor: None,
+            page_size: None,
         };
 
-        let mut all_agents = self.db.query_agents(&query_params)?;
+        let mut all_agents = self.db.query_agents(&query_params).await?;
 
         // Sort alphabetically by name
         all_agents.sort_by(|a, b| a.name.cmp(&b.name));
@@ -730,34 +1805,56 @@ impl McpHandler {
             uri: String,
         }
 
-        let read_params: ResourcesReadParams = serde_json::from_value(params.unwrap_or(Value::Null))?;
+        let read_params: ResourcesReadParams = serde_json::from_value(params.unwrap_or(Value::Null))
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?;
         debug!("Handling resources/read request for URI: {}", read_params.uri);
 
         // Parse URI format: agent://name
         let uri_parts: Vec<&str> = read_params.uri.split("://").collect();
         if uri_parts.len() != 2 || uri_parts[0] != "agent" {
-            return Err(anyhow::anyhow!("Invalid resource URI format. Expected 'agent://name', got '{}'", read_params.uri));
+            return Err(McpError::InvalidParams(format!(
+                "Invalid resource URI format. Expected 'agent://name', got '{}'",
+                read_params.uri
+            ))
+            .into());
         }
 
         let agent_name = uri_parts[1];
 
-        // Find agent metadata by name
-        let agent = self.agents
-            .iter()
-            .find(|a| a.name == agent_name)
-            .ok_or_else(|| anyhow::anyhow!("Resource not found: {}", read_params.uri))?;
+        // Confirm the agent is still in the index before touching disk.
+        if !self.agents.lock().unwrap().iter().any(|a| a.name == agent_name) {
+            return Err(McpError::ResourceNotFound(read_params.uri.clone()).into());
+        }
 
         // Get file path from database
-        let file_path = self.db.get_agent_file_path(agent_name)?;
-
-        // Load full definition via JIT (with caching)
-        let start = std::time::Instant::now();
-        let definition = self.loader.get_agent_definition_jit(&file_path)?;
-        let load_time = start.elapsed().as_secs_f64() * 1000.0;
-
-        debug!("Loaded agent definition in {:.2}ms", load_time);
+        let file_path = self.db.get_agent_file_path(agent_name).await?;
+
+        // Prefer the disk cache: a warm entry whose source mtime still matches
+        // skips the JIT parse entirely.
+        let definition = if let Some(def) = self
+            .definition_disk
+            .as_ref()
+            .and_then(|disk| disk.get(agent_name, file_path.as_path()))
+        {
+            def
+        } else {
+            // Load full definition via JIT (with caching)
+            let start = std::time::Instant::now();
+            let definition = self.loader.get_agent_definition_jit(&file_path)?;
+            let elapsed = start.elapsed();
+            self.metrics.record_jit_load(elapsed);
+            let load_time = elapsed.as_secs_f64() * 1000.0;
+
+            debug!("Loaded agent definition in {:.2}ms", load_time);
+
+            // Persist the freshly parsed definition to the disk cache.
+            if let Some(disk) = &self.definition_disk {
+                disk.put(agent_name, file_path.as_path(), &definition);
+            }
+            definition
+        };
 
-        // Cache the definition
+        // Cache the definition in memory
         {
             let mut cache = self.definition_cache.lock().unwrap();
             cache.put(agent_name.to_string(), definition.clone());
@@ -766,7 +1863,7 @@ impl McpHandler {
         // Convert definition to string for MCP resource content
         let content = serde_json::to_string_pretty(&definition)?;
 
-        info!("Retrieved resource: {} ({:.2}ms)", read_params.uri, load_time);
+        info!("Retrieved resource: {}", read_params.uri);
 
         Ok(serde_json::json!({
             "uri": read_params.uri,
@@ -775,25 +1872,666 @@ impl McpHandler {
         }))
     }
 
-    fn generate_reasoning(&self, params: &AgentQueryParams) -> String {
-        let mut parts = Vec::new();
+    /// Current index generation.
+    pub fn generation(&self) -> u64 {
+        self.index_generation.load(Ordering::SeqCst)
+    }
+
+    /// Record that the agent index changed: bump the generation counter and
+    /// append the new resource to the catalog so active subscribers observe it
+    /// as a delta. Returns the new generation.
+    pub fn register_resource(&self, resource: ResourceMetadata) -> u64 {
+        let generation = self.index_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.resource_catalog
+            .lock()
+            .unwrap()
+            .push(GenerationedResource {
+                generation,
+                resource,
+            });
+        generation
+    }
 
-        if let Some(context) = &params.context {
-            parts.push(format!("context matching '{}'", context));
+    /// Build a [`BatchIterator`] over the resources newer than `since`. A
+    /// `None` cursor drains the entire catalog; `Some(g)` resumes strictly
+    /// after generation `g`.
+    fn resource_deltas(&self, since: Option<u64>, batch_size: usize) -> BatchIterator {
+        let catalog = self.resource_catalog.lock().unwrap();
+        let high_water = self.generation();
+        let items: Vec<ResourceMetadata> = catalog
+            .iter()
+            .filter(|g| match since {
+                Some(c) => g.generation > c,
+                None => true,
+            })
+            .map(|g| g.resource.clone())
+            .collect();
+        BatchIterator::new(items, batch_size, high_water)
+    }
+
+    /// Register interest in the resource catalog. In `"snapshot"` mode the
+    /// caller drains the current deltas and the subscription ends; in
+    /// `"subscribe"` mode the id is retained so later changes can be streamed
+    /// as `notifications/resources/updated`.
+    async fn handle_resources_subscribe(&self, params: Option<Value>) -> Result<Value> {
+        #[derive(Deserialize)]
+        struct SubscribeParams {
+            #[serde(default)]
+            mode: Option<String>,
+            #[serde(default)]
+            cursor: Option<String>,
+            #[serde(default)]
+            limit: Option<usize>,
         }
-        if let Some(role) = &params.role {
-            parts.push(format!("role '{}'", role));
+
+        let sub_params: SubscribeParams = match params {
+            Some(p) => serde_json::from_value(p)?,
+            None => SubscribeParams {
+                mode: None,
+                cursor: None,
+                limit: None,
+            },
+        };
+
+        let mode = sub_params.mode.unwrap_or_else(|| "snapshot".to_string());
+        let batch_size = sub_params.limit.unwrap_or(RESOURCE_BATCH_SIZE).min(500);
+        let since = sub_params.cursor.and_then(|c| c.parse::<u64>().ok());
+
+        let mut iter = self.resource_deltas(since, batch_size);
+        let batch = iter.next_batch().unwrap_or_default();
+        let high_water = iter.high_water();
+
+        // In subscribe mode retain the cursor so future deltas can be pushed.
+        let subscription_id = if mode == "subscribe" {
+            let id = format!("sub-{}", self.subscription_counter.fetch_add(1, Ordering::SeqCst));
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .insert(id.clone(), high_water);
+            Some(id)
+        } else {
+            None
+        };
+
+        info!(
+            "resources/subscribe ({}): {} delta(s) at generation {}",
+            mode,
+            batch.len(),
+            high_water
+        );
+
+        Ok(serde_json::json!({
+            "resources": batch,
+            "cursor": high_water.to_string(),
+            "mode": mode,
+            "complete": iter.is_drained(),
+            "subscriptionId": subscription_id,
+        }))
+    }
+
+    /// Build a `notifications/resources/updated` JSON-RPC notification carrying
+    /// the given resource deltas. Notifications carry no `id`.
+    pub fn resources_updated_notification(resources: &[ResourceMetadata]) -> Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/resources/updated",
+            "params": { "resources": resources },
+        })
+    }
+
+    /// Subscribe to the raw agent-delta broadcast stream. A transport driving
+    /// `agents/watch` (e.g. an SSE route) holds one receiver per connection,
+    /// narrows each delta to that connection's registered
+    /// [`AgentWatchFilter`] via [`Self::filter_agent_delta`], and forwards the
+    /// non-empty result as [`Self::agents_updated_notification`].
+    pub fn watch_agent_events(&self) -> broadcast::Receiver<AgentDelta> {
+        self.agent_events.subscribe()
+    }
+
+    /// Narrow `delta` to subscription `id`'s registered filter. Returns `None`
+    /// when the subscription is unknown (never registered, or already
+    /// dropped) or the filtered delta has nothing left to report.
+    pub fn filter_agent_delta(&self, id: &str, delta: &AgentDelta) -> Option<AgentDelta> {
+        let filter = self.watch_subscriptions.lock().unwrap().get(id).cloned()?;
+        let filtered = delta.filtered(&filter);
+        (!filtered.is_empty()).then_some(filtered)
+    }
+
+    /// Drop a `agents/watch` subscription, e.g. when its connection closes.
+    pub fn unwatch_agents(&self, id: &str) {
+        self.watch_subscriptions.lock().unwrap().remove(id);
+    }
+
+    /// Build a `notifications/agents/updated` JSON-RPC notification carrying
+    /// an agent-index delta. Notifications carry no `id`.
+    pub fn agents_updated_notification(delta: &AgentDelta) -> Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/agents/updated",
+            "params": delta,
+        })
+    }
+
+    /// React to a filesystem event on one agent definition file under
+    /// `agent_dir`: re-run it through [`AgentLoader`], update the in-memory
+    /// index, `definition_cache`, and `agent_embeddings` accordingly, and
+    /// broadcast the resulting delta to `agents/watch` subscribers. A reload
+    /// failure (the file may be mid-write) is logged and otherwise ignored,
+    /// leaving any existing entry untouched rather than dropping it.
+    ///
+    /// Agent files are named `<name>.md` under `agent_dir` (mirroring
+    /// `handle_prompts_get`'s convention for the sibling `commands` directory),
+    /// so a [`AgentFileChange::Removed`] event whose path was never observed
+    /// by this process yet (e.g. an agent present at startup that's never
+    /// changed since) falls back to the file stem as its name.
+    pub fn apply_agent_file_change(&self, path: &std::path::Path, change: AgentFileChange) {
+        let delta = match change {
+            AgentFileChange::Removed => {
+                let name = match self.agent_paths.lock().unwrap().remove(path) {
+                    Some(name) => name,
+                    None => match path.file_stem().and_then(|s| s.to_str()) {
+                        Some(stem) => stem.to_string(),
+                        None => return,
+                    },
+                };
+
+                let removed = self
+                    .agents
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|a| a.name == name);
+                if !removed {
+                    return;
+                }
+                self.agents.lock().unwrap().retain(|a| a.name != name);
+                self.agent_embeddings.lock().unwrap().remove(&name);
+                self.definition_cache.lock().unwrap().pop(&name);
+
+                AgentDelta {
+                    added: Vec::new(),
+                    updated: Vec::new(),
+                    removed: vec![name],
+                }
+            }
+            AgentFileChange::CreatedOrModified => {
+                let definition = match self.loader.get_agent_definition_jit(path) {
+                    Ok(def) => def,
+                    Err(e) => {
+                        warn!("agents/watch: failed to reload {}: {}", path.display(), e);
+                        return;
+                    }
+                };
+                let metadata = definition.metadata.clone();
+                let name = metadata.name.clone();
+
+                self.agent_paths
+                    .lock()
+                    .unwrap()
+                    .insert(path.to_path_buf(), name.clone());
+                self.agent_embeddings
+                    .lock()
+                    .unwrap()
+                    .insert(name.clone(), embed(&agent_embed_text(&metadata)));
+                self.definition_cache
+                    .lock()
+                    .unwrap()
+                    .put(name.clone(), definition);
+
+                let mut agents = self.agents.lock().unwrap();
+                let is_update = match agents.iter_mut().find(|a| a.name == name) {
+                    Some(slot) => {
+                        *slot = metadata.clone();
+                        true
+                    }
+                    None => {
+                        agents.push(metadata.clone());
+                        false
+                    }
+                };
+                drop(agents);
+
+                if is_update {
+                    AgentDelta {
+                        added: Vec::new(),
+                        updated: vec![metadata],
+                        removed: Vec::new(),
+                    }
+                } else {
+                    AgentDelta {
+                        added: vec![metadata],
+                        updated: Vec::new(),
+                        removed: Vec::new(),
+                    }
+                }
+            }
+        };
+
+        self.index_generation.fetch_add(1, Ordering::SeqCst);
+        info!(
+            "agents/watch: +{} ~{} -{} (generation {})",
+            delta.added.len(),
+            delta.updated.len(),
+            delta.removed.len(),
+            self.generation()
+        );
+        // No active receivers is not an error -- it just means nobody is
+        // watching right now.
+        let _ = self.agent_events.send(delta);
+    }
+
+    /// Spawn a background task that watches `agent_dir` with `notify` and
+    /// keeps the agent index live: every create/modify/delete under it is
+    /// routed through [`Self::apply_agent_file_change`]. Runs for the life of
+    /// the process. Mirrors [`Self::spawn_refresher`]'s shape (an `Arc<Self>`
+    /// task handle returned to the caller to hold onto), but reacts to
+    /// filesystem events directly instead of polling on an interval.
+    pub fn spawn_agent_watcher(self: &Arc<Self>) -> notify::Result<tokio::task::JoinHandle<()>>
+    where
+        S: 'static,
+    {
+        use notify::{RecursiveMode, Watcher};
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        })?;
+        watcher.watch(&self.agent_dir, RecursiveMode::NonRecursive)?;
+
+        let handler = Arc::clone(self);
+        Ok(tokio::spawn(async move {
+            // Keep the watcher alive for as long as this task runs; dropping
+            // it would stop event delivery.
+            let _watcher = watcher;
+            while let Some(event) = rx.recv().await {
+                let change = match event.kind {
+                    notify::EventKind::Create(_) | notify::EventKind::Modify(_) => {
+                        AgentFileChange::CreatedOrModified
+                    }
+                    notify::EventKind::Remove(_) => AgentFileChange::Removed,
+                    _ => continue,
+                };
+                for path in event.paths {
+                    if path.extension().map_or(true, |ext| ext != "md") {
+                        continue;
+                    }
+                    handler.apply_agent_file_change(&path, change);
+                }
+            }
+        }))
+    }
+
+    /// Rank a candidate set by BM25 relevance to `query` and describe the
+    /// ranking. When no query terms are supplied the candidates keep their
+    /// index order and confidence is zero.
+    fn rank_and_explain(
+        &self,
+        query: &str,
+        agents: Vec<AgentMetadata>,
+    ) -> (Vec<AgentMetadata>, String, f64) {
+        let (ranked, matched, confidence) = rank_agents(query, agents);
+
+        let reasoning = if query.trim().is_empty() {
+            "Listing all available agents".to_string()
+        } else if matched.is_empty() {
+            "No query terms matched; returning agents in index order".to_string()
+        } else {
+            format!("Ranked by BM25 relevance on: {}", matched.join(", "))
+        };
+
+        (ranked, reasoning, confidence)
+    }
+
+    /// Embedding for `query`, served from the LRU cache or computed and cached.
+    fn query_embedding(&self, query: &str) -> Vec<f32> {
+        {
+            let mut cache = self.query_embedding_cache.lock().unwrap();
+            if let Some(v) = cache.get(query) {
+                return v.clone();
+            }
         }
-        if let Some(capability) = &params.capability {
-            parts.push(format!("capability '{}'", capability));
+        let v = embed(query);
+        let mut cache = self.query_embedding_cache.lock().unwrap();
+        cache.put(query.to_string(), v.clone());
+        v
+    }
+
+    /// Embedding for an agent: the precomputed index vector when available,
+    /// otherwise computed on the fly (e.g. for agents loaded after startup).
+    fn agent_embedding(&self, agent: &AgentMetadata) -> Vec<f32> {
+        self.agent_embeddings
+            .lock()
+            .unwrap()
+            .get(&agent.name)
+            .cloned()
+            .unwrap_or_else(|| embed(&agent_embed_text(agent)))
+    }
+
+    /// Rank candidates by cosine similarity to the embedded `query`, keeping
+    /// the top `k`. Confidence is the best similarity, clamped to `[0, 1]`.
+    fn rank_semantic(
+        &self,
+        query: &str,
+        agents: Vec<AgentMetadata>,
+        k: usize,
+    ) -> (Vec<AgentMetadata>, String, f64) {
+        if query.trim().is_empty() || agents.is_empty() {
+            return (agents, "Listing all available agents".to_string(), 0.0);
         }
 
-        if parts.is_empty() {
-            "Listing all available agents".to_string()
+        let q = self.query_embedding(query);
+        let mut scored: Vec<(f32, AgentMetadata)> = agents
+            .into_iter()
+            .map(|a| (cosine(&q, &self.agent_embedding(&a)), a))
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(k.max(1));
+
+        let confidence = scored
+            .first()
+            .map(|(s, _)| (*s as f64).clamp(0.0, 1.0))
+            .unwrap_or(0.0);
+        let ranked = scored.into_iter().map(|(_, a)| a).collect();
+        (
+            ranked,
+            format!("Ranked by semantic similarity to '{}'", query),
+            confidence,
+        )
+    }
+
+    /// Rank candidates by a linear blend of normalized BM25 and cosine
+    /// similarity, keeping the top `k`.
+    fn rank_hybrid(
+        &self,
+        query: &str,
+        agents: Vec<AgentMetadata>,
+        k: usize,
+    ) -> (Vec<AgentMetadata>, String, f64) {
+        if query.trim().is_empty() || agents.is_empty() {
+            return (agents, "Listing all available agents".to_string(), 0.0);
+        }
+
+        let (bm, matched) = bm25_scores(query, &agents);
+        let max_bm = bm.iter().cloned().fold(0.0_f64, f64::max);
+        let q = self.query_embedding(query);
+
+        let mut scored: Vec<(f64, AgentMetadata)> = agents
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let cos = (cosine(&q, &self.agent_embedding(&a)) as f64).max(0.0);
+                let bm_norm = if max_bm > 0.0 { bm[i] / max_bm } else { 0.0 };
+                (HYBRID_ALPHA * bm_norm + (1.0 - HYBRID_ALPHA) * cos, a)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(k.max(1));
+
+        let confidence = scored.first().map(|(s, _)| s.clamp(0.0, 1.0)).unwrap_or(0.0);
+        let reasoning = if matched.is_empty() {
+            format!("Ranked by hybrid relevance to '{}'", query)
         } else {
-            format!("Found agents for: {}", parts.join(", "))
+            format!("Ranked by hybrid relevance on: {}", matched.join(", "))
+        };
+        let ranked = scored.into_iter().map(|(_, a)| a).collect();
+        (ranked, reasoning, confidence)
+    }
+}
+
+/// Parse a command file's YAML frontmatter into its description and declared
+/// prompt arguments. Falls back to a humanized name when the frontmatter is
+/// absent or malformed.
+fn parse_prompt_metadata(content: &str, name: &str) -> (String, Vec<PromptArgument>) {
+    let fallback = || name.replace('-', " ").to_uppercase();
+
+    let frontmatter = match crate::loader::extract_frontmatter(content) {
+        Ok(fm) => fm,
+        Err(_) => return (fallback(), Vec::new()),
+    };
+    let yaml: serde_json::Value = match serde_yaml::from_str(&frontmatter) {
+        Ok(yaml) => yaml,
+        Err(_) => return (fallback(), Vec::new()),
+    };
+
+    let description = yaml
+        .get("description")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(fallback);
+    let arguments = yaml
+        .get("arguments")
+        .cloned()
+        .and_then(|v| serde_json::from_value::<Vec<PromptArgument>>(v).ok())
+        .unwrap_or_default();
+
+    (description, arguments)
+}
+
+/// Map a handler error to the JSON-RPC code it should surface, consulting the
+/// structured [`McpError`] taxonomy first, then a bare [`RpcError`] code, and
+/// defaulting to the generic internal-error code.
+fn classify_error_code(e: &anyhow::Error) -> i32 {
+    if let Some(mcp) = e.downcast_ref::<McpError>() {
+        mcp.code()
+    } else if let Some(rpc) = e.downcast_ref::<RpcError>() {
+        rpc.code
+    } else {
+        -32603
+    }
+}
+
+/// Extend a refresh backoff window after a failed reload: double the current
+/// delay (starting from `base`) up to `cap`, and set the deadline from `now`.
+fn bump_backoff(
+    backoff: &mut Option<(std::time::Instant, std::time::Duration)>,
+    base: std::time::Duration,
+    cap: std::time::Duration,
+    now: std::time::Instant,
+) {
+    let next = match backoff {
+        Some((_, d)) => (*d * 2).min(cap),
+        None => base.min(cap),
+    };
+    *backoff = Some((now + next, next));
+}
+
+/// Concatenate the free-text fields of a query into a single search string.
+fn query_text(params: &AgentQueryParams) -> String {
+    let mut parts = Vec::new();
+    if let Some(context) = &params.context {
+        parts.push(context.as_str());
+    }
+    if let Some(role) = &params.role {
+        parts.push(role.as_str());
+    }
+    if let Some(capability) = &params.capability {
+        parts.push(capability.as_str());
+    }
+    parts.join(" ")
+}
+
+/// Slice a ranked agent list into one page, starting right after
+/// `cursor.after` (by agent name, the stable sort key) when resuming.
+/// Returns the page and, when results remain beyond it, a cursor encoding the
+/// filter and index epoch needed to resume from the last item returned.
+fn paginate(
+    agents: Vec<AgentMetadata>,
+    cursor: Option<&PageCursor>,
+    page_size: usize,
+    params: &AgentQueryParams,
+    epoch: u64,
+) -> (Vec<AgentMetadata>, Option<String>) {
+    let start = match cursor {
+        Some(c) => agents
+            .iter()
+            .position(|a| a.name == c.after)
+            .map(|i| i + 1)
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    let take = agents.len().saturating_sub(start).min(page_size);
+    let page: Vec<AgentMetadata> = agents[start..start + take].to_vec();
+
+    let next_cursor = if start + take < agents.len() {
+        page.last().map(|last| {
+            PageCursor {
+                after: last.name.clone(),
+                context: params.context.clone(),
+                role: params.role.clone(),
+                capability: params.capability.clone(),
+                epoch,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    (page, next_cursor)
+}
+
+/// Okapi BM25 term-frequency saturation parameter.
+const BM25_K1: f64 = 1.2;
+/// Okapi BM25 length-normalization parameter.
+const BM25_B: f64 = 0.75;
+
+/// Split text into lowercased alphanumeric tokens on word boundaries.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Flatten an agent's searchable text (name, role, description, capabilities)
+/// into a single token bag used for both document length and term frequency.
+fn agent_tokens(agent: &AgentMetadata) -> Vec<String> {
+    let mut text = format!("{} {} {}", agent.name, agent.role, agent.description);
+    for cap in &agent.capabilities {
+        text.push(' ');
+        text.push_str(cap);
+    }
+    tokenize(&text)
+}
+
+/// Score each agent against the free-text `query` with Okapi BM25, in input
+/// order. Also returns the distinct query terms that matched at least one
+/// agent. An empty query or candidate set yields all-zero scores.
+fn bm25_scores(query: &str, agents: &[AgentMetadata]) -> (Vec<f64>, Vec<String>) {
+    let mut terms = tokenize(query);
+    terms.sort();
+    terms.dedup();
+
+    if terms.is_empty() || agents.is_empty() {
+        return (vec![0.0; agents.len()], Vec::new());
+    }
+
+    // Tokenize each candidate once.
+    let docs: Vec<Vec<String>> = agents.iter().map(agent_tokens).collect();
+    let n = docs.len() as f64;
+    let total_len: usize = docs.iter().map(|d| d.len()).sum();
+    let avgdl = (total_len as f64 / n).max(1.0);
+
+    // IDF per query term over the candidate set.
+    let mut idf = std::collections::HashMap::new();
+    for t in &terms {
+        let n_t = docs.iter().filter(|d| d.contains(t)).count() as f64;
+        idf.insert(t.as_str(), ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln());
+    }
+
+    let mut matched_all: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let scores = docs
+        .iter()
+        .map(|doc| {
+            let dl = doc.len() as f64;
+            let mut score = 0.0;
+            for t in &terms {
+                let f = doc.iter().filter(|w| w.as_str() == t.as_str()).count() as f64;
+                if f > 0.0 {
+                    matched_all.insert(t.clone());
+                    let norm = f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl);
+                    score += idf[t.as_str()] * (f * (BM25_K1 + 1.0)) / norm;
+                }
+            }
+            score
+        })
+        .collect();
+
+    let mut matched: Vec<String> = matched_all.into_iter().collect();
+    matched.sort();
+    (scores, matched)
+}
+
+/// Rank `agents` against the free-text `query` with Okapi BM25. Returns the
+/// agents reordered by descending relevance, the distinct query terms that
+/// matched at least one agent, and a confidence in `[0, 1)` derived from the
+/// top document score.
+fn rank_agents(query: &str, agents: Vec<AgentMetadata>) -> (Vec<AgentMetadata>, Vec<String>, f64) {
+    let (scores, matched) = bm25_scores(query, &agents);
+
+    let mut scored: Vec<(f64, AgentMetadata)> = scores.into_iter().zip(agents).collect();
+    // Highest score first; preserve input order among equals.
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let top_score = scored.first().map(|(s, _)| *s).unwrap_or(0.0);
+    let confidence = if top_score > 0.0 {
+        top_score / (top_score + 1.0)
+    } else {
+        0.0
+    };
+
+    let ranked = scored.into_iter().map(|(_, a)| a).collect();
+    (ranked, matched, confidence)
+}
+
+/// Dimensionality of the hashed bag-of-words embedding. Fixed so every vector
+/// is directly comparable without an external model.
+const EMBED_DIM: usize = 256;
+/// Weight of the lexical (BM25) component when blending in `hybrid` mode; the
+/// semantic cosine component takes the remainder.
+const HYBRID_ALPHA: f64 = 0.5;
+
+/// Embed text into a fixed-dimension, L2-normalized vector using the signed
+/// hashing trick over its tokens. Deterministic and dependency-free.
+fn embed(text: &str) -> Vec<f32> {
+    use std::hash::{Hash, Hasher};
+
+    let mut v = vec![0.0f32; EMBED_DIM];
+    for token in tokenize(text) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        token.hash(&mut hasher);
+        let h = hasher.finish();
+        let idx = (h % EMBED_DIM as u64) as usize;
+        let sign = if (h >> 63) & 1 == 0 { 1.0 } else { -1.0 };
+        v[idx] += sign;
+    }
+
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v.iter_mut() {
+            *x /= norm;
         }
     }
+    v
+}
+
+/// The text used to embed an agent: name, description, and capabilities.
+fn agent_embed_text(agent: &AgentMetadata) -> String {
+    let mut s = format!("{} {}", agent.name, agent.description);
+    for cap in &agent.capabilities {
+        s.push(' ');
+        s.push_str(cap);
+    }
+    s
+}
+
+/// Cosine similarity of two L2-normalized vectors (their dot product).
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
 }
 
 /// Get current process memory usage in MB
@@ -0,0 +1,199 @@
+/*!
+ * Prometheus-style operational metrics for the MCP handler.
+ *
+ * [`handle_health`](crate::mcp::McpHandler) reports a point-in-time snapshot;
+ * this registry accumulates counters and latency histograms over the life of
+ * the process so a `metrics` method can render them in Prometheus text format
+ * for standard scraping.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bucket bounds (milliseconds) shared by every latency histogram.
+const LATENCY_BUCKETS_MS: &[f64] = &[
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+/// A fixed-bucket latency histogram tracking per-bucket counts, the running
+/// sum, and the total observation count.
+#[derive(Debug, Clone)]
+struct Histogram {
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            counts: vec![0; LATENCY_BUCKETS_MS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value_ms: f64) {
+        self.sum += value_ms;
+        self.count += 1;
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            if value_ms <= *bound {
+                self.counts[i] += 1;
+                break;
+            }
+        }
+    }
+
+    /// Render this histogram as a Prometheus metric family. `labels` is the
+    /// optional label set to apply to every series (without the `le` label).
+    fn render(&self, name: &str, labels: &str, out: &mut String) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0u64;
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            cumulative += self.counts[i];
+            out.push_str(&format!(
+                "{name}_bucket{{{labels}{sep}le=\"{bound}\"}} {cumulative}\n"
+            ));
+        }
+        out.push_str(&format!(
+            "{name}_bucket{{{labels}{sep}le=\"+Inf\"}} {count}\n",
+            count = self.count
+        ));
+        out.push_str(&format!("{name}_sum{{{labels}}} {sum}\n", sum = self.sum));
+        out.push_str(&format!(
+            "{name}_count{{{labels}}} {count}\n",
+            count = self.count
+        ));
+    }
+}
+
+struct MetricsInner {
+    method_counts: BTreeMap<String, u64>,
+    method_latency: BTreeMap<String, Histogram>,
+    error_counts: BTreeMap<i32, u64>,
+    jit_load: Histogram,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+/// Shared, thread-safe metrics registry held by the handler.
+pub struct MetricsRegistry {
+    inner: Mutex<MetricsInner>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(MetricsInner {
+                method_counts: BTreeMap::new(),
+                method_latency: BTreeMap::new(),
+                error_counts: BTreeMap::new(),
+                jit_load: Histogram::new(),
+                cache_hits: 0,
+                cache_misses: 0,
+            }),
+        }
+    }
+
+    /// Record a dispatched request: its method, service latency, and error
+    /// code when the call failed.
+    pub fn record_request(&self, method: &str, duration: Duration, error_code: Option<i32>) {
+        let ms = duration.as_secs_f64() * 1000.0;
+        let mut inner = self.inner.lock().unwrap();
+        *inner.method_counts.entry(method.to_string()).or_insert(0) += 1;
+        inner
+            .method_latency
+            .entry(method.to_string())
+            .or_insert_with(Histogram::new)
+            .observe(ms);
+        if let Some(code) = error_code {
+            *inner.error_counts.entry(code).or_insert(0) += 1;
+        }
+    }
+
+    /// Record a just-in-time agent definition load.
+    pub fn record_jit_load(&self, duration: Duration) {
+        let ms = duration.as_secs_f64() * 1000.0;
+        self.inner.lock().unwrap().jit_load.observe(ms);
+    }
+
+    /// Record a cache lookup outcome.
+    pub fn record_cache(&self, hit: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        if hit {
+            inner.cache_hits += 1;
+        } else {
+            inner.cache_misses += 1;
+        }
+    }
+
+    /// Render the full registry in Prometheus text exposition format.
+    pub fn render(&self, uptime_secs: f64, definition_cache_entries: usize) -> String {
+        let inner = self.inner.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP orchestr8_requests_total Total requests per method.\n");
+        out.push_str("# TYPE orchestr8_requests_total counter\n");
+        for (method, count) in &inner.method_counts {
+            out.push_str(&format!(
+                "orchestr8_requests_total{{method=\"{method}\"}} {count}\n"
+            ));
+        }
+
+        out.push_str("# HELP orchestr8_request_duration_ms Request latency per method.\n");
+        out.push_str("# TYPE orchestr8_request_duration_ms histogram\n");
+        for (method, hist) in &inner.method_latency {
+            hist.render(
+                "orchestr8_request_duration_ms",
+                &format!("method=\"{method}\""),
+                &mut out,
+            );
+        }
+
+        out.push_str("# HELP orchestr8_errors_total Errors per JSON-RPC error code.\n");
+        out.push_str("# TYPE orchestr8_errors_total counter\n");
+        for (code, count) in &inner.error_counts {
+            out.push_str(&format!(
+                "orchestr8_errors_total{{code=\"{code}\"}} {count}\n"
+            ));
+        }
+
+        out.push_str("# HELP orchestr8_jit_load_duration_ms Agent definition JIT load latency.\n");
+        out.push_str("# TYPE orchestr8_jit_load_duration_ms histogram\n");
+        inner
+            .jit_load
+            .render("orchestr8_jit_load_duration_ms", "", &mut out);
+
+        out.push_str("# HELP orchestr8_cache_hits_total Cache hits.\n");
+        out.push_str("# TYPE orchestr8_cache_hits_total counter\n");
+        out.push_str(&format!(
+            "orchestr8_cache_hits_total {}\n",
+            inner.cache_hits
+        ));
+        out.push_str("# HELP orchestr8_cache_misses_total Cache misses.\n");
+        out.push_str("# TYPE orchestr8_cache_misses_total counter\n");
+        out.push_str(&format!(
+            "orchestr8_cache_misses_total {}\n",
+            inner.cache_misses
+        ));
+
+        out.push_str("# HELP orchestr8_definition_cache_entries In-memory definition cache occupancy.\n");
+        out.push_str("# TYPE orchestr8_definition_cache_entries gauge\n");
+        out.push_str(&format!(
+            "orchestr8_definition_cache_entries {definition_cache_entries}\n"
+        ));
+
+        out.push_str("# HELP orchestr8_uptime_seconds Process uptime.\n");
+        out.push_str("# TYPE orchestr8_uptime_seconds gauge\n");
+        out.push_str(&format!("orchestr8_uptime_seconds {uptime_secs}\n"));
+
+        out
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,191 @@
+/*!
+ * Persistent SQLite-backed cache tier.
+ *
+ * The in-memory [`QueryCache`](crate::cache::QueryCache) and the definition
+ * LRU are lost on restart, so `agents/query` and `agents/get_definition` cold
+ * start every time the process comes up. This module backs those caches with a
+ * SQLite table so warm entries survive a restart, honoring per-entry TTL and
+ * LRU-by-last-access eviction.
+ */
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Logical partition within the shared cache table. Lets query results and
+/// agent definitions share one table without key collisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheTier {
+    Query,
+    Definition,
+}
+
+impl CacheTier {
+    fn as_str(self) -> &'static str {
+        match self {
+            CacheTier::Query => "query",
+            CacheTier::Definition => "definition",
+        }
+    }
+}
+
+/// On-disk cache backend. Shared behind an `Arc` by the handler.
+pub struct PersistentCache {
+    conn: Mutex<Connection>,
+    max_entries: usize,
+    default_ttl_secs: i64,
+    disk_hits: AtomicU64,
+    disk_misses: AtomicU64,
+}
+
+impl PersistentCache {
+    /// Open (or create) the cache database at `path`. `max_entries` bounds the
+    /// on-disk row count; `default_ttl_secs` is used when a `put` does not
+    /// specify its own TTL.
+    pub fn open(path: &str, max_entries: usize, default_ttl_secs: i64) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS query_cache (
+                tier        TEXT    NOT NULL,
+                key         TEXT    NOT NULL,
+                payload     TEXT    NOT NULL,
+                inserted_at INTEGER NOT NULL,
+                ttl_secs    INTEGER NOT NULL,
+                last_access INTEGER NOT NULL,
+                PRIMARY KEY (tier, key)
+            );",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_entries,
+            default_ttl_secs,
+            disk_hits: AtomicU64::new(0),
+            disk_misses: AtomicU64::new(0),
+        })
+    }
+
+    /// Look up a cached payload, honoring TTL expiry. A hit touches the row's
+    /// last-access time so eviction favors genuinely cold entries.
+    pub fn get(&self, tier: CacheTier, key: &str) -> Option<Value> {
+        let now = unix_now();
+        let conn = self.conn.lock().unwrap();
+
+        let row: Option<(String, i64, i64)> = conn
+            .query_row(
+                "SELECT payload, inserted_at, ttl_secs FROM query_cache
+                 WHERE tier = ?1 AND key = ?2",
+                params![tier.as_str(), key],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .ok();
+
+        match row {
+            Some((payload, inserted_at, ttl_secs)) => {
+                if ttl_secs > 0 && now - inserted_at >= ttl_secs {
+                    // Expired: drop it and report a miss.
+                    let _ = conn.execute(
+                        "DELETE FROM query_cache WHERE tier = ?1 AND key = ?2",
+                        params![tier.as_str(), key],
+                    );
+                    self.disk_misses.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+
+                let _ = conn.execute(
+                    "UPDATE query_cache SET last_access = ?3
+                     WHERE tier = ?1 AND key = ?2",
+                    params![tier.as_str(), key, now],
+                );
+                self.disk_hits.fetch_add(1, Ordering::Relaxed);
+                serde_json::from_str(&payload).ok()
+            }
+            None => {
+                self.disk_misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Write (or replace) a payload. `ttl_secs` of `None` uses the configured
+    /// default; a non-positive TTL means the entry never expires.
+    pub fn put(&self, tier: CacheTier, key: &str, payload: &Value, ttl_secs: Option<i64>) {
+        let now = unix_now();
+        let ttl = ttl_secs.unwrap_or(self.default_ttl_secs);
+        let serialized = match serde_json::to_string(payload) {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute(
+            "INSERT INTO query_cache (tier, key, payload, inserted_at, ttl_secs, last_access)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?4)
+             ON CONFLICT (tier, key) DO UPDATE SET
+                payload = excluded.payload,
+                inserted_at = excluded.inserted_at,
+                ttl_secs = excluded.ttl_secs,
+                last_access = excluded.last_access",
+            params![tier.as_str(), key, serialized, now, ttl],
+        );
+
+        self.evict_locked(&conn);
+    }
+
+    /// Purge every tier.
+    pub fn clear(&self) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute("DELETE FROM query_cache", []);
+    }
+
+    /// Disk hit/miss counters and current row count.
+    pub fn stats(&self) -> DiskCacheStats {
+        let size = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row("SELECT COUNT(*) FROM query_cache", [], |r| r.get::<_, i64>(0))
+                .unwrap_or(0) as u64
+        };
+        DiskCacheStats {
+            hits: self.disk_hits.load(Ordering::Relaxed),
+            misses: self.disk_misses.load(Ordering::Relaxed),
+            size,
+        }
+    }
+
+    /// Trim the table back to `max_entries`, dropping least-recently-accessed
+    /// rows first. Assumes the caller holds the connection lock.
+    fn evict_locked(&self, conn: &Connection) {
+        let count = conn
+            .query_row("SELECT COUNT(*) FROM query_cache", [], |r| r.get::<_, i64>(0))
+            .unwrap_or(0) as usize;
+
+        if count > self.max_entries {
+            let overflow = (count - self.max_entries) as i64;
+            let _ = conn.execute(
+                "DELETE FROM query_cache WHERE rowid IN (
+                    SELECT rowid FROM query_cache ORDER BY last_access ASC LIMIT ?1
+                 )",
+                params![overflow],
+            );
+        }
+    }
+}
+
+/// Snapshot of the on-disk cache counters.
+#[derive(Debug, Clone, Copy)]
+pub struct DiskCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: u64,
+}
+
+/// Seconds since the Unix epoch, saturating at 0 on a pre-epoch clock.
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
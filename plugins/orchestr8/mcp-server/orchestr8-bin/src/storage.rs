@@ -0,0 +1,234 @@
+/*!
+ * Pluggable agent-metadata storage.
+ *
+ * The resource and discovery handlers used to reach straight into a single
+ * concrete [`Database`](crate::db::Database), which wired the MCP layer to one
+ * backend and made handler tests require a real SQLite file. This module hoists
+ * the handful of operations the handler actually needs behind a [`Storage`]
+ * trait so the server can run against any backend: a zero-setup in-memory store
+ * for development and tests, or the persistent SQLite store in production.
+ *
+ * The concrete backends live behind feature flags (`memory-storage`,
+ * `sqlite-storage`) so a build only pulls in what it uses. The
+ * [`storage_migrate`](bin/storage_migrate.rs) binary copies metadata from one
+ * `Storage` to another for moving between them.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use crate::loader::AgentMetadata;
+use crate::mcp::AgentQueryParams;
+#[cfg(feature = "sqlite-storage")]
+use crate::db::Database;
+
+/// Backend-agnostic access to agent metadata. Only the operations the MCP
+/// handler performs are exposed; full definitions are still loaded lazily from
+/// disk by the [`AgentLoader`](crate::loader::AgentLoader) using the path this
+/// trait resolves.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Candidates matching a discovery query, already filtered and capped by
+    /// `params.limit`. Ranking happens in the handler.
+    async fn query_agents(&self, params: &AgentQueryParams) -> Result<Vec<AgentMetadata>>;
+
+    /// On-disk path of an agent's definition file, for JIT loading.
+    async fn get_agent_file_path(&self, name: &str) -> Result<PathBuf>;
+
+    /// Every agent's metadata, in no particular order.
+    async fn list_agents(&self) -> Result<Vec<AgentMetadata>>;
+
+    /// Number of indexed agents.
+    async fn count_agents(&self) -> Result<usize>;
+
+    /// Approximate on-disk footprint of the backend in megabytes. In-memory
+    /// backends report `0.0`.
+    async fn get_size_mb(&self) -> Result<f64>;
+
+    /// Import agent metadata (with each agent's definition file path) into the
+    /// backend, used as a migration destination. Defaults to rejecting the
+    /// import so read-only backends don't silently drop data; writable stores
+    /// override it.
+    async fn import_agents(&self, _entries: Vec<(AgentMetadata, PathBuf)>) -> Result<()> {
+        anyhow::bail!("storage backend is read-only")
+    }
+}
+
+/// Open a [`Storage`] backend from a `scheme:detail` spec: `sqlite:<path>` or
+/// `memory`. Used by the `storage_migrate` tool; only schemes whose feature is
+/// enabled are available.
+pub fn open_storage(spec: &str) -> Result<Box<dyn Storage>> {
+    match spec {
+        "memory" => {
+            #[cfg(feature = "memory-storage")]
+            {
+                Ok(Box::new(InMemoryStorage::new([])))
+            }
+            #[cfg(not(feature = "memory-storage"))]
+            {
+                anyhow::bail!("memory backend requires the `memory-storage` feature")
+            }
+        }
+        _ => match spec.split_once(':') {
+            #[cfg(feature = "sqlite-storage")]
+            Some(("sqlite", path)) => Ok(Box::new(Database::open(path)?)),
+            _ => anyhow::bail!(
+                "unsupported storage spec: {spec} (expected `sqlite:<path>` or `memory`)"
+            ),
+        },
+    }
+}
+
+/// In-memory agent store. Holds the metadata and file paths in maps, so it
+/// needs no filesystem or database and is cheap to build from a fixture —
+/// ideal for unit-testing handlers against a known agent set.
+#[cfg(feature = "memory-storage")]
+pub struct InMemoryStorage {
+    inner: std::sync::Mutex<MemoryState>,
+}
+
+#[cfg(feature = "memory-storage")]
+#[derive(Default)]
+struct MemoryState {
+    agents: Vec<AgentMetadata>,
+    paths: std::collections::HashMap<String, PathBuf>,
+}
+
+#[cfg(feature = "memory-storage")]
+impl InMemoryStorage {
+    /// Build a store from agent metadata paired with each agent's definition
+    /// file path.
+    pub fn new(entries: impl IntoIterator<Item = (AgentMetadata, PathBuf)>) -> Self {
+        let mut state = MemoryState::default();
+        for (agent, path) in entries {
+            state.put(agent, path);
+        }
+        Self {
+            inner: std::sync::Mutex::new(state),
+        }
+    }
+
+    /// Insert or replace a single agent.
+    pub fn insert(&self, agent: AgentMetadata, path: PathBuf) {
+        self.inner.lock().unwrap().put(agent, path);
+    }
+}
+
+#[cfg(feature = "memory-storage")]
+impl MemoryState {
+    fn put(&mut self, agent: AgentMetadata, path: PathBuf) {
+        self.paths.insert(agent.name.clone(), path);
+        if let Some(existing) = self.agents.iter_mut().find(|a| a.name == agent.name) {
+            *existing = agent;
+        } else {
+            self.agents.push(agent);
+        }
+    }
+}
+
+#[cfg(feature = "memory-storage")]
+#[async_trait]
+impl Storage for InMemoryStorage {
+    async fn query_agents(&self, params: &AgentQueryParams) -> Result<Vec<AgentMetadata>> {
+        // Mirror the SQLite backend's coarse filtering: match role/capability
+        // substrings, then apply the limit. Relevance ranking is the handler's
+        // job, so order is left as-is here.
+        let matches = |a: &AgentMetadata| {
+            let role_ok = params
+                .role
+                .as_deref()
+                .map_or(true, |r| a.role.to_lowercase().contains(&r.to_lowercase()));
+            let cap_ok = params.capability.as_deref().map_or(true, |c| {
+                let c = c.to_lowercase();
+                a.capabilities.iter().any(|x| x.to_lowercase().contains(&c))
+            });
+            role_ok && cap_ok
+        };
+
+        let limit = params.limit.unwrap_or(usize::MAX);
+        let state = self.inner.lock().unwrap();
+        Ok(state
+            .agents
+            .iter()
+            .filter(|a| matches(a))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_agent_file_path(&self, name: &str) -> Result<PathBuf> {
+        self.inner
+            .lock()
+            .unwrap()
+            .paths
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Agent not found: {}", name))
+    }
+
+    async fn list_agents(&self) -> Result<Vec<AgentMetadata>> {
+        Ok(self.inner.lock().unwrap().agents.clone())
+    }
+
+    async fn count_agents(&self) -> Result<usize> {
+        Ok(self.inner.lock().unwrap().agents.len())
+    }
+
+    async fn get_size_mb(&self) -> Result<f64> {
+        Ok(0.0)
+    }
+
+    async fn import_agents(&self, entries: Vec<(AgentMetadata, PathBuf)>) -> Result<()> {
+        let mut state = self.inner.lock().unwrap();
+        for (agent, path) in entries {
+            state.put(agent, path);
+        }
+        Ok(())
+    }
+}
+
+/// SQLite/on-disk backend: the production store. The existing
+/// [`Database`](crate::db::Database) already implements the indexing and query
+/// logic, so it implements [`Storage`] directly — no adapter needed. This is
+/// the default backend for [`McpHandler`](crate::mcp::McpHandler).
+#[cfg(feature = "sqlite-storage")]
+#[async_trait]
+impl Storage for crate::db::Database {
+    async fn query_agents(&self, params: &AgentQueryParams) -> Result<Vec<AgentMetadata>> {
+        Database::query_agents(self, params)
+    }
+
+    async fn get_agent_file_path(&self, name: &str) -> Result<PathBuf> {
+        Database::get_agent_file_path(self, name)
+    }
+
+    async fn list_agents(&self) -> Result<Vec<AgentMetadata>> {
+        Database::query_agents(
+            self,
+            &AgentQueryParams {
+                context: None,
+                role: None,
+                capability: None,
+                limit: Some(usize::MAX),
+                cursor: None,
+                page_size: None,
+            },
+        )
+    }
+
+    async fn count_agents(&self) -> Result<usize> {
+        Ok(self.list_agents().await?.len())
+    }
+
+    async fn get_size_mb(&self) -> Result<f64> {
+        Database::get_size_mb(self)
+    }
+
+    async fn import_agents(&self, entries: Vec<(AgentMetadata, PathBuf)>) -> Result<()> {
+        for (agent, path) in entries {
+            self.upsert_agent(&agent, &path)?;
+        }
+        Ok(())
+    }
+}